@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::CrossPayError;
+use crate::iso::{ISO_3166_ALPHA3, ISO_4217};
+use crate::state::FeeRounding;
+
 // ========================================
 // PDA SEEDS
 // ========================================
@@ -16,6 +20,81 @@ pub const WITHDRAWAL_REQUEST_SEED: &[u8] = b"withdrawal_request";
 /// Seed for LiquidityProvider PDA
 pub const LIQUIDITY_PROVIDER_SEED: &[u8] = b"liquidity_provider";
 
+/// Seed for the singleton PlatformConfig PDA
+pub const PLATFORM_CONFIG_SEED: &[u8] = b"platform_config";
+
+/// Seed for the per-mint vault holding large transfers during cool-off
+pub const HOLD_VAULT_SEED: &[u8] = b"hold_vault";
+
+/// Seed for ComplianceOfficer PDA
+pub const COMPLIANCE_OFFICER_SEED: &[u8] = b"compliance_officer";
+
+/// Seed for ComplianceHold PDA
+pub const COMPLIANCE_HOLD_SEED: &[u8] = b"compliance_hold";
+
+/// Seed for Corridor PDA (sender country, receiver country)
+pub const CORRIDOR_SEED: &[u8] = b"corridor";
+
+/// Seed for DeniedAddress PDA (one per blocked pubkey)
+pub const DENIED_ADDRESS_SEED: &[u8] = b"denied_address";
+
+/// Seed for the singleton FeeSchedule PDA
+pub const FEE_SCHEDULE_SEED: &[u8] = b"fee_schedule";
+
+/// Seed for MintFeeConfig PDA (fee floor, ceiling and rounding per mint)
+pub const MINT_FEE_CONFIG_SEED: &[u8] = b"mint_fee_config";
+
+/// Seed for the per-mint vault funding referral rewards
+pub const REFERRAL_VAULT_SEED: &[u8] = b"referral_vault";
+
+/// Seed for ReferralRewards PDA (referrer, mint)
+pub const REFERRAL_REWARDS_SEED: &[u8] = b"referral_rewards";
+
+/// Seed for a liquidity provider's quote on a withdrawal
+pub const QUOTE_SEED: &[u8] = b"quote";
+
+/// Seed for one provider's portion of a partially filled withdrawal
+pub const WITHDRAWAL_FILL_SEED: &[u8] = b"withdrawal_fill";
+
+/// Seed for the reference exchange rate of a currency pair
+pub const REFERENCE_RATE_SEED: &[u8] = b"reference_rate";
+
+/// Seed for BatchSummary PDA
+pub const BATCH_SUMMARY_SEED: &[u8] = b"batch_summary";
+
+/// Seed for PaymentSchedule PDA
+pub const PAYMENT_SCHEDULE_SEED: &[u8] = b"payment_schedule";
+
+/// Seed for the per-sender delegate PDA that spends scheduled payments
+pub const SCHEDULE_DELEGATE_SEED: &[u8] = b"schedule_delegate";
+
+/// Seed for PaymentStream PDA
+pub const PAYMENT_STREAM_SEED: &[u8] = b"payment_stream";
+
+/// Seed for the escrow token account holding a stream's funds
+pub const STREAM_ESCROW_SEED: &[u8] = b"stream_escrow";
+
+/// Seed for Contract PDA
+pub const CONTRACT_SEED: &[u8] = b"contract";
+
+/// Seed for the escrow token account holding a contract's funds
+pub const CONTRACT_ESCROW_SEED: &[u8] = b"contract_escrow";
+
+/// Seed for Invoice PDA
+pub const INVOICE_SEED: &[u8] = b"invoice";
+
+/// Seed for TransferRefund PDA
+pub const TRANSFER_REFUND_SEED: &[u8] = b"transfer_refund";
+
+/// Seed for ClaimableTransfer PDA
+pub const CLAIMABLE_TRANSFER_SEED: &[u8] = b"claimable_transfer";
+
+/// Seed for the escrow token account holding a claimable transfer's funds
+pub const CLAIMABLE_ESCROW_SEED: &[u8] = b"claimable_escrow";
+
+/// Seed for ClaimCommitment PDA
+pub const CLAIM_COMMITMENT_SEED: &[u8] = b"claim_commitment";
+
 // ========================================
 // TRUST SCORE CONFIGURATION
 // ========================================
@@ -41,6 +120,94 @@ pub const MAX_COUNTRY_CODE_LEN: usize = 3;
 /// Maximum length for location string (e.g., "Lagos, Nigeria")
 pub const MAX_LOCATION_LEN: usize = 50;
 
+// ========================================
+// HOLD CONFIGURATION
+// ========================================
+
+/// Maximum cool-off period for large transfers (7 days)
+pub const MAX_HOLD_PERIOD: i64 = 7 * 86_400;
+
+// ========================================
+// RFQ AND ROUTING CONFIGURATION
+// ========================================
+
+/// Maximum length of a withdrawal's quote window (1 day)
+pub const MAX_QUOTE_WINDOW: i64 = 86_400;
+
+/// Maximum number of candidate providers route_withdrawal will compare
+pub const MAX_ROUTE_CANDIDATES: usize = 10;
+
+/// Maximum number of providers a single withdrawal can be split across
+pub const MAX_WITHDRAWAL_FILLS: u8 = 5;
+
+/// How long a provider's acceptance holds a withdrawal before it reopens (30 minutes)
+pub const WITHDRAWAL_LOCK_PERIOD: i64 = 30 * 60;
+
+/// How long a provider has to settle its fill before it can be cancelled (1 day)
+pub const WITHDRAWAL_FILL_PERIOD: i64 = 86_400;
+
+// ========================================
+// REFERENCE RATE CONFIGURATION
+// ========================================
+
+/// Currency provider exchange rates are quoted against (tokens are USD stablecoins)
+pub const REFERENCE_BASE_CURRENCY: [u8; 3] = *b"USD";
+
+// ========================================
+// CORRIDOR CONFIGURATION
+// ========================================
+
+/// Length of the window used for corridor daily caps
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+// ========================================
+// TRAVEL RULE CONFIGURATION
+// ========================================
+
+/// Maximum length of the pointer to an encrypted travel-rule payload
+pub const MAX_TRAVEL_RULE_URI_LEN: usize = 128;
+
+// ========================================
+// BATCH CONFIGURATION
+// ========================================
+
+/// Maximum number of receivers paid in a single batch transfer
+/// Each leg needs 5 remaining accounts on top of 15 fixed ones (program included),
+/// and a transaction can lock at most 64 accounts even through an address lookup
+/// table, so only 9 legs fit. Payrolls of 30+ payees must be split across batches.
+pub const MAX_BATCH_SIZE: usize = 9;
+
+// ========================================
+// SPLIT CONFIGURATION
+// ========================================
+
+/// Maximum number of receivers sharing a single split transfer
+pub const MAX_SPLIT_RECEIVERS: usize = 5;
+
+// ========================================
+// SCHEDULE CONFIGURATION
+// ========================================
+
+/// Minimum interval between scheduled payments (1 day)
+pub const MIN_SCHEDULE_INTERVAL: i64 = 86_400;
+
+// ========================================
+// CONTRACT CONFIGURATION
+// ========================================
+
+/// Maximum number of milestones in a single contract
+pub const MAX_MILESTONES: usize = 10;
+
+// ========================================
+// CLAIMABLE TRANSFER CONFIGURATION
+// ========================================
+
+/// Maximum length of the secret revealed by claim_transfer
+pub const MAX_CLAIM_SECRET_LEN: usize = 64;
+
+/// Minimum length of a claim secret, so its hash cannot be brute-forced
+pub const MIN_CLAIM_SECRET_LEN: usize = 16;
+
 // ========================================
 // FEE CONFIGURATION
 // ========================================
@@ -52,6 +219,23 @@ pub const PLATFORM_FEE_BPS: u16 = 50;
 /// Platform fee recipient pubkey (your treasury address)
 pub const PLATFORM_FEE_RECIPIENT: Pubkey = pubkey!("3NPHMMM5dNde1ZV8VPkFQrd2TEPH6n9WTyPt3AyvfxdD");
 
+/// Rounding applied to platform fees when a mint has no fee config
+/// Rounding up means any non-zero rate charges at least one base unit.
+pub const DEFAULT_FEE_ROUNDING: FeeRounding = FeeRounding::Up;
+
+/// Maximum number of volume-based fee tiers
+pub const MAX_FEE_TIERS: usize = 5;
+
+/// Window over which a sender's volume is counted for fee tiers (30 days)
+/// The window restarts with the first send after it lapses.
+pub const ROLLING_VOLUME_WINDOW: i64 = 30 * 86_400;
+
+/// Maximum provider or off-ramp fee on a withdrawal (10%)
+pub const MAX_WITHDRAWAL_FEE_BPS: u16 = 1_000;
+
+/// Maximum flat provider fee on a withdrawal (10 tokens at 6 decimals)
+pub const MAX_WITHDRAWAL_FIXED_FEE: u64 = 10_000_000;
+
 /// Fee divisor for basis points calculation
 /// To calculate fee: (amount * fee_bps) / BASIS_POINTS_DIVISOR
 pub const BASIS_POINTS_DIVISOR: u64 = 10_000;
@@ -62,22 +246,129 @@ pub const BASIS_POINTS_DIVISOR: u64 = 10_000;
 
 /// Calculate platform fee for a given amount
 /// Returns the fee amount in the same units as the input
-pub fn calculate_platform_fee(amount: u64) -> u64 {
-    amount
-        .checked_mul(PLATFORM_FEE_BPS as u64)
-        .unwrap_or(0)
-        .checked_div(BASIS_POINTS_DIVISOR)
-        .unwrap_or(0)
+pub fn calculate_platform_fee(amount: u64) -> Result<u64> {
+    calculate_fee_at_bps(amount, PLATFORM_FEE_BPS, DEFAULT_FEE_ROUNDING)
+}
+
+/// Calculate the fee for `amount` at an explicit basis-point rate
+/// Intermediate math is done in u128 and rounded as requested.
+pub fn calculate_fee_at_bps(amount: u64, fee_bps: u16, rounding: FeeRounding) -> Result<u64> {
+    let numerator = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    let divisor = BASIS_POINTS_DIVISOR as u128;
+
+    let fee = match rounding {
+        FeeRounding::Down => numerator / divisor,
+        FeeRounding::Up => numerator.div_ceil(divisor),
+        FeeRounding::HalfUp => (numerator + divisor / 2) / divisor,
+    };
+
+    u64::try_from(fee).map_err(|_| error!(CrossPayError::ArithmeticOverflow))
+}
+
+/// Calculate a fee at `fee_bps`, then clamp it to `[min_fee, max_fee]`
+/// A `max_fee` of 0 means uncapped. Fails if the floor exceeds `amount`.
+pub fn calculate_bounded_fee(
+    amount: u64,
+    fee_bps: u16,
+    rounding: FeeRounding,
+    min_fee: u64,
+    max_fee: u64,
+) -> Result<u64> {
+    let mut fee = calculate_fee_at_bps(amount, fee_bps, rounding)?.max(min_fee);
+    if max_fee > 0 {
+        fee = fee.min(max_fee);
+    }
+
+    require!(fee <= amount, CrossPayError::AmountBelowMinimumFee);
+
+    Ok(fee)
 }
 
 /// Calculate net amount after deducting platform fee
-pub fn calculate_net_amount(amount: u64) -> u64 {
-    amount.saturating_sub(calculate_platform_fee(amount))
+pub fn calculate_net_amount(amount: u64) -> Result<u64> {
+    amount
+        .checked_sub(calculate_platform_fee(amount)?)
+        .ok_or(error!(CrossPayError::ArithmeticOverflow))
 }
 
-/// Validate country code length
+/// Calculate the provider and platform fees on a withdrawal of `amount`
+/// Returns `(provider_fee, platform_fee)`; fails if together they exceed the amount
+pub fn calculate_withdrawal_fees(
+    amount: u64,
+    provider_fee_bps: u16,
+    provider_fixed_fee: u64,
+    offramp_fee_bps: u16,
+) -> Result<(u64, u64)> {
+    let provider_fee = calculate_fee_at_bps(amount, provider_fee_bps, DEFAULT_FEE_ROUNDING)?
+        .checked_add(provider_fixed_fee)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    let platform_fee = calculate_fee_at_bps(amount, offramp_fee_bps, DEFAULT_FEE_ROUNDING)?;
+
+    let total_fee = provider_fee
+        .checked_add(platform_fee)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    require!(total_fee <= amount, CrossPayError::InvalidWithdrawalFee);
+
+    Ok((provider_fee, platform_fee))
+}
+
+/// Local-currency payout for `net_amount` tokens at a 10^6-scaled exchange rate
+/// Kept unscaled in u128 so quotes can be compared without rounding.
+pub fn calculate_fiat_payout(net_amount: u64, exchange_rate: u64) -> u128 {
+    net_amount as u128 * exchange_rate as u128
+}
+
+/// Whether `rate` is within `max_deviation_bps` of `reference` in either direction
+pub fn rate_within_band(rate: u64, reference: u64, max_deviation_bps: u16) -> bool {
+    let deviation = rate.abs_diff(reference) as u128 * BASIS_POINTS_DIVISOR as u128;
+    deviation <= reference as u128 * max_deviation_bps as u128
+}
+
+/// Split `net_amount` by basis-point shares, rounding each share down
+/// Rounding dust is added to the first share so the parts always sum to
+/// `net_amount`. Returns None if the shares don't sum to 10000.
+pub fn calculate_split_amounts(net_amount: u64, shares_bps: &[u16]) -> Option<Vec<u64>> {
+    let total_bps: u64 = shares_bps.iter().map(|bps| *bps as u64).sum();
+    if total_bps != BASIS_POINTS_DIVISOR {
+        return None;
+    }
+
+    let mut amounts: Vec<u64> = shares_bps
+        .iter()
+        .map(|bps| ((net_amount as u128 * *bps as u128) / BASIS_POINTS_DIVISOR as u128) as u64)
+        .collect();
+    let distributed: u64 = amounts.iter().sum();
+    amounts[0] += net_amount - distributed;
+
+    Some(amounts)
+}
+
+/// Calculate how much of a stream has vested at `now`
+/// Vesting is linear per second between `start_time` and `end_time`
+pub fn calculate_vested_amount(total: u64, start_time: i64, end_time: i64, now: i64) -> u64 {
+    if now <= start_time {
+        return 0;
+    }
+    if now >= end_time || end_time <= start_time {
+        return total;
+    }
+
+    let elapsed = (now - start_time) as u128;
+    let duration = (end_time - start_time) as u128;
+    // elapsed < duration, so the result always fits back into a u64
+    ((total as u128 * elapsed) / duration) as u64
+}
+
+/// Validate an ISO 3166-1 alpha-3 country code (uppercase, e.g. "NGA")
 pub fn is_valid_country_code(code: &str) -> bool {
-    !code.is_empty() && code.len() <= MAX_COUNTRY_CODE_LEN
+    ISO_3166_ALPHA3.binary_search(&code).is_ok()
+}
+
+/// Validate an ISO 4217 currency code (uppercase, e.g. "NGN")
+pub fn is_valid_currency_code(code: &str) -> bool {
+    ISO_4217.binary_search(&code).is_ok()
 }
 
 /// Validate location string length
@@ -93,7 +384,7 @@ mod tests {
     fn test_calculate_platform_fee() {
         // Test with 1000 USDC (1000 * 10^6)
         let amount = 1_000_000_000;
-        let fee = calculate_platform_fee(amount);
+        let fee = calculate_platform_fee(amount).unwrap();
         // 0.5% of 1000 = 5 USDC = 5 * 10^6 = 5,000,000
         assert_eq!(fee, 5_000_000);
     }
@@ -101,19 +392,160 @@ mod tests {
     #[test]
     fn test_calculate_net_amount() {
         let amount = 1_000_000_000;
-        let net = calculate_net_amount(amount);
+        let net = calculate_net_amount(amount).unwrap();
         // 1000 - 5 = 995 USDC
         assert_eq!(net, 995_000_000);
     }
 
+    #[test]
+    fn test_calculate_fee_at_bps() {
+        let amount = 1_000_000_000;
+        // Zero-fee corridor promo
+        assert_eq!(calculate_fee_at_bps(amount, 0, FeeRounding::Up).unwrap(), 0);
+        // 1% corridor override
+        assert_eq!(
+            calculate_fee_at_bps(amount, 100, FeeRounding::Down).unwrap(),
+            10_000_000
+        );
+        // No overflow at the top of the u64 range
+        assert_eq!(
+            calculate_fee_at_bps(u64::MAX, 10_000, FeeRounding::Down).unwrap(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_fee_rounding() {
+        // 150 * 50 bps = 0.75 base units
+        assert_eq!(calculate_fee_at_bps(150, 50, FeeRounding::Down).unwrap(), 0);
+        assert_eq!(calculate_fee_at_bps(150, 50, FeeRounding::Up).unwrap(), 1);
+        assert_eq!(calculate_fee_at_bps(150, 50, FeeRounding::HalfUp).unwrap(), 1);
+        // 50 * 50 bps = 0.25 base units
+        assert_eq!(calculate_fee_at_bps(50, 50, FeeRounding::HalfUp).unwrap(), 0);
+        // Tiny transfers no longer go fee-free by default
+        assert_eq!(calculate_platform_fee(1).unwrap(), 1);
+        assert_eq!(calculate_net_amount(1).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_calculate_bounded_fee() {
+        // Floor lifts small fees
+        assert_eq!(
+            calculate_bounded_fee(1_000, 50, FeeRounding::Down, 100, 0).unwrap(),
+            100
+        );
+        // Ceiling caps large fees
+        assert_eq!(
+            calculate_bounded_fee(1_000_000_000, 50, FeeRounding::Down, 0, 1_000_000).unwrap(),
+            1_000_000
+        );
+        // Zero max means uncapped
+        assert_eq!(
+            calculate_bounded_fee(1_000_000_000, 50, FeeRounding::Down, 0, 0).unwrap(),
+            5_000_000
+        );
+        // Floor above the amount itself is rejected
+        assert!(calculate_bounded_fee(50, 50, FeeRounding::Down, 100, 0).is_err());
+    }
+
+    #[test]
+    fn test_calculate_withdrawal_fees() {
+        // 1% + 2 USDC fixed to the provider, 0.5% to the platform
+        assert_eq!(
+            calculate_withdrawal_fees(100_000_000, 100, 2_000_000, 50).unwrap(),
+            (3_000_000, 500_000)
+        );
+        assert_eq!(calculate_withdrawal_fees(1_000, 0, 0, 0).unwrap(), (0, 0));
+        // Fixed fee larger than the withdrawal is rejected
+        assert!(calculate_withdrawal_fees(1_000, 0, 1_001, 0).is_err());
+    }
+
+    #[test]
+    fn test_calculate_fiat_payout() {
+        // 49.5 USDC at 1500 NGN/USDC
+        assert_eq!(
+            calculate_fiat_payout(49_500_000, 1_500_000_000),
+            74_250 * 10u128.pow(12)
+        );
+        // A better rate can outweigh a higher fee
+        let (cheap_fee, _) = calculate_withdrawal_fees(100_000_000, 50, 0, 0).unwrap();
+        let (dear_fee, _) = calculate_withdrawal_fees(100_000_000, 200, 0, 0).unwrap();
+        assert!(
+            calculate_fiat_payout(100_000_000 - dear_fee, 1_530_000_000)
+                > calculate_fiat_payout(100_000_000 - cheap_fee, 1_500_000_000)
+        );
+    }
+
+    #[test]
+    fn test_rate_within_band() {
+        // 2% band around 1500 NGN/USD
+        let reference = 1_500_000_000;
+        assert!(rate_within_band(1_530_000_000, reference, 200));
+        assert!(rate_within_band(1_470_000_000, reference, 200));
+        assert!(!rate_within_band(1_530_000_001, reference, 200));
+        assert!(!rate_within_band(1_000_000_000, reference, 200));
+        assert!(rate_within_band(u64::MAX, u64::MAX, 0));
+    }
+
+    #[test]
+    fn test_calculate_split_amounts() {
+        assert_eq!(
+            calculate_split_amounts(1_000, &[5000, 3000, 2000]),
+            Some(vec![500, 300, 200])
+        );
+        // 100 / 3 leaves 1 unit of dust, which goes to the first receiver
+        assert_eq!(
+            calculate_split_amounts(100, &[3334, 3333, 3333]),
+            Some(vec![34, 33, 33])
+        );
+        assert_eq!(calculate_split_amounts(100, &[5000, 4000]), None);
+        assert_eq!(calculate_split_amounts(100, &[]), None);
+    }
+
+    #[test]
+    fn test_calculate_vested_amount() {
+        let total = 1_000_000_000;
+        assert_eq!(calculate_vested_amount(total, 100, 200, 50), 0);
+        assert_eq!(calculate_vested_amount(total, 100, 200, 100), 0);
+        assert_eq!(calculate_vested_amount(total, 100, 200, 150), 500_000_000);
+        assert_eq!(calculate_vested_amount(total, 100, 200, 200), total);
+        assert_eq!(calculate_vested_amount(total, 100, 200, 500), total);
+        // Large amounts must not overflow the intermediate product
+        assert_eq!(calculate_vested_amount(u64::MAX, 0, 4, 2), u64::MAX / 2);
+    }
+
     #[test]
     fn test_country_code_validation() {
         assert!(is_valid_country_code("USA"));
-        assert!(is_valid_country_code("NG"));
+        assert!(is_valid_country_code("NGA"));
+        assert!(is_valid_country_code("KEN"));
+        assert!(!is_valid_country_code("NG"));
+        assert!(!is_valid_country_code("ZZ"));
+        assert!(!is_valid_country_code("ZZZ"));
+        assert!(!is_valid_country_code("123"));
+        assert!(!is_valid_country_code("nga"));
         assert!(!is_valid_country_code(""));
         assert!(!is_valid_country_code("USAA"));
     }
 
+    #[test]
+    fn test_currency_code_validation() {
+        assert!(is_valid_currency_code("NGN"));
+        assert!(is_valid_currency_code("KES"));
+        assert!(is_valid_currency_code("USD"));
+        assert!(!is_valid_currency_code("ngn"));
+        assert!(!is_valid_currency_code("XXX"));
+        assert!(!is_valid_currency_code("XAU"));
+        assert!(!is_valid_currency_code("US"));
+        assert!(!is_valid_currency_code(""));
+    }
+
+    #[test]
+    fn test_iso_tables_sorted() {
+        assert!(ISO_3166_ALPHA3.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ISO_4217.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
     #[test]
     fn test_location_validation() {
         assert!(is_valid_location("Lagos"));
@@ -11,7 +11,7 @@ pub enum CrossPayError {
     #[msg("Insufficient token balance")]
     InsufficientBalance, // 6002
 
-    #[msg("Invalid country code - must be 3 characters or less")]
+    #[msg("Invalid country code - must be an uppercase ISO 3166-1 alpha-3 code")]
     InvalidCountryCode, // 6003
 
     #[msg("Invalid transfer status for this operation")]
@@ -47,4 +47,241 @@ pub enum CrossPayError {
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
 
+    #[msg("Invalid batch size - must contain between 1 and 32 legs")]
+    InvalidBatchSize, // 6015
+
+    #[msg("Invalid receiver accounts supplied for batch leg")]
+    InvalidReceiverAccount, // 6016
+
+    #[msg("Invalid payment schedule - check interval and payment count")]
+    InvalidSchedule, // 6017
+
+    #[msg("Scheduled payment is not due yet")]
+    PaymentNotDue, // 6018
+
+    #[msg("Payment schedule has no remaining payments")]
+    ScheduleCompleted, // 6019
+
+    #[msg("Invalid stream window - end time must be after start time")]
+    InvalidStreamWindow, // 6020
+
+    #[msg("Invalid stream status for this operation")]
+    InvalidStreamStatus, // 6021
+
+    #[msg("Nothing has vested yet that can be withdrawn")]
+    NothingToWithdraw, // 6022
+
+    #[msg("Invalid milestones - must contain between 1 and 10 with matching descriptions")]
+    InvalidMilestones, // 6023
+
+    #[msg("Invalid milestone status for this operation")]
+    InvalidMilestoneStatus, // 6024
+
+    #[msg("Invalid contract status for this operation")]
+    InvalidContractStatus, // 6025
+
+    #[msg("Invalid arbiter - must differ from client and freelancer")]
+    InvalidArbiter, // 6026
+
+    #[msg("Invalid invoice status for this operation")]
+    InvalidInvoiceStatus, // 6027
+
+    #[msg("Payment exceeds the outstanding invoice balance")]
+    InvoiceOverpayment, // 6028
+
+    #[msg("Invoice is not past its due date")]
+    InvoiceNotOverdue, // 6029
+
+    #[msg("Invalid claim status for this operation")]
+    InvalidClaimStatus, // 6030
+
+    #[msg("Claim secret does not match the transfer")]
+    InvalidClaimSecret, // 6031
+
+    #[msg("Claimable transfer has expired")]
+    ClaimExpired, // 6032
+
+    #[msg("Claimable transfer has not expired yet")]
+    ClaimNotExpired, // 6033
+
+    #[msg("Invalid split - 2 to 5 distinct receivers whose shares sum to 10000 bps")]
+    InvalidSplit, // 6034
+
+    #[msg("Refund exceeds the amount received for this transfer")]
+    RefundExceedsTransfer, // 6035
+
+    #[msg("Invalid hold period - must be between 0 and 7 days")]
+    InvalidHoldPeriod, // 6036
+
+    #[msg("Hold vault is required for transfers above the threshold")]
+    HoldVaultRequired, // 6037
+
+    #[msg("Transfer is still within its cool-off window")]
+    HoldPeriodActive, // 6038
+
+    #[msg("Cool-off window for this transfer has ended")]
+    HoldPeriodEnded, // 6039
+
+    #[msg("Account is frozen by compliance")]
+    AccountFrozen, // 6040
+
+    #[msg("A compliance reason code is required")]
+    InvalidComplianceReason, // 6041
+
+    #[msg("Compliance hold is not active")]
+    ComplianceHoldNotActive, // 6042
+
+    #[msg("Transfers on this corridor are disabled")]
+    CorridorDisabled, // 6043
+
+    #[msg("Amount is outside this corridor's minimum and maximum")]
+    AmountOutsideCorridorLimits, // 6044
+
+    #[msg("Corridor daily cap exceeded")]
+    CorridorDailyCapExceeded, // 6045
+
+    #[msg("Invalid corridor configuration")]
+    InvalidCorridor, // 6046
+
+    #[msg("Invalid currency code - must be an ISO 4217 code")]
+    InvalidCurrencyCode, // 6047
+
+    #[msg("Provider does not pay out in the requested currency")]
+    CurrencyMismatch, // 6048
+
+    #[msg("Address is on the sanctions deny-list")]
+    SanctionedAddress, // 6049
+
+    #[msg("Travel-rule data is required for transfers of this size")]
+    TravelRuleDataRequired, // 6050
+
+    #[msg("Invalid travel-rule data - empty hash or pointer over 128 characters")]
+    InvalidTravelRuleData, // 6051
+
+    #[msg("Invalid fee tiers - 1 to 5 tiers starting at zero volume with increasing thresholds")]
+    InvalidFeeTiers, // 6052
+
+    #[msg("Amount is below the minimum fee for this mint")]
+    AmountBelowMinimumFee, // 6053
+
+    #[msg("Invalid mint fee config - minimum fee exceeds maximum")]
+    InvalidMintFeeConfig, // 6054
+
+    #[msg("Invalid referrer - must be another registered user")]
+    InvalidReferrer, // 6055
+
+    #[msg("Referral rewards account and vault are required for referred receivers")]
+    ReferralAccountsRequired, // 6056
+
+    #[msg("Invalid referral share - must be at most 10000 bps")]
+    InvalidReferralShare, // 6057
+
+    #[msg("Withdrawal fee exceeds the allowed maximum or the withdrawal amount")]
+    InvalidWithdrawalFee, // 6058
+
+    #[msg("The quote window for this withdrawal has closed")]
+    QuoteWindowClosed, // 6059
+
+    #[msg("The quote window for this withdrawal is still open")]
+    QuoteWindowOpen, // 6060
+
+    #[msg("Withdrawal is not open for quotes")]
+    NotRfqWithdrawal, // 6061
+
+    #[msg("Provider trust score is below the withdrawal's minimum")]
+    TrustScoreTooLow, // 6062
+
+    #[msg("Quote does not belong to this withdrawal or provider")]
+    InvalidQuote, // 6063
+
+    #[msg("No eligible quote was found")]
+    NoEligibleQuote, // 6064
+
+    #[msg("Invalid quote window")]
+    InvalidQuoteWindow, // 6065
+
+    #[msg("Invalid trust score - must be at most 10000")]
+    InvalidTrustScore, // 6066
+
+    #[msg("Withdrawal is open for quotes - accept a quote instead")]
+    RfqWithdrawal, // 6067
+
+    #[msg("Provider does not support this payout method")]
+    PayoutMethodNotSupported, // 6068
+
+    #[msg("At least one payout method is required")]
+    InvalidPayoutMethods, // 6069
+
+    #[msg("No eligible liquidity provider was found")]
+    NoEligibleProvider, // 6070
+
+    #[msg("Invalid liquidity provider account")]
+    InvalidProviderAccount, // 6071
+
+    #[msg("Fill amount must be positive and within the unfilled remainder")]
+    InvalidFillAmount, // 6072
+
+    #[msg("Too many fills - maximum 5 per withdrawal")]
+    TooManyFills, // 6073
+
+    #[msg("Withdrawal is locked by a provider that has not timed out")]
+    WithdrawalLocked, // 6074
+
+    #[msg("Provider payout after fees is below the withdrawal's limit")]
+    RateBelowLimit, // 6075
+
+    #[msg("Limit order has expired")]
+    LimitOrderExpired, // 6076
+
+    #[msg("Withdrawal is not a limit order")]
+    NotLimitOrder, // 6077
+
+    #[msg("Invalid limit order - expiry must be in the future and needs a target rate")]
+    InvalidLimitOrder, // 6078
+
+    #[msg("Limit order has not expired yet")]
+    LimitOrderNotExpired, // 6079
+
+    #[msg("Invalid reference rate configuration")]
+    InvalidReferenceRate, // 6080
+
+    #[msg("Reference rate is missing or stale")]
+    StaleReferenceRate, // 6081
+
+    #[msg("Exchange rate deviates too far from the reference rate")]
+    RateOutsideReferenceBand, // 6082
+
+    #[msg("Claim must be revealed in a later slot than its commitment")]
+    ClaimRevealTooEarly, // 6083
+
+    #[msg("Contract deadline must be in the future")]
+    InvalidContractDeadline, // 6084
+
+    #[msg("Contract deadline has not passed yet")]
+    ContractNotExpired, // 6085
+
+    #[msg("Amount is at or above the hold threshold - send it with initiate_transfer")]
+    HoldRequired, // 6086
+
+    #[msg("Net amount after fees is below the freelancer's minimum")]
+    NetAmountTooLow, // 6087
+
+    #[msg("Withdrawal fill has not timed out yet")]
+    FillNotExpired, // 6088
+
+    #[msg("Provider held the last lock on this withdrawal - another provider must accept it")]
+    RelockNotAllowed, // 6089
+
+    #[msg("Referrer has already claimed the reward being refunded")]
+    ReferralRewardClaimed, // 6090
+
+    #[msg("Freelancer has not opened this withdrawal to provider acceptance")]
+    ProviderAcceptanceDisabled, // 6091
+
+    #[msg("Contract deadline has passed")]
+    ContractExpired, // 6092
+
+    #[msg("Sender's token account no longer delegates to the payment schedule")]
+    ScheduleNotDelegated, // 6093
+
 }
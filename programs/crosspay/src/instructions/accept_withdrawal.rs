@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for a freelancer opening their withdrawal to provider acceptance
+#[derive(Accounts)]
+pub struct SetProviderAcceptance<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::Pending @ CrossPayError::InvalidWithdrawalStatus,
+        constraint = !withdrawal_request.is_rfq() @ CrossPayError::RfqWithdrawal,
+        has_one = freelancer
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    pub freelancer: Signer<'info>,
+}
+
+/// Let providers accept the withdrawal directly, or stop them from doing so
+pub fn set_provider_acceptance(ctx: Context<SetProviderAcceptance>, enabled: bool) -> Result<()> {
+    ctx.accounts.withdrawal_request.provider_accepts = enabled;
+
+    msg!("Provider acceptance set - Enabled: {}", enabled);
+
+    Ok(())
+}
+
+/// Context for a liquidity provider claiming an open withdrawal
+#[derive(Accounts)]
+pub struct AcceptWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = !withdrawal_request.is_rfq() @ CrossPayError::RfqWithdrawal,
+        constraint = withdrawal_request.provider_accepts @ CrossPayError::ProviderAcceptanceDisabled
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        seeds = [b"liquidity_provider", authority.key().as_ref()],
+        bump = liquidity_provider.bump,
+        has_one = authority,
+        constraint = liquidity_provider.is_active @ CrossPayError::ProviderNotActive,
+        constraint = !liquidity_provider.is_frozen @ CrossPayError::AccountFrozen,
+        constraint = liquidity_provider.currency == withdrawal_request.currency @ CrossPayError::CurrencyMismatch,
+        constraint = liquidity_provider.free_liquidity() >= withdrawal_request.amount @ CrossPayError::InsufficientLiquidity,
+        constraint = liquidity_provider.trust_score >= withdrawal_request.min_trust_score @ CrossPayError::TrustScoreTooLow,
+        constraint = liquidity_provider.supports(withdrawal_request.payout_method) @ CrossPayError::PayoutMethodNotSupported
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Reference rate PDA for the payout currency - may be unconfigured;
+    /// validated in ReferenceRate::load_pair
+    pub reference_rate: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Claim an open withdrawal at the provider's current rate and fees
+/// The first provider to accept holds it until the lock times out or the
+/// freelancer releases it; a lapsed lock can be taken over directly, but not
+/// by the provider that held it.
+pub fn accept_withdrawal(ctx: Context<AcceptWithdrawal>) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let liquidity_provider = &ctx.accounts.liquidity_provider;
+    let clock = Clock::get()?;
+
+    require!(
+        !withdrawal_request.order_expired(clock.unix_timestamp),
+        CrossPayError::LimitOrderExpired
+    );
+    if withdrawal_request.status != WithdrawalStatus::Pending {
+        require!(
+            withdrawal_request.lock_expired(clock.unix_timestamp),
+            CrossPayError::WithdrawalLocked
+        );
+    }
+    require!(
+        withdrawal_request.last_locker != Some(liquidity_provider.authority),
+        CrossPayError::RelockNotAllowed
+    );
+    if let Some(reference_rate) =
+        ReferenceRate::load_pair(&ctx.accounts.reference_rate, &withdrawal_request.currency)?
+    {
+        reference_rate.check_rate(liquidity_provider.exchange_rate, clock.unix_timestamp)?;
+    }
+
+    require!(
+        withdrawal_request.accepts_rate(
+            liquidity_provider.exchange_rate,
+            liquidity_provider.fee_bps,
+            liquidity_provider.fixed_fee,
+            ctx.accounts.platform_config.offramp_fee_bps,
+        )?,
+        CrossPayError::RateBelowLimit
+    );
+
+    withdrawal_request.select_provider(
+        liquidity_provider.authority,
+        liquidity_provider.exchange_rate,
+        liquidity_provider.fee_bps,
+        liquidity_provider.fixed_fee,
+        ctx.accounts.platform_config.offramp_fee_bps,
+    )?;
+    withdrawal_request.lock_expires_at = clock
+        .unix_timestamp
+        .checked_add(WITHDRAWAL_LOCK_PERIOD)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    withdrawal_request.last_locker = Some(liquidity_provider.authority);
+
+    msg!(
+        "Withdrawal accepted by {} until {} - Rate: {}, Net: {}",
+        liquidity_provider.authority,
+        withdrawal_request.lock_expires_at,
+        liquidity_provider.exchange_rate,
+        withdrawal_request.net_amount
+    );
+
+    Ok(())
+}
+
+/// Context for reopening a locked withdrawal - by its freelancer at any time, or
+/// by anyone once the lock has timed out
+#[derive(Accounts)]
+pub struct ReleaseWithdrawalLock<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::ProviderSelected @ CrossPayError::InvalidWithdrawalStatus,
+        constraint = withdrawal_request.lock_expires_at != 0 @ CrossPayError::InvalidWithdrawalStatus
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Return a locked withdrawal to the open pool
+pub fn release_withdrawal_lock(ctx: Context<ReleaseWithdrawalLock>) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let clock = Clock::get()?;
+
+    if ctx.accounts.authority.key() != withdrawal_request.freelancer {
+        require!(
+            withdrawal_request.lock_expired(clock.unix_timestamp),
+            CrossPayError::WithdrawalLocked
+        );
+    }
+
+    let provider = withdrawal_request.selected_provider.unwrap_or_default();
+    withdrawal_request.reopen();
+
+    msg!(
+        "Withdrawal lock by {} released - returned to the open pool",
+        provider
+    );
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for the platform admin appointing a compliance officer
+#[derive(Accounts)]
+#[instruction(officer: Pubkey)]
+pub struct AddComplianceOfficer<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ComplianceOfficer::LEN,
+        seeds = [b"compliance_officer", officer.as_ref()],
+        bump
+    )]
+    pub compliance_officer: Account<'info, ComplianceOfficer>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Appoint a compliance officer
+pub fn add_compliance_officer(ctx: Context<AddComplianceOfficer>, officer: Pubkey) -> Result<()> {
+    let compliance_officer = &mut ctx.accounts.compliance_officer;
+    let clock = Clock::get()?;
+
+    compliance_officer.officer = officer;
+    compliance_officer.appointed_by = ctx.accounts.admin.key();
+    compliance_officer.created_at = clock.unix_timestamp;
+    compliance_officer.bump = ctx.bumps.compliance_officer;
+
+    msg!("Compliance officer appointed: {}", officer);
+
+    Ok(())
+}
+
+/// Context for the platform admin removing a compliance officer
+#[derive(Accounts)]
+pub struct RemoveComplianceOfficer<'info> {
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"compliance_officer", compliance_officer.officer.as_ref()],
+        bump = compliance_officer.bump
+    )]
+    pub compliance_officer: Account<'info, ComplianceOfficer>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Revoke a compliance officer's appointment
+pub fn remove_compliance_officer(ctx: Context<RemoveComplianceOfficer>) -> Result<()> {
+    msg!(
+        "Compliance officer removed: {}",
+        ctx.accounts.compliance_officer.officer
+    );
+
+    Ok(())
+}
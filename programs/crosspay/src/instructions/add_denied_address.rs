@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for the platform admin adding an address to the deny-list
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct AddDeniedAddress<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = DeniedAddress::LEN,
+        seeds = [b"denied_address", address.as_ref()],
+        bump
+    )]
+    pub denied_address: Account<'info, DeniedAddress>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Block an address from sending, receiving or providing liquidity
+pub fn add_denied_address(
+    ctx: Context<AddDeniedAddress>,
+    address: Pubkey,
+    reason: ComplianceReason,
+) -> Result<()> {
+    require!(
+        reason != ComplianceReason::None,
+        CrossPayError::InvalidComplianceReason
+    );
+
+    let denied_address = &mut ctx.accounts.denied_address;
+    let clock = Clock::get()?;
+
+    denied_address.address = address;
+    denied_address.reason = reason;
+    denied_address.added_by = ctx.accounts.admin.key();
+    denied_address.created_at = clock.unix_timestamp;
+    denied_address.bump = ctx.bumps.denied_address;
+
+    msg!("Address added to deny-list: {}", address);
+
+    Ok(())
+}
+
+/// Context for the platform admin removing an address from the deny-list
+#[derive(Accounts)]
+pub struct RemoveDeniedAddress<'info> {
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"denied_address", denied_address.address.as_ref()],
+        bump = denied_address.bump
+    )]
+    pub denied_address: Account<'info, DeniedAddress>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Lift the block on an address
+pub fn remove_denied_address(ctx: Context<RemoveDeniedAddress>) -> Result<()> {
+    msg!(
+        "Address removed from deny-list: {}",
+        ctx.accounts.denied_address.address
+    );
+
+    Ok(())
+}
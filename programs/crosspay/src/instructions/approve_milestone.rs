@@ -0,0 +1,324 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::instructions::confirm_transfer::pay_referral_reward;
+use crate::state::*;
+
+/// Context for settling a milestone, shared by client approval and
+/// arbiter resolution
+#[derive(Accounts)]
+pub struct SettleMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"contract",
+            contract.client.as_ref(),
+            contract.freelancer.as_ref(),
+            &contract.nonce.to_le_bytes()
+        ],
+        bump = contract.bump,
+        constraint = contract.status == ContractStatus::Active @ CrossPayError::InvalidContractStatus
+    )]
+    pub contract: Account<'info, Contract>,
+
+    #[account(
+        mut,
+        seeds = [b"contract_escrow", contract.key().as_ref()],
+        bump
+    )]
+    pub contract_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", contract.client.as_ref()],
+        bump = client_profile.bump
+    )]
+    pub client_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", contract.freelancer.as_ref()],
+        bump = freelancer_profile.bump
+    )]
+    pub freelancer_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Deny-list PDA for the client - must not exist when the client is refunded
+    #[account(
+        seeds = [b"denied_address", contract.client.as_ref()],
+        bump
+    )]
+    pub client_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the freelancer - must not exist when the freelancer is paid
+    #[account(
+        seeds = [b"denied_address", contract.freelancer.as_ref()],
+        bump
+    )]
+    pub freelancer_denial: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = client_token_account.owner == contract.client,
+        constraint = client_token_account.mint == contract.mint
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = freelancer_token_account.owner == contract.freelancer,
+        constraint = freelancer_token_account.mint == contract.mint
+    )]
+    pub freelancer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == contract.mint
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Rewards PDA of the receiver's referrer - required when the receiver
+    /// was referred, may be unopened; validated in ReferralRewards::share_of
+    #[account(mut)]
+    pub referral_rewards: Option<UncheckedAccount<'info>>,
+
+    /// Only required when a referral reward is credited
+    #[account(
+        mut,
+        seeds = [b"referral_vault", contract.mint.as_ref()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> SettleMilestone<'info> {
+    /// Run `f` with the contract PDA's signer seeds, which own the escrow
+    fn with_escrow_signer<T>(&self, f: impl FnOnce(&[&[&[u8]]]) -> Result<T>) -> Result<T> {
+        let client_key = self.contract.client;
+        let freelancer_key = self.contract.freelancer;
+        let nonce_bytes = self.contract.nonce.to_le_bytes();
+        f(&[&[
+            b"contract",
+            client_key.as_ref(),
+            freelancer_key.as_ref(),
+            &nonce_bytes,
+            &[self.contract.bump],
+        ]])
+    }
+
+    /// Move tokens out of the contract escrow, signed by the contract PDA
+    fn pay_from_escrow(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.with_escrow_signer(|signer_seeds| {
+            let cpi_accounts = Transfer {
+                from: self.contract_escrow.to_account_info(),
+                to,
+                authority: self.contract.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, amount)
+        })
+    }
+
+    /// Release a milestone to the freelancer, less the platform fee
+    fn release(&mut self, index: usize) -> Result<()> {
+        require!(
+            self.freelancer_denial.data_is_empty(),
+            CrossPayError::SanctionedAddress
+        );
+        let amount = self.contract.milestones[index].amount;
+        self.platform_config
+            .check_travel_rule(amount, self.contract.travel_rule.as_ref())?;
+        let platform_fee = self.contract.milestones[index].platform_fee;
+        let net_amount = amount
+            .checked_sub(platform_fee)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        self.pay_from_escrow(self.freelancer_token_account.to_account_info(), net_amount)?;
+
+        // The freelancer's referrer shares in the fee; the treasury takes the rest
+        let referral_rewards = self.referral_rewards.as_ref().map(|info| info.to_account_info());
+        let referral_reward = ReferralRewards::share_of(
+            referral_rewards.as_ref(),
+            self.freelancer_profile.referrer,
+            &self.contract.mint,
+            platform_fee,
+            self.platform_config.referral_share_bps,
+        )?;
+        self.with_escrow_signer(|signer_seeds| {
+            pay_referral_reward(
+                referral_reward,
+                referral_rewards.as_ref(),
+                self.referral_vault.as_ref(),
+                self.contract_escrow.to_account_info(),
+                self.contract.to_account_info(),
+                self.token_program.to_account_info(),
+                signer_seeds,
+            )
+        })?;
+        let treasury_fee = platform_fee - referral_reward;
+        if treasury_fee > 0 {
+            self.pay_from_escrow(self.platform_fee_account.to_account_info(), treasury_fee)?;
+        }
+
+        self.contract.milestones[index].status = MilestoneStatus::Released;
+        self.contract.released_amount = self
+            .contract
+            .released_amount
+            .checked_add(amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        self.client_profile.record_sent(amount, Clock::get()?.unix_timestamp)?;
+        self.freelancer_profile.total_received = self
+            .freelancer_profile
+            .total_received
+            .checked_add(net_amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        msg!(
+            "Milestone {} released: {} tokens, {} received (fee: {})",
+            index,
+            amount,
+            net_amount,
+            platform_fee
+        );
+
+        Ok(())
+    }
+
+    /// Return a milestone's escrowed funds to the client
+    fn refund(&mut self, index: usize) -> Result<()> {
+        require!(
+            self.client_denial.data_is_empty(),
+            CrossPayError::SanctionedAddress
+        );
+        let amount = self.contract.milestones[index].amount;
+
+        self.pay_from_escrow(self.client_token_account.to_account_info(), amount)?;
+
+        self.contract.milestones[index].status = MilestoneStatus::Refunded;
+        self.contract.refunded_amount = self
+            .contract
+            .refunded_amount
+            .checked_add(amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        msg!("Milestone {} refunded: {} tokens", index, amount);
+
+        Ok(())
+    }
+
+    /// Close out the contract once every milestone is settled
+    fn complete_if_settled(&mut self) -> Result<()> {
+        if self.contract.is_settled() {
+            self.contract.status = ContractStatus::Completed;
+            self.contract.completed_at = Some(Clock::get()?.unix_timestamp);
+        }
+        Ok(())
+    }
+}
+
+/// Client approves a submitted milestone and releases its funds
+pub fn approve_milestone(ctx: Context<SettleMilestone>, index: u8) -> Result<()> {
+    let accounts = ctx.accounts;
+    let index = index as usize;
+
+    require_keys_eq!(
+        accounts.authority.key(),
+        accounts.contract.client,
+        CrossPayError::Unauthorized
+    );
+    let milestone = accounts
+        .contract
+        .milestones
+        .get(index)
+        .ok_or(CrossPayError::InvalidMilestones)?;
+    require!(
+        milestone.status == MilestoneStatus::Submitted,
+        CrossPayError::InvalidMilestoneStatus
+    );
+
+    accounts.release(index)?;
+    accounts.complete_if_settled()
+}
+
+/// Return an unsubmitted milestone's funds to the client
+/// The freelancer can decline a milestone at any time; the client can reclaim
+/// one once the contract deadline has passed without it being submitted.
+pub fn cancel_milestone(ctx: Context<SettleMilestone>, index: u8) -> Result<()> {
+    let accounts = ctx.accounts;
+    let index = index as usize;
+    let authority = accounts.authority.key();
+
+    if authority == accounts.contract.client {
+        require!(
+            Clock::get()?.unix_timestamp >= accounts.contract.deadline,
+            CrossPayError::ContractNotExpired
+        );
+    } else {
+        require_keys_eq!(
+            authority,
+            accounts.contract.freelancer,
+            CrossPayError::Unauthorized
+        );
+    }
+    let milestone = accounts
+        .contract
+        .milestones
+        .get(index)
+        .ok_or(CrossPayError::InvalidMilestones)?;
+    require!(
+        milestone.status == MilestoneStatus::Pending,
+        CrossPayError::InvalidMilestoneStatus
+    );
+
+    accounts.refund(index)?;
+    accounts.complete_if_settled()
+}
+
+/// Arbiter resolves a disputed milestone in favour of one party
+pub fn resolve_milestone(
+    ctx: Context<SettleMilestone>,
+    index: u8,
+    release_to_freelancer: bool,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    let index = index as usize;
+
+    require_keys_eq!(
+        accounts.authority.key(),
+        accounts.contract.arbiter,
+        CrossPayError::Unauthorized
+    );
+    let milestone = accounts
+        .contract
+        .milestones
+        .get(index)
+        .ok_or(CrossPayError::InvalidMilestones)?;
+    require!(
+        milestone.status == MilestoneStatus::Disputed,
+        CrossPayError::InvalidMilestoneStatus
+    );
+
+    if release_to_freelancer {
+        accounts.release(index)?;
+    } else {
+        accounts.refund(index)?;
+    }
+    accounts.complete_if_settled()
+}
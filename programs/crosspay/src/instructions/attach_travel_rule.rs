@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for the sender attaching travel-rule data to a pending transfer
+#[derive(Accounts)]
+pub struct AttachTravelRule<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"transfer_request",
+            transfer_request.sender.as_ref(),
+            transfer_request.receiver.as_ref(),
+            &transfer_request.nonce.to_le_bytes()
+        ],
+        bump = transfer_request.bump,
+        constraint = transfer_request.sender == authority.key() @ CrossPayError::Unauthorized,
+        constraint = transfer_request.status == TransferStatus::Pending @ CrossPayError::InvalidTransferStatus
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Record the hash of the originator/beneficiary payload and where its
+/// encrypted copy is stored. Replaces any previously attached data.
+pub fn attach_travel_rule(
+    ctx: Context<AttachTravelRule>,
+    data_hash: [u8; 32],
+    blob_uri: String,
+) -> Result<()> {
+    let travel_rule = TravelRuleRecord {
+        data_hash,
+        blob_uri,
+    };
+    travel_rule.validate()?;
+
+    let transfer_request = &mut ctx.accounts.transfer_request;
+
+    transfer_request.travel_rule = Some(travel_rule);
+
+    msg!(
+        "Travel-rule data attached to transfer of {} tokens",
+        transfer_request.amount
+    );
+
+    Ok(())
+}
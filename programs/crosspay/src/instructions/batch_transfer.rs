@@ -0,0 +1,327 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::instructions::confirm_transfer::{pay_referral_reward, pay_treasury_fee};
+use crate::state::*;
+
+/// Context for paying many receivers in a single instruction
+///
+/// Each leg is supplied through `remaining_accounts` as a group of
+/// `[receiver_profile (mut), receiver_token_account (mut), corridor (mut),
+/// receiver_denial, referral_rewards (mut)]`, in the same order as the `amounts`
+/// argument. `referral_rewards` is the receiver's referrer's rewards PDA and may
+/// be any account for receivers without a referrer.
+#[derive(Accounts)]
+pub struct BatchTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", sender.key().as_ref()],
+        bump = sender_profile.bump,
+        has_one = authority,
+        constraint = sender_profile.kyc_verified @ CrossPayError::KycNotVerified,
+        constraint = !sender_profile.is_frozen @ CrossPayError::AccountFrozen
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = BatchSummary::LEN,
+        seeds = [
+            b"batch_summary",
+            sender.key().as_ref(),
+            &sender_profile.transfer_nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub batch_summary: Account<'info, BatchSummary>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == authority.key(),
+        constraint = sender_token_account.mint == mint.key()
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Fee schedule PDA - may be unconfigured
+    #[account(
+        seeds = [b"fee_schedule"],
+        bump
+    )]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Fee config PDA for the mint - may be unconfigured
+    #[account(
+        seeds = [b"mint_fee_config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_fee_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == mint.key()
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
+    /// Only required when a leg credits a referral reward
+    #[account(
+        mut,
+        seeds = [b"referral_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", sender.key().as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Sender pubkey - used for PDA derivation
+    pub sender: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pay every receiver its net amount and record a batch summary
+/// `travel_rule` covers every leg and is required once any leg reaches the threshold.
+pub fn batch_transfer<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchTransfer<'info>>,
+    amounts: Vec<u64>,
+    travel_rule: Option<TravelRuleRecord>,
+) -> Result<()> {
+    require!(
+        !amounts.is_empty() && amounts.len() <= MAX_BATCH_SIZE,
+        CrossPayError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == amounts.len() * RECEIVER_LEG_ACCOUNTS,
+        CrossPayError::InvalidReceiverAccount
+    );
+
+    if let Some(travel_rule) = &travel_rule {
+        travel_rule.validate()?;
+    }
+
+    let mut total_amount: u64 = 0;
+    for amount in amounts.iter() {
+        require!(*amount > 0, CrossPayError::InvalidAmount);
+        ctx.accounts.platform_config.check_unheld(*amount)?;
+        ctx.accounts
+            .platform_config
+            .check_travel_rule(*amount, travel_rule.as_ref())?;
+        total_amount = total_amount
+            .checked_add(*amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+    }
+
+    require!(
+        ctx.accounts.sender_token_account.amount >= total_amount,
+        CrossPayError::InsufficientBalance
+    );
+
+    let clock = Clock::get()?;
+    let mint = ctx.accounts.mint.key();
+    let mut legs = Vec::with_capacity(amounts.len());
+    let mut total_net_amount: u64 = 0;
+    let mut total_fee: u64 = 0;
+    let mut total_referral_reward: u64 = 0;
+
+    // Fee tier follows the sender's volume before this batch
+    let fee_resolver = FeeResolver::load(
+        &ctx.accounts.fee_schedule,
+        &ctx.accounts.mint_fee_config,
+        ctx.accounts.sender_profile.volume_at(clock.unix_timestamp),
+    )?;
+
+    for (amount, accounts) in amounts
+        .iter()
+        .zip(ctx.remaining_accounts.chunks(RECEIVER_LEG_ACCOUNTS))
+    {
+        let ReceiverLeg {
+            profile: mut receiver_profile,
+            token_account: receiver_token_account,
+            corridor,
+            referral_rewards,
+        } = load_receiver_accounts(accounts, mint, &ctx.accounts.sender_profile, ctx.program_id)?;
+        let receiver = receiver_profile.authority;
+
+        let (_, platform_fee) = fee_resolver.resolve(Corridor::load(corridor)?.as_ref(), *amount)?;
+        let net_amount = amount
+            .checked_sub(platform_fee)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        Corridor::check_and_record(corridor, *amount, clock.unix_timestamp)?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.sender_token_account.to_account_info(),
+            to: receiver_token_account.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, net_amount)?;
+
+        let referral_reward = ReferralRewards::share_of(
+            Some(referral_rewards),
+            receiver_profile.referrer,
+            &mint,
+            platform_fee,
+            ctx.accounts.platform_config.referral_share_bps,
+        )?;
+        pay_referral_reward(
+            referral_reward,
+            Some(referral_rewards),
+            ctx.accounts.referral_vault.as_ref(),
+            ctx.accounts.sender_token_account.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            &[],
+        )?;
+
+        receiver_profile.total_received = receiver_profile
+            .total_received
+            .checked_add(net_amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        // Persist now so a receiver appearing in several legs sees the updated total
+        receiver_profile.exit(ctx.program_id)?;
+
+        total_net_amount = total_net_amount
+            .checked_add(net_amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        total_fee = total_fee
+            .checked_add(platform_fee)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        total_referral_reward = total_referral_reward
+            .checked_add(referral_reward)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        legs.push(BatchLeg {
+            receiver,
+            amount: *amount,
+            net_amount,
+            platform_fee,
+        });
+    }
+
+    // The treasury's part of every leg's fee goes over in one transfer
+    pay_treasury_fee(
+        total_fee,
+        total_referral_reward,
+        &ctx.accounts.platform_fee_account,
+        ctx.accounts.sender_token_account.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        &[],
+    )?;
+
+    let batch_summary = &mut ctx.accounts.batch_summary;
+
+    batch_summary.sender = ctx.accounts.sender.key();
+    batch_summary.mint = mint;
+    batch_summary.total_amount = total_amount;
+    batch_summary.total_net_amount = total_net_amount;
+    batch_summary.total_fee = total_fee;
+    batch_summary.legs = legs;
+    batch_summary.created_at = clock.unix_timestamp;
+    batch_summary.nonce = ctx.accounts.sender_profile.next_transfer_nonce()?;
+    batch_summary.bump = ctx.bumps.batch_summary;
+    batch_summary.travel_rule = travel_rule;
+
+    ctx.accounts.sender_profile.record_sent(total_amount, clock.unix_timestamp)?;
+
+    msg!(
+        "Batch transfer completed: {} legs, {} tokens sent, {} received (fee: {})",
+        batch_summary.legs.len(),
+        total_amount,
+        total_net_amount,
+        total_fee
+    );
+
+    Ok(())
+}
+
+/// Number of `remaining_accounts` supplied for each receiver
+pub(crate) const RECEIVER_LEG_ACCOUNTS: usize = 5;
+
+/// One receiver's accounts, loaded from `remaining_accounts`
+pub(crate) struct ReceiverLeg<'info> {
+    pub profile: Account<'info, UserProfile>,
+    pub token_account: Account<'info, TokenAccount>,
+    pub corridor: &'info AccountInfo<'info>,
+    pub referral_rewards: &'info AccountInfo<'info>,
+}
+
+/// Load and validate a `[receiver_profile, receiver_token_account, corridor,
+/// receiver_denial, referral_rewards]` group passed through `remaining_accounts`
+/// `referral_rewards` is only validated once a reward is priced against it.
+pub(crate) fn load_receiver_accounts<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    mint: Pubkey,
+    sender_profile: &Account<'info, UserProfile>,
+    program_id: &Pubkey,
+) -> Result<ReceiverLeg<'info>> {
+    let receiver_profile = Account::<UserProfile>::try_from(&accounts[0])?;
+    let receiver_token_account = Account::<TokenAccount>::try_from(&accounts[1])?;
+    let corridor = &accounts[2];
+    let receiver = receiver_profile.authority;
+
+    // The profile must be the canonical PDA of its authority
+    let expected_profile = Pubkey::create_program_address(
+        &[b"user_profile", receiver.as_ref(), &[receiver_profile.bump]],
+        program_id,
+    )
+    .map_err(|_| CrossPayError::InvalidReceiverAccount)?;
+    require_keys_eq!(
+        expected_profile,
+        receiver_profile.key(),
+        CrossPayError::InvalidReceiverAccount
+    );
+    // Paying yourself would clobber the sender profile written back at exit
+    require_keys_neq!(
+        receiver_profile.key(),
+        sender_profile.key(),
+        CrossPayError::InvalidReceiverAccount
+    );
+    require_keys_eq!(
+        receiver_token_account.owner,
+        receiver,
+        CrossPayError::InvalidReceiverAccount
+    );
+    require_keys_eq!(
+        receiver_token_account.mint,
+        mint,
+        CrossPayError::InvalidReceiverAccount
+    );
+    require_keys_eq!(
+        corridor.key(),
+        Corridor::address(&sender_profile.country_code, &receiver_profile.country_code),
+        CrossPayError::InvalidCorridor
+    );
+    require!(
+        DeniedAddress::is_clear(&accounts[3], &receiver),
+        CrossPayError::SanctionedAddress
+    );
+
+    Ok(ReceiverLeg {
+        profile: receiver_profile,
+        token_account: receiver_token_account,
+        corridor,
+        referral_rewards: &accounts[4],
+    })
+}
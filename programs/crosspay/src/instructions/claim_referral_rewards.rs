@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for a referrer claiming their accrued rewards
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"referral_rewards",
+            referral_rewards.referrer.as_ref(),
+            referral_rewards.mint.as_ref()
+        ],
+        bump = referral_rewards.bump,
+        constraint = referral_rewards.referrer == referrer.key() @ CrossPayError::Unauthorized
+    )]
+    pub referral_rewards: Account<'info, ReferralRewards>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"referral_vault", referral_rewards.mint.as_ref()],
+        bump
+    )]
+    pub referral_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = referrer_token_account.owner == referrer.key(),
+        constraint = referrer_token_account.mint == referral_rewards.mint
+    )]
+    pub referrer_token_account: Account<'info, TokenAccount>,
+
+    pub referrer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pay out the referrer's whole claimable balance
+pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    let referral_rewards = &mut ctx.accounts.referral_rewards;
+    let amount = referral_rewards.claimable;
+
+    require!(amount > 0, CrossPayError::NothingToWithdraw);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"platform_config", &[ctx.accounts.platform_config.bump]]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.referral_vault.to_account_info(),
+        to: ctx.accounts.referrer_token_account.to_account_info(),
+        authority: ctx.accounts.platform_config.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    referral_rewards.claimable = 0;
+
+    msg!(
+        "Referral rewards claimed: {} tokens by {}",
+        amount,
+        referral_rewards.referrer
+    );
+
+    Ok(())
+}
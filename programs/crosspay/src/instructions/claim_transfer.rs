@@ -0,0 +1,320 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use solana_sha256_hasher::{hash, hashv};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::instructions::confirm_transfer::pay_referral_reward;
+use crate::state::*;
+
+/// Context for a recipient committing to a claimable transfer's secret
+#[derive(Accounts)]
+pub struct CommitClaim<'info> {
+    #[account(
+        seeds = [
+            b"claimable_transfer",
+            claimable_transfer.sender.as_ref(),
+            &claimable_transfer.nonce.to_le_bytes()
+        ],
+        bump = claimable_transfer.bump,
+        constraint = claimable_transfer.status == ClaimStatus::Pending @ CrossPayError::InvalidClaimStatus,
+        constraint = claimable_transfer.sender != authority.key() @ CrossPayError::Unauthorized
+    )]
+    pub claimable_transfer: Account<'info, ClaimableTransfer>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ClaimCommitment::LEN,
+        seeds = [
+            b"claim_commitment",
+            claimable_transfer.key().as_ref(),
+            authority.key().as_ref()
+        ],
+        bump
+    )]
+    pub claim_commitment: Account<'info, ClaimCommitment>,
+
+    /// The recipient must have onboarded before committing
+    #[account(
+        seeds = [b"user_profile", authority.key().as_ref()],
+        bump = receiver_profile.bump,
+        has_one = authority
+    )]
+    pub receiver_profile: Account<'info, UserProfile>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Commit to sha256(secret || claimant) ahead of revealing the secret
+/// The secret only appears on-chain in claim_transfer, where it is bound to the
+/// committed claimant, so a copied reveal cannot be replayed by another signer.
+pub fn commit_claim(ctx: Context<CommitClaim>, commitment: [u8; 32]) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp < ctx.accounts.claimable_transfer.expires_at,
+        CrossPayError::ClaimExpired
+    );
+
+    let claim_commitment = &mut ctx.accounts.claim_commitment;
+
+    claim_commitment.claimable_transfer = ctx.accounts.claimable_transfer.key();
+    claim_commitment.claimant = ctx.accounts.authority.key();
+    claim_commitment.commitment = commitment;
+    claim_commitment.slot = clock.slot;
+    claim_commitment.bump = ctx.bumps.claim_commitment;
+
+    msg!(
+        "Claim committed by {} at slot {}",
+        claim_commitment.claimant,
+        claim_commitment.slot
+    );
+
+    Ok(())
+}
+
+/// Context for claiming an escrowed transfer with its secret
+#[derive(Accounts)]
+pub struct ClaimTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"claimable_transfer",
+            claimable_transfer.sender.as_ref(),
+            &claimable_transfer.nonce.to_le_bytes()
+        ],
+        bump = claimable_transfer.bump,
+        constraint = claimable_transfer.status == ClaimStatus::Pending @ CrossPayError::InvalidClaimStatus
+    )]
+    pub claimable_transfer: Account<'info, ClaimableTransfer>,
+
+    #[account(
+        mut,
+        seeds = [b"claimable_escrow", claimable_transfer.key().as_ref()],
+        bump
+    )]
+    pub claimable_escrow: Account<'info, TokenAccount>,
+
+    /// Only the signer who committed beforehand can reveal
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"claim_commitment",
+            claimable_transfer.key().as_ref(),
+            authority.key().as_ref()
+        ],
+        bump = claim_commitment.bump,
+        constraint = claim_commitment.claimant == authority.key() @ CrossPayError::Unauthorized
+    )]
+    pub claim_commitment: Account<'info, ClaimCommitment>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", claimable_transfer.sender.as_ref()],
+        bump = sender_profile.bump
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    /// The recipient must have onboarded before claiming
+    #[account(
+        mut,
+        seeds = [b"user_profile", authority.key().as_ref()],
+        bump = receiver_profile.bump,
+        has_one = authority
+    )]
+    pub receiver_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Corridor PDA for the sender and claimant countries - may be unconfigured
+    /// The claimant is unknown until now, so the corridor is charged at claim time.
+    #[account(
+        mut,
+        seeds = [
+            b"corridor",
+            sender_profile.country_code.as_bytes(),
+            receiver_profile.country_code.as_bytes()
+        ],
+        bump
+    )]
+    pub corridor: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", claimable_transfer.sender.as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the claimant - must not exist
+    #[account(
+        seeds = [b"denied_address", authority.key().as_ref()],
+        bump,
+        constraint = receiver_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub receiver_denial: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == authority.key(),
+        constraint = receiver_token_account.mint == claimable_transfer.mint
+    )]
+    pub receiver_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == claimable_transfer.mint
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Rewards PDA of the receiver's referrer - required when the receiver
+    /// was referred, may be unopened; validated in ReferralRewards::share_of
+    #[account(mut)]
+    pub referral_rewards: Option<UncheckedAccount<'info>>,
+
+    /// Only required when a referral reward is credited
+    #[account(
+        mut,
+        seeds = [b"referral_vault", claimable_transfer.mint.as_ref()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Claim an escrowed transfer by revealing the preimage of its claim hash
+/// The reveal must match the signer's commitment from an earlier slot.
+pub fn claim_transfer(ctx: Context<ClaimTransfer>, secret: Vec<u8>) -> Result<()> {
+    let claimable_transfer = &mut ctx.accounts.claimable_transfer;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp < claimable_transfer.expires_at,
+        CrossPayError::ClaimExpired
+    );
+    require!(
+        secret.len() >= MIN_CLAIM_SECRET_LEN && secret.len() <= MAX_CLAIM_SECRET_LEN,
+        CrossPayError::InvalidClaimSecret
+    );
+    require!(
+        clock.slot > ctx.accounts.claim_commitment.slot,
+        CrossPayError::ClaimRevealTooEarly
+    );
+    require!(
+        hashv(&[&secret, ctx.accounts.authority.key().as_ref()]).to_bytes()
+            == ctx.accounts.claim_commitment.commitment,
+        CrossPayError::InvalidClaimSecret
+    );
+    require!(
+        hash(&secret).to_bytes() == claimable_transfer.claim_hash,
+        CrossPayError::InvalidClaimSecret
+    );
+    require_keys_neq!(
+        ctx.accounts.authority.key(),
+        claimable_transfer.sender,
+        CrossPayError::Unauthorized
+    );
+    Corridor::check_and_record(
+        &ctx.accounts.corridor,
+        claimable_transfer.amount,
+        clock.unix_timestamp,
+    )?;
+    ctx.accounts.platform_config.check_travel_rule(
+        claimable_transfer.amount,
+        claimable_transfer.travel_rule.as_ref(),
+    )?;
+
+    let sender_key = claimable_transfer.sender;
+    let nonce_bytes = claimable_transfer.nonce.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"claimable_transfer",
+        sender_key.as_ref(),
+        &nonce_bytes,
+        &[claimable_transfer.bump],
+    ]];
+
+    // Transfer net amount to the claimant
+    let receiver_cpi_accounts = Transfer {
+        from: ctx.accounts.claimable_escrow.to_account_info(),
+        to: ctx.accounts.receiver_token_account.to_account_info(),
+        authority: claimable_transfer.to_account_info(),
+    };
+    let receiver_cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        receiver_cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(receiver_cpi_ctx, claimable_transfer.net_amount)?;
+
+    // The claimant's referrer shares in the fee; the treasury takes the rest
+    let referral_rewards = ctx.accounts.referral_rewards.as_ref().map(|info| info.as_ref());
+    let referral_reward = ReferralRewards::share_of(
+        referral_rewards,
+        ctx.accounts.receiver_profile.referrer,
+        &claimable_transfer.mint,
+        claimable_transfer.platform_fee,
+        ctx.accounts.platform_config.referral_share_bps,
+    )?;
+    pay_referral_reward(
+        referral_reward,
+        referral_rewards,
+        ctx.accounts.referral_vault.as_ref(),
+        ctx.accounts.claimable_escrow.to_account_info(),
+        claimable_transfer.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+    let treasury_fee = claimable_transfer.platform_fee - referral_reward;
+
+    // Transfer the rest of the platform fee to the treasury (only if > 0)
+    if treasury_fee > 0 {
+        let fee_cpi_accounts = Transfer {
+            from: ctx.accounts.claimable_escrow.to_account_info(),
+            to: ctx.accounts.platform_fee_account.to_account_info(),
+            authority: claimable_transfer.to_account_info(),
+        };
+        let fee_cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            fee_cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(fee_cpi_ctx, treasury_fee)?;
+    }
+
+    claimable_transfer.status = ClaimStatus::Claimed;
+    claimable_transfer.claimed_by = Some(ctx.accounts.authority.key());
+    claimable_transfer.completed_at = Some(clock.unix_timestamp);
+
+    ctx.accounts.sender_profile.record_sent(claimable_transfer.amount, clock.unix_timestamp)?;
+
+    ctx.accounts.receiver_profile.total_received = ctx
+        .accounts
+        .receiver_profile
+        .total_received
+        .checked_add(claimable_transfer.net_amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    msg!(
+        "Transfer claimed by {}: {} received (fee: {})",
+        ctx.accounts.authority.key(),
+        claimable_transfer.net_amount,
+        claimable_transfer.platform_fee
+    );
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::instructions::batch_transfer::{
+    load_receiver_accounts, ReceiverLeg, RECEIVER_LEG_ACCOUNTS,
+};
+use crate::instructions::confirm_transfer::{pay_referral_reward, pay_treasury_fee};
+use crate::state::*;
+
+/// Context for confirming a split transfer
+///
+/// Each split is supplied through `remaining_accounts` as a group of
+/// `[receiver_profile (mut), receiver_token_account (mut), corridor (mut),
+/// receiver_denial, referral_rewards (mut)]`, in the same order as
+/// `transfer_request.splits`.
+#[derive(Accounts)]
+pub struct ConfirmSplitTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"transfer_request",
+            transfer_request.sender.as_ref(),
+            transfer_request.receiver.as_ref(),
+            &transfer_request.nonce.to_le_bytes()
+        ],
+        bump = transfer_request.bump,
+        constraint = transfer_request.status == TransferStatus::Pending @ CrossPayError::InvalidTransferStatus,
+        constraint = !transfer_request.splits.is_empty() @ CrossPayError::InvalidSplit
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", sender.key().as_ref()],
+        bump = sender_profile.bump,
+        has_one = authority,
+        constraint = !sender_profile.is_frozen @ CrossPayError::AccountFrozen
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == authority.key(),
+        constraint = sender_token_account.mint == transfer_request.mint
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == transfer_request.mint
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
+    /// Only required when a leg credits a referral reward
+    #[account(
+        mut,
+        seeds = [b"referral_vault", transfer_request.mint.as_ref()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", sender.key().as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Validated via seeds in sender_profile
+    pub sender: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Confirm a split transfer and pay every receiver its share
+pub fn confirm_split_transfer<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ConfirmSplitTransfer<'info>>,
+) -> Result<()> {
+    let transfer_request = &mut ctx.accounts.transfer_request;
+    let clock = Clock::get()?;
+
+    require!(
+        ctx.accounts.sender_token_account.amount >= transfer_request.amount,
+        CrossPayError::InsufficientBalance
+    );
+    ctx.accounts
+        .platform_config
+        .check_unheld(transfer_request.amount)?;
+    // Fee was priced at initiation; only check that the amounts add up
+    require!(
+        transfer_request.net_amount.checked_add(transfer_request.platform_fee)
+            == Some(transfer_request.amount),
+        CrossPayError::InvalidFeeCalculation
+    );
+    ctx.accounts
+        .platform_config
+        .check_travel_rule(transfer_request.amount, transfer_request.travel_rule.as_ref())?;
+    require!(
+        ctx.remaining_accounts.len() == transfer_request.splits.len() * RECEIVER_LEG_ACCOUNTS,
+        CrossPayError::InvalidReceiverAccount
+    );
+
+    let shares_bps: Vec<u16> = transfer_request
+        .splits
+        .iter()
+        .map(|split| split.share_bps)
+        .collect();
+    let split_amounts = calculate_split_amounts(transfer_request.net_amount, &shares_bps)
+        .ok_or(CrossPayError::InvalidSplit)?;
+    // Corridors are charged each receiver's share of the gross amount
+    let gross_split_amounts = calculate_split_amounts(transfer_request.amount, &shares_bps)
+        .ok_or(CrossPayError::InvalidSplit)?;
+    let mut total_referral_reward: u64 = 0;
+
+    for (((split, split_amount), gross_split_amount), accounts) in transfer_request
+        .splits
+        .iter()
+        .zip(split_amounts.iter())
+        .zip(gross_split_amounts.iter())
+        .zip(ctx.remaining_accounts.chunks(RECEIVER_LEG_ACCOUNTS))
+    {
+        let ReceiverLeg {
+            profile: mut receiver_profile,
+            token_account: receiver_token_account,
+            corridor,
+            referral_rewards,
+        } = load_receiver_accounts(
+            accounts,
+            transfer_request.mint,
+            &ctx.accounts.sender_profile,
+            ctx.program_id,
+        )?;
+        require_keys_eq!(
+            receiver_profile.authority,
+            split.receiver,
+            CrossPayError::InvalidReceiverAccount
+        );
+
+        Corridor::check_and_record(corridor, *gross_split_amount, clock.unix_timestamp)?;
+
+        if *split_amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.sender_token_account.to_account_info(),
+                to: receiver_token_account.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, *split_amount)?;
+        }
+
+        // The receiver's referrer shares in the fee taken from this receiver's split
+        let split_fee = gross_split_amount.saturating_sub(*split_amount);
+        let referral_reward = ReferralRewards::share_of(
+            Some(referral_rewards),
+            receiver_profile.referrer,
+            &transfer_request.mint,
+            split_fee,
+            ctx.accounts.platform_config.referral_share_bps,
+        )?;
+        pay_referral_reward(
+            referral_reward,
+            Some(referral_rewards),
+            ctx.accounts.referral_vault.as_ref(),
+            ctx.accounts.sender_token_account.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            &[],
+        )?;
+        total_referral_reward = total_referral_reward
+            .checked_add(referral_reward)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        receiver_profile.total_received = receiver_profile
+            .total_received
+            .checked_add(*split_amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        receiver_profile.exit(ctx.program_id)?;
+    }
+
+    // The splits add up to the net amount, so the treasury keeps the fee
+    // less every referrer's share
+    pay_treasury_fee(
+        transfer_request.platform_fee,
+        total_referral_reward,
+        &ctx.accounts.platform_fee_account,
+        ctx.accounts.sender_token_account.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        &[],
+    )?;
+
+    transfer_request.referral_reward = total_referral_reward;
+    transfer_request.status = TransferStatus::Completed;
+    transfer_request.completed_at = Some(clock.unix_timestamp);
+
+    ctx.accounts
+        .sender_profile
+        .record_sent(transfer_request.amount, clock.unix_timestamp)?;
+
+    msg!(
+        "Split transfer completed: {} tokens sent, {} received across {} receivers (fee: {})",
+        transfer_request.amount,
+        transfer_request.net_amount,
+        transfer_request.splits.len(),
+        transfer_request.platform_fee
+    );
+
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
+use crate::constants::*;
 use crate::errors::CrossPayError;
 use crate::state::*;
-use crate::constants::*;
 
 /// Context for confirming and executing a transfer
 #[derive(Accounts)]
@@ -17,7 +17,8 @@ pub struct ConfirmTransfer<'info> {
             &transfer_request.nonce.to_le_bytes()
         ],
         bump = transfer_request.bump,
-        constraint = transfer_request.status == TransferStatus::Pending @ CrossPayError::InvalidTransferStatus
+        constraint = transfer_request.status == TransferStatus::Pending @ CrossPayError::InvalidTransferStatus,
+        constraint = transfer_request.splits.is_empty() @ CrossPayError::InvalidSplit
     )]
     pub transfer_request: Account<'info, TransferRequest>,
 
@@ -25,7 +26,8 @@ pub struct ConfirmTransfer<'info> {
         mut,
         seeds = [b"user_profile", sender.key().as_ref()],
         bump = sender_profile.bump,
-        has_one = authority
+        has_one = authority,
+        constraint = !sender_profile.is_frozen @ CrossPayError::AccountFrozen
     )]
     pub sender_profile: Account<'info, UserProfile>,
 
@@ -36,6 +38,34 @@ pub struct ConfirmTransfer<'info> {
     )]
     pub receiver_profile: Account<'info, UserProfile>,
 
+    /// CHECK: Corridor PDA for the sender and receiver countries - may be unconfigured
+    #[account(
+        mut,
+        seeds = [
+            b"corridor",
+            sender_profile.country_code.as_bytes(),
+            receiver_profile.country_code.as_bytes()
+        ],
+        bump
+    )]
+    pub corridor: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", sender.key().as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the receiver - must not exist
+    #[account(
+        seeds = [b"denied_address", transfer_request.receiver.as_ref()],
+        bump,
+        constraint = receiver_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub receiver_denial: UncheckedAccount<'info>,
+
     #[account(
         mut,
         constraint = sender_token_account.owner == authority.key(),
@@ -50,13 +80,41 @@ pub struct ConfirmTransfer<'info> {
     )]
     pub receiver_token_account: Account<'info, TokenAccount>,
 
-    // #[account(
-    //     mut,
-    //     seeds = [b"platform_fee", transfer_request.mint.as_ref()],
-    //     bump,
-    //     constraint = platform_fee_account.mint == transfer_request.mint
-    // )]
-    // pub platform_fee_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == transfer_request.mint
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
+    /// Must be initialized (initialize_platform_config) before any transfer can be
+    /// confirmed, as it carries the hold, travel-rule and referral settings
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Only required when the transfer is at or above the hold threshold
+    #[account(
+        mut,
+        seeds = [b"hold_vault", transfer_request.mint.as_ref()],
+        bump
+    )]
+    pub hold_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Rewards PDA of the receiver's referrer - required when the receiver
+    /// was referred, may be unopened; validated in ReferralRewards::share_of
+    #[account(mut)]
+    pub referral_rewards: Option<UncheckedAccount<'info>>,
+
+    /// Only required when a referral reward is credited
+    #[account(
+        mut,
+        seeds = [b"referral_vault", transfer_request.mint.as_ref()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
 
     /// CHECK: Validated via seeds in sender_profile
     pub sender: UncheckedAccount<'info>,
@@ -72,69 +130,137 @@ pub fn confirm_transfer(ctx: Context<ConfirmTransfer>) -> Result<()> {
     let transfer_request = &mut ctx.accounts.transfer_request;
     let clock = Clock::get()?;
 
-    // Validate sender has sufficient balance
+    // Validate sender has sufficient balance, including the fee when they bear it
+    let sender_debit = transfer_request
+        .sender_debit()
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
     require!(
-        ctx.accounts.sender_token_account.amount >= transfer_request.amount,
+        ctx.accounts.sender_token_account.amount >= sender_debit,
         CrossPayError::InsufficientBalance
     );
 
-    // Validate fee calculations are correct
-    let expected_platform_fee = calculate_platform_fee(transfer_request.amount);
-    let expected_net_amount = calculate_net_amount(transfer_request.amount);
+    // Re-check the corridor, which may have changed since initiation
+    let mut corridor = Corridor::load(&ctx.accounts.corridor)?;
+    if let Some(corridor) = &corridor {
+        corridor.check_transfer(transfer_request.amount, clock.unix_timestamp)?;
+    }
 
+    // The fee was priced at initiation from the tier, corridor and mint fee
+    // config in force then, so it is not recomputed here - later config
+    // changes must not strand pending transfers. Verify amounts add up correctly
+    let expected_net_amount = match transfer_request.fee_bearer {
+        FeeBearer::Receiver => transfer_request.amount.checked_sub(transfer_request.platform_fee),
+        FeeBearer::Sender => Some(transfer_request.amount),
+    };
     require!(
-        transfer_request.platform_fee == expected_platform_fee,
+        expected_net_amount == Some(transfer_request.net_amount),
         CrossPayError::InvalidFeeCalculation
     );
 
-    require!(
-        transfer_request.net_amount == expected_net_amount,
-        CrossPayError::InvalidFeeCalculation
-    );
+    // Transfers at or above the travel-rule threshold must carry
+    // originator and beneficiary data before funds can move
+    ctx.accounts
+        .platform_config
+        .check_travel_rule(transfer_request.amount, transfer_request.travel_rule.as_ref())?;
 
-    // Verify amounts add up correctly
-    require!(
-        transfer_request.net_amount.checked_add(transfer_request.platform_fee)
-            .unwrap_or(0) == transfer_request.amount,
-        CrossPayError::InvalidFeeCalculation
-    );
+    // Large transfers go to the hold vault for the cool-off window
+    // instead of straight to the receiver
+    let hold = ctx.accounts.platform_config.requires_hold(transfer_request.amount);
+    let destination = if hold {
+        ctx.accounts
+            .hold_vault
+            .as_ref()
+            .ok_or(CrossPayError::HoldVaultRequired)?
+            .to_account_info()
+    } else {
+        ctx.accounts.receiver_token_account.to_account_info()
+    };
+
+    // Freelancers are onboarded through partner communities, so the receiver's
+    // referrer earns a share of the fee and the treasury keeps the rest.
+    let referral_rewards = ctx.accounts.referral_rewards.as_ref();
+    let referral_reward = ReferralRewards::share_of(
+        referral_rewards.map(|info| info.as_ref()),
+        ctx.accounts.receiver_profile.referrer,
+        &transfer_request.mint,
+        transfer_request.platform_fee,
+        ctx.accounts.platform_config.referral_share_bps,
+    )?;
+    transfer_request.referral_reward = referral_reward;
+
+    // A held transfer escrows the whole fee with the net amount, so neither the
+    // referrer nor the treasury is paid unless the transfer is released
+    let escrowed = if hold {
+        sender_debit
+    } else {
+        transfer_request.net_amount
+    };
 
-    // Transfer net amount to receiver
+    // Transfer net amount to receiver (or hold vault)
     let receiver_cpi_accounts = Transfer {
         from: ctx.accounts.sender_token_account.to_account_info(),
-        to: ctx.accounts.receiver_token_account.to_account_info(),
+        to: destination,
         authority: ctx.accounts.authority.to_account_info(),
     };
     let receiver_cpi_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
         receiver_cpi_accounts
     );
-    token::transfer(receiver_cpi_ctx, transfer_request.net_amount)?;
-
-    // // Transfer platform fee to fee account (only if fee > 0)
-    // if transfer_request.platform_fee > 0 {
-    //     let fee_cpi_accounts = Transfer {
-    //         from: ctx.accounts.sender_token_account.to_account_info(),
-    //         to: ctx.accounts.platform_fee_account.to_account_info(),
-    //         authority: ctx.accounts.authority.to_account_info(),
-    //     };
-    //     let fee_cpi_ctx = CpiContext::new(
-    //         ctx.accounts.token_program.to_account_info(),
-    //         fee_cpi_accounts
-    //     );
-    //     token::transfer(fee_cpi_ctx, transfer_request.platform_fee)?;
-    // }
+    token::transfer(receiver_cpi_ctx, escrowed)?;
 
-    // Update transfer status
-    transfer_request.status = TransferStatus::Completed;
-    transfer_request.completed_at = Some(clock.unix_timestamp);
+    if !hold {
+        pay_referral_reward(
+            referral_reward,
+            referral_rewards.map(|info| info.as_ref()),
+            ctx.accounts.referral_vault.as_ref(),
+            ctx.accounts.sender_token_account.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            &[],
+        )?;
+        pay_treasury_fee(
+            transfer_request.platform_fee,
+            referral_reward,
+            &ctx.accounts.platform_fee_account,
+            ctx.accounts.sender_token_account.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            &[],
+        )?;
+    }
+
+    if let Some(corridor) = corridor.as_mut() {
+        corridor.record_transfer(transfer_request.amount, clock.unix_timestamp)?;
+        corridor.store(&ctx.accounts.corridor)?;
+    }
 
     // Update user profiles:
-    // - Sender tracks what they actually sent (gross amount)
+    // - Sender tracks what they actually sent (gross amount plus any fee they bear)
     // - Receiver tracks what they actually received (net amount)
-    ctx.accounts.sender_profile.total_sent = ctx.accounts.sender_profile.total_sent
-        .checked_add(transfer_request.amount)  // Gross amount sent
-        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    ctx.accounts.sender_profile.record_sent(sender_debit, clock.unix_timestamp)?;
+
+    if hold {
+        let release_at = clock
+            .unix_timestamp
+            .checked_add(ctx.accounts.platform_config.hold_period)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        transfer_request.status = TransferStatus::Held;
+        transfer_request.release_at = Some(release_at);
+
+        msg!(
+            "Transfer held until {}: {} tokens sent, {} escrowed (fee: {})",
+            release_at,
+            transfer_request.amount,
+            sender_debit,
+            transfer_request.platform_fee
+        );
+
+        return Ok(());
+    }
+
+    // Update transfer status
+    transfer_request.status = TransferStatus::Completed;
+    transfer_request.completed_at = Some(clock.unix_timestamp);
 
     ctx.accounts.receiver_profile.total_received = ctx.accounts.receiver_profile.total_received
         .checked_add(transfer_request.net_amount)  // Net amount received
@@ -148,4 +274,60 @@ pub fn confirm_transfer(ctx: Context<ConfirmTransfer>) -> Result<()> {
     );
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Move a referral reward from `from` into the referral vault and credit it to
+/// the referrer's balance; a no-op for a zero reward
+/// `signer_seeds` is empty when `authority` signed the transaction itself.
+pub(crate) fn pay_referral_reward<'info>(
+    reward: u64,
+    referral_rewards: Option<&AccountInfo<'info>>,
+    referral_vault: Option<&Account<'info, TokenAccount>>,
+    from: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if reward == 0 {
+        return Ok(());
+    }
+    // ReferralRewards::share_of only returns a reward for a present, opened balance
+    let referral_rewards = referral_rewards.ok_or(CrossPayError::ReferralAccountsRequired)?;
+    let referral_vault = referral_vault.ok_or(CrossPayError::ReferralAccountsRequired)?;
+
+    let cpi_accounts = Transfer {
+        from,
+        to: referral_vault.to_account_info(),
+        authority,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, reward)?;
+
+    ReferralRewards::credit(referral_rewards, reward)
+}
+
+/// Pay the treasury what is left of `platform_fee` once the referral share is taken
+pub(crate) fn pay_treasury_fee<'info>(
+    platform_fee: u64,
+    referral_reward: u64,
+    platform_fee_account: &Account<'info, TokenAccount>,
+    from: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let treasury_fee = platform_fee
+        .checked_sub(referral_reward)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    if treasury_fee == 0 {
+        return Ok(());
+    }
+
+    let cpi_accounts = Transfer {
+        from,
+        to: platform_fee_account.to_account_info(),
+        authority,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, treasury_fee)
+}
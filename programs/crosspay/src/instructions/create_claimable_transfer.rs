@@ -0,0 +1,242 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for sending funds to a recipient who has not onboarded yet
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreateClaimableTransfer<'info> {
+    #[account(
+        seeds = [b"user_profile", sender.key().as_ref()],
+        bump = sender_profile.bump,
+        has_one = authority,
+        constraint = sender_profile.kyc_verified @ CrossPayError::KycNotVerified,
+        constraint = !sender_profile.is_frozen @ CrossPayError::AccountFrozen
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ClaimableTransfer::LEN,
+        seeds = [
+            b"claimable_transfer",
+            sender.key().as_ref(),
+            &nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub claimable_transfer: Account<'info, ClaimableTransfer>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"claimable_escrow", claimable_transfer.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = claimable_transfer
+    )]
+    pub claimable_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == authority.key(),
+        constraint = sender_token_account.mint == mint.key()
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Fee schedule PDA - may be unconfigured
+    #[account(
+        seeds = [b"fee_schedule"],
+        bump
+    )]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Fee config PDA for the mint - may be unconfigured
+    #[account(
+        seeds = [b"mint_fee_config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_fee_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", sender.key().as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Sender pubkey - used for PDA derivation
+    pub sender: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Escrow a transfer claimable by the recipient who reveals the preimage of `claim_hash`
+/// `claim_hash` is sha256 of a random secret of at least MIN_CLAIM_SECRET_LEN
+/// bytes shared out of band; never hash a bare email or phone number, which can
+/// be brute-forced.
+pub fn create_claimable_transfer(
+    ctx: Context<CreateClaimableTransfer>,
+    nonce: u64,
+    amount: u64,
+    claim_hash: [u8; 32],
+    expires_at: i64,
+    travel_rule: Option<TravelRuleRecord>,
+) -> Result<()> {
+    require!(amount > 0, CrossPayError::InvalidAmount);
+    ctx.accounts.platform_config.check_unheld(amount)?;
+    if let Some(travel_rule) = &travel_rule {
+        travel_rule.validate()?;
+    }
+    ctx.accounts
+        .platform_config
+        .check_travel_rule(amount, travel_rule.as_ref())?;
+    require!(
+        ctx.accounts.sender_token_account.amount >= amount,
+        CrossPayError::InsufficientBalance
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        expires_at > clock.unix_timestamp,
+        CrossPayError::ClaimExpired
+    );
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.sender_token_account.to_account_info(),
+        to: ctx.accounts.claimable_escrow.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    // The claimant and so the corridor are unknown yet, so no corridor override applies
+    let (_, platform_fee) = FeeResolver::load(
+        &ctx.accounts.fee_schedule,
+        &ctx.accounts.mint_fee_config,
+        ctx.accounts.sender_profile.volume_at(clock.unix_timestamp),
+    )?
+    .resolve(None, amount)?;
+    let net_amount = amount
+        .checked_sub(platform_fee)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    let claimable_transfer = &mut ctx.accounts.claimable_transfer;
+
+    claimable_transfer.sender = ctx.accounts.sender.key();
+    claimable_transfer.mint = ctx.accounts.mint.key();
+    claimable_transfer.amount = amount;
+    claimable_transfer.net_amount = net_amount;
+    claimable_transfer.platform_fee = platform_fee;
+    claimable_transfer.claim_hash = claim_hash;
+    claimable_transfer.expires_at = expires_at;
+    claimable_transfer.status = ClaimStatus::Pending;
+    claimable_transfer.claimed_by = None;
+    claimable_transfer.created_at = clock.unix_timestamp;
+    claimable_transfer.completed_at = None;
+    claimable_transfer.nonce = nonce;
+    claimable_transfer.bump = ctx.bumps.claimable_transfer;
+    claimable_transfer.travel_rule = travel_rule;
+
+    msg!(
+        "Claimable transfer created: {} tokens, expires at {}",
+        amount,
+        expires_at
+    );
+
+    Ok(())
+}
+
+/// Context for the sender reclaiming an expired, unclaimed transfer
+#[derive(Accounts)]
+pub struct ReclaimTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"claimable_transfer",
+            claimable_transfer.sender.as_ref(),
+            &claimable_transfer.nonce.to_le_bytes()
+        ],
+        bump = claimable_transfer.bump,
+        constraint = claimable_transfer.sender == authority.key() @ CrossPayError::Unauthorized,
+        constraint = claimable_transfer.status == ClaimStatus::Pending @ CrossPayError::InvalidClaimStatus
+    )]
+    pub claimable_transfer: Account<'info, ClaimableTransfer>,
+
+    #[account(
+        mut,
+        seeds = [b"claimable_escrow", claimable_transfer.key().as_ref()],
+        bump
+    )]
+    pub claimable_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == authority.key(),
+        constraint = sender_token_account.mint == claimable_transfer.mint
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Return an unclaimed transfer to the sender once it has expired
+pub fn reclaim_transfer(ctx: Context<ReclaimTransfer>) -> Result<()> {
+    let claimable_transfer = &mut ctx.accounts.claimable_transfer;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= claimable_transfer.expires_at,
+        CrossPayError::ClaimNotExpired
+    );
+
+    let sender_key = claimable_transfer.sender;
+    let nonce_bytes = claimable_transfer.nonce.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"claimable_transfer",
+        sender_key.as_ref(),
+        &nonce_bytes,
+        &[claimable_transfer.bump],
+    ]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.claimable_escrow.to_account_info(),
+        to: ctx.accounts.sender_token_account.to_account_info(),
+        authority: claimable_transfer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, claimable_transfer.amount)?;
+
+    claimable_transfer.status = ClaimStatus::Reclaimed;
+    claimable_transfer.completed_at = Some(clock.unix_timestamp);
+
+    msg!(
+        "Claimable transfer reclaimed: {} tokens",
+        claimable_transfer.amount
+    );
+
+    Ok(())
+}
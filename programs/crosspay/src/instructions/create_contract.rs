@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for creating a milestone contract funded into escrow
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreateContract<'info> {
+    #[account(
+        seeds = [b"user_profile", client.key().as_ref()],
+        bump = client_profile.bump,
+        has_one = authority,
+        constraint = client_profile.kyc_verified @ CrossPayError::KycNotVerified,
+        constraint = !client_profile.is_frozen @ CrossPayError::AccountFrozen
+    )]
+    pub client_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"user_profile", freelancer.key().as_ref()],
+        bump = freelancer_profile.bump
+    )]
+    pub freelancer_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Corridor PDA for the sender and receiver countries - may be unconfigured
+    #[account(
+        mut,
+        seeds = [
+            b"corridor",
+            client_profile.country_code.as_bytes(),
+            freelancer_profile.country_code.as_bytes()
+        ],
+        bump
+    )]
+    pub corridor: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the client - must not exist
+    #[account(
+        seeds = [b"denied_address", client.key().as_ref()],
+        bump,
+        constraint = client_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub client_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the freelancer - must not exist
+    #[account(
+        seeds = [b"denied_address", freelancer.key().as_ref()],
+        bump,
+        constraint = freelancer_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub freelancer_denial: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Contract::LEN,
+        seeds = [
+            b"contract",
+            client.key().as_ref(),
+            freelancer.key().as_ref(),
+            &nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub contract: Account<'info, Contract>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"contract_escrow", contract.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = contract
+    )]
+    pub contract_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = client_token_account.owner == authority.key(),
+        constraint = client_token_account.mint == mint.key()
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Fee schedule PDA - may be unconfigured
+    #[account(
+        seeds = [b"fee_schedule"],
+        bump
+    )]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Fee config PDA for the mint - may be unconfigured
+    #[account(
+        seeds = [b"mint_fee_config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_fee_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: Freelancer pubkey - validated via freelancer_profile seeds
+    pub freelancer: UncheckedAccount<'info>,
+
+    /// CHECK: Client pubkey - used for PDA derivation
+    pub client: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Create a contract with N milestones and fund the escrow up front
+/// Milestones still unsubmitted at `deadline` can be reclaimed by the client.
+pub fn create_contract(
+    ctx: Context<CreateContract>,
+    nonce: u64,
+    arbiter: Pubkey,
+    amounts: Vec<u64>,
+    description_hashes: Vec<[u8; 32]>,
+    deadline: i64,
+    travel_rule: Option<TravelRuleRecord>,
+) -> Result<()> {
+    let client = ctx.accounts.client.key();
+    let freelancer = ctx.accounts.freelancer.key();
+
+    require!(
+        !amounts.is_empty()
+            && amounts.len() <= MAX_MILESTONES
+            && amounts.len() == description_hashes.len(),
+        CrossPayError::InvalidMilestones
+    );
+    require_keys_neq!(client, freelancer, CrossPayError::InvalidMilestones);
+    require!(
+        arbiter != client && arbiter != freelancer,
+        CrossPayError::InvalidArbiter
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        deadline > clock.unix_timestamp,
+        CrossPayError::InvalidContractDeadline
+    );
+
+    if let Some(travel_rule) = &travel_rule {
+        travel_rule.validate()?;
+    }
+
+    let fee_resolver = FeeResolver::load(
+        &ctx.accounts.fee_schedule,
+        &ctx.accounts.mint_fee_config,
+        ctx.accounts.client_profile.volume_at(clock.unix_timestamp),
+    )?;
+    let corridor = Corridor::load(&ctx.accounts.corridor)?;
+
+    let mut total_amount: u64 = 0;
+    let mut milestones = Vec::with_capacity(amounts.len());
+    for (amount, description_hash) in amounts.iter().zip(description_hashes.iter()) {
+        require!(*amount > 0, CrossPayError::InvalidAmount);
+        // Each milestone is released as its own payment
+        ctx.accounts.platform_config.check_unheld(*amount)?;
+        ctx.accounts
+            .platform_config
+            .check_travel_rule(*amount, travel_rule.as_ref())?;
+        let (_, platform_fee) = fee_resolver.resolve(corridor.as_ref(), *amount)?;
+        require!(platform_fee <= *amount, CrossPayError::InvalidFeeCalculation);
+        Corridor::check_and_record(&ctx.accounts.corridor, *amount, clock.unix_timestamp)?;
+        total_amount = total_amount
+            .checked_add(*amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        milestones.push(Milestone {
+            amount: *amount,
+            platform_fee,
+            description_hash: *description_hash,
+            status: MilestoneStatus::Pending,
+        });
+    }
+
+    require!(
+        ctx.accounts.client_token_account.amount >= total_amount,
+        CrossPayError::InsufficientBalance
+    );
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.client_token_account.to_account_info(),
+        to: ctx.accounts.contract_escrow.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, total_amount)?;
+
+    let contract = &mut ctx.accounts.contract;
+
+    contract.client = client;
+    contract.freelancer = freelancer;
+    contract.arbiter = arbiter;
+    contract.mint = ctx.accounts.mint.key();
+    contract.total_amount = total_amount;
+    contract.released_amount = 0;
+    contract.refunded_amount = 0;
+    contract.milestones = milestones;
+    contract.status = ContractStatus::Active;
+    contract.created_at = clock.unix_timestamp;
+    contract.completed_at = None;
+    contract.deadline = deadline;
+    contract.travel_rule = travel_rule;
+    contract.nonce = nonce;
+    contract.bump = ctx.bumps.contract;
+
+    msg!(
+        "Contract created: {} milestones, {} tokens escrowed for {}",
+        contract.milestones.len(),
+        total_amount,
+        freelancer
+    );
+
+    Ok(())
+}
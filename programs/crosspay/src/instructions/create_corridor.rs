@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Validate corridor fee override and amount limits
+fn validate_corridor_settings(
+    fee_bps: Option<u16>,
+    min_amount: u64,
+    max_amount: u64,
+) -> Result<()> {
+    require!(
+        fee_bps.is_none_or(|bps| bps as u64 <= BASIS_POINTS_DIVISOR),
+        CrossPayError::InvalidCorridor
+    );
+    require!(
+        max_amount == 0 || min_amount <= max_amount,
+        CrossPayError::InvalidCorridor
+    );
+
+    Ok(())
+}
+
+/// Context for the platform admin configuring a new corridor
+#[derive(Accounts)]
+#[instruction(sender_country: String, receiver_country: String)]
+pub struct CreateCorridor<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Corridor::LEN,
+        seeds = [b"corridor", sender_country.as_bytes(), receiver_country.as_bytes()],
+        bump
+    )]
+    pub corridor: Account<'info, Corridor>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Configure the transfer corridor between two countries
+#[allow(clippy::too_many_arguments)]
+pub fn create_corridor(
+    ctx: Context<CreateCorridor>,
+    sender_country: String,
+    receiver_country: String,
+    enabled: bool,
+    fee_bps: Option<u16>,
+    min_amount: u64,
+    max_amount: u64,
+    daily_cap: u64,
+) -> Result<()> {
+    require!(
+        is_valid_country_code(&sender_country) && is_valid_country_code(&receiver_country),
+        CrossPayError::InvalidCountryCode
+    );
+    validate_corridor_settings(fee_bps, min_amount, max_amount)?;
+
+    let corridor = &mut ctx.accounts.corridor;
+
+    corridor.sender_country = sender_country;
+    corridor.receiver_country = receiver_country;
+    corridor.enabled = enabled;
+    corridor.fee_bps = fee_bps;
+    corridor.min_amount = min_amount;
+    corridor.max_amount = max_amount;
+    corridor.daily_cap = daily_cap;
+    corridor.current_day = 0;
+    corridor.daily_volume = 0;
+    corridor.bump = ctx.bumps.corridor;
+
+    msg!(
+        "Corridor {} -> {} configured (enabled: {})",
+        corridor.sender_country,
+        corridor.receiver_country,
+        enabled
+    );
+
+    Ok(())
+}
+
+/// Context for the platform admin updating a corridor
+#[derive(Accounts)]
+pub struct UpdateCorridor<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"corridor",
+            corridor.sender_country.as_bytes(),
+            corridor.receiver_country.as_bytes()
+        ],
+        bump = corridor.bump
+    )]
+    pub corridor: Account<'info, Corridor>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Update a corridor's status, fee override, limits and daily cap
+pub fn update_corridor(
+    ctx: Context<UpdateCorridor>,
+    enabled: bool,
+    fee_bps: Option<u16>,
+    min_amount: u64,
+    max_amount: u64,
+    daily_cap: u64,
+) -> Result<()> {
+    validate_corridor_settings(fee_bps, min_amount, max_amount)?;
+
+    let corridor = &mut ctx.accounts.corridor;
+
+    corridor.enabled = enabled;
+    corridor.fee_bps = fee_bps;
+    corridor.min_amount = min_amount;
+    corridor.max_amount = max_amount;
+    corridor.daily_cap = daily_cap;
+
+    msg!(
+        "Corridor {} -> {} updated (enabled: {})",
+        corridor.sender_country,
+        corridor.receiver_country,
+        enabled
+    );
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for a receiver issuing an invoice to a payer
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreateInvoice<'info> {
+    #[account(
+        seeds = [b"user_profile", issuer.key().as_ref()],
+        bump = issuer_profile.bump,
+        has_one = authority,
+        constraint = !issuer_profile.is_frozen @ CrossPayError::AccountFrozen
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Invoice::LEN,
+        seeds = [
+            b"invoice",
+            issuer.key().as_ref(),
+            payer.key().as_ref(),
+            &nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub invoice: Account<'info, Invoice>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: Payer pubkey - only recorded on the invoice
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: Issuer pubkey - used for PDA derivation
+    pub issuer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Issue an invoice that the payer can settle with pay_invoice
+pub fn create_invoice(
+    ctx: Context<CreateInvoice>,
+    nonce: u64,
+    amount: u64,
+    due_date: i64,
+    reference_hash: [u8; 32],
+) -> Result<()> {
+    require!(amount > 0, CrossPayError::InvalidAmount);
+    require_keys_neq!(
+        ctx.accounts.issuer.key(),
+        ctx.accounts.payer.key(),
+        CrossPayError::Unauthorized
+    );
+
+    let invoice = &mut ctx.accounts.invoice;
+    let clock = Clock::get()?;
+
+    invoice.issuer = ctx.accounts.issuer.key();
+    invoice.payer = ctx.accounts.payer.key();
+    invoice.mint = ctx.accounts.mint.key();
+    invoice.amount = amount;
+    invoice.amount_paid = 0;
+    invoice.due_date = due_date;
+    invoice.reference_hash = reference_hash;
+    invoice.status = InvoiceStatus::Open;
+    invoice.last_transfer = None;
+    invoice.payment_count = 0;
+    invoice.created_at = clock.unix_timestamp;
+    invoice.paid_at = None;
+    invoice.nonce = nonce;
+    invoice.bump = ctx.bumps.invoice;
+
+    msg!(
+        "Invoice issued: {} tokens to be paid by {} before {}",
+        amount,
+        invoice.payer,
+        due_date
+    );
+
+    Ok(())
+}
+
+/// Context for flagging an unpaid invoice as overdue (permissionless)
+#[derive(Accounts)]
+pub struct MarkInvoiceOverdue<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"invoice",
+            invoice.issuer.as_ref(),
+            invoice.payer.as_ref(),
+            &invoice.nonce.to_le_bytes()
+        ],
+        bump = invoice.bump,
+        constraint = matches!(
+            invoice.status,
+            InvoiceStatus::Open | InvoiceStatus::PartiallyPaid
+        ) @ CrossPayError::InvalidInvoiceStatus
+    )]
+    pub invoice: Account<'info, Invoice>,
+}
+
+/// Mark an invoice that is past its due date and not fully paid as overdue
+pub fn mark_invoice_overdue(ctx: Context<MarkInvoiceOverdue>) -> Result<()> {
+    let invoice = &mut ctx.accounts.invoice;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp > invoice.due_date,
+        CrossPayError::InvoiceNotOverdue
+    );
+
+    invoice.status = InvoiceStatus::Overdue;
+
+    msg!(
+        "Invoice overdue: {} of {} tokens outstanding",
+        invoice.amount - invoice.amount_paid,
+        invoice.amount
+    );
+
+    Ok(())
+}
@@ -0,0 +1,312 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Approve, Mint, Revoke, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for creating a recurring payment schedule
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreatePaymentSchedule<'info> {
+    #[account(
+        seeds = [b"user_profile", sender.key().as_ref()],
+        bump = sender_profile.bump,
+        has_one = authority,
+        constraint = sender_profile.kyc_verified @ CrossPayError::KycNotVerified,
+        constraint = !sender_profile.is_frozen @ CrossPayError::AccountFrozen
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"user_profile", receiver.key().as_ref()],
+        bump = receiver_profile.bump
+    )]
+    pub receiver_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Corridor PDA for the sender and receiver countries - may be unconfigured
+    #[account(
+        seeds = [
+            b"corridor",
+            sender_profile.country_code.as_bytes(),
+            receiver_profile.country_code.as_bytes()
+        ],
+        bump
+    )]
+    pub corridor: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", sender.key().as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the receiver - must not exist
+    #[account(
+        seeds = [b"denied_address", receiver.key().as_ref()],
+        bump,
+        constraint = receiver_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub receiver_denial: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PaymentSchedule::LEN,
+        seeds = [
+            b"payment_schedule",
+            sender.key().as_ref(),
+            receiver.key().as_ref(),
+            &nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub payment_schedule: Account<'info, PaymentSchedule>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == authority.key(),
+        constraint = sender_token_account.mint == mint.key()
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA delegate that spends scheduled payments - validated via seeds
+    #[account(
+        seeds = [b"schedule_delegate", sender.key().as_ref()],
+        bump
+    )]
+    pub schedule_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: Fee schedule PDA - may be unconfigured
+    #[account(
+        seeds = [b"fee_schedule"],
+        bump
+    )]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Fee config PDA for the mint - may be unconfigured
+    #[account(
+        seeds = [b"mint_fee_config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_fee_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Rewards PDA of the receiver's referrer - required when the receiver
+    /// was referred, may be unopened; validated in ReferralRewards::share_of
+    pub referral_rewards: Option<UncheckedAccount<'info>>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: Receiver pubkey - validated via receiver_profile seeds
+    pub receiver: UncheckedAccount<'info>,
+
+    /// CHECK: Sender pubkey - used for PDA derivation
+    pub sender: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Create a recurring payment schedule and delegate the allowance it needs
+#[allow(clippy::too_many_arguments)]
+pub fn create_payment_schedule(
+    ctx: Context<CreatePaymentSchedule>,
+    nonce: u64,
+    amount: u64,
+    interval: i64,
+    total_payments: u32,
+    first_payment_at: i64,
+    travel_rule: Option<TravelRuleRecord>,
+) -> Result<()> {
+    require!(amount > 0, CrossPayError::InvalidAmount);
+    require_keys_neq!(
+        ctx.accounts.sender.key(),
+        ctx.accounts.receiver.key(),
+        CrossPayError::InvalidSchedule
+    );
+    require!(
+        interval >= MIN_SCHEDULE_INTERVAL && total_payments > 0,
+        CrossPayError::InvalidSchedule
+    );
+    ctx.accounts.platform_config.check_unheld(amount)?;
+    if let Some(travel_rule) = &travel_rule {
+        travel_rule.validate()?;
+    }
+    ctx.accounts
+        .platform_config
+        .check_travel_rule(amount, travel_rule.as_ref())?;
+
+    let clock = Clock::get()?;
+    require!(
+        first_payment_at >= clock.unix_timestamp,
+        CrossPayError::InvalidSchedule
+    );
+
+    // Fail early on a closed corridor; each payment is charged to it when executed
+    let corridor = Corridor::load(&ctx.accounts.corridor)?;
+    if let Some(corridor) = &corridor {
+        corridor.check_transfer(amount, clock.unix_timestamp)?;
+    }
+
+    // Every payment is priced once, at the sender's current tier
+    let (_, platform_fee) = FeeResolver::load(
+        &ctx.accounts.fee_schedule,
+        &ctx.accounts.mint_fee_config,
+        ctx.accounts.sender_profile.volume_at(clock.unix_timestamp),
+    )?
+    .resolve(corridor.as_ref(), amount)?;
+    let net_amount = amount
+        .checked_sub(platform_fee)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    // The referral share is locked in with the fee; a referrer balance opened
+    // later only earns from new schedules
+    let referral_fee = ReferralRewards::share_of(
+        ctx.accounts.referral_rewards.as_ref().map(|info| info.as_ref()),
+        ctx.accounts.receiver_profile.referrer,
+        &ctx.accounts.mint.key(),
+        platform_fee,
+        ctx.accounts.platform_config.referral_share_bps,
+    )?;
+
+    // One SPL delegate per token account, so all schedules of a sender share
+    // the same delegate PDA and their allowances are stacked.
+    let allowance = amount
+        .checked_mul(total_payments as u64)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    let sender_token_account = &ctx.accounts.sender_token_account;
+    let existing_allowance =
+        if sender_token_account.delegate == Some(ctx.accounts.schedule_delegate.key()).into() {
+            sender_token_account.delegated_amount
+        } else {
+            0
+        };
+    let new_allowance = existing_allowance
+        .checked_add(allowance)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    let cpi_accounts = Approve {
+        to: ctx.accounts.sender_token_account.to_account_info(),
+        delegate: ctx.accounts.schedule_delegate.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::approve(cpi_ctx, new_allowance)?;
+
+    let payment_schedule = &mut ctx.accounts.payment_schedule;
+
+    payment_schedule.sender = ctx.accounts.sender.key();
+    payment_schedule.receiver = ctx.accounts.receiver.key();
+    payment_schedule.mint = ctx.accounts.mint.key();
+    payment_schedule.amount = amount;
+    payment_schedule.net_amount = net_amount;
+    payment_schedule.platform_fee = platform_fee;
+    payment_schedule.referral_fee = referral_fee;
+    payment_schedule.interval = interval;
+    payment_schedule.next_due = first_payment_at;
+    payment_schedule.remaining_payments = total_payments;
+    payment_schedule.payments_made = 0;
+    payment_schedule.created_at = clock.unix_timestamp;
+    payment_schedule.nonce = nonce;
+    payment_schedule.bump = ctx.bumps.payment_schedule;
+    payment_schedule.travel_rule = travel_rule;
+
+    msg!(
+        "Payment schedule created: {} x {} tokens to {}",
+        total_payments,
+        amount,
+        payment_schedule.receiver
+    );
+
+    Ok(())
+}
+
+/// Context for cancelling a payment schedule
+#[derive(Accounts)]
+pub struct CancelPaymentSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"payment_schedule",
+            payment_schedule.sender.as_ref(),
+            payment_schedule.receiver.as_ref(),
+            &payment_schedule.nonce.to_le_bytes()
+        ],
+        bump = payment_schedule.bump,
+        constraint = payment_schedule.sender == authority.key() @ CrossPayError::Unauthorized,
+        close = authority
+    )]
+    pub payment_schedule: Account<'info, PaymentSchedule>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == authority.key(),
+        constraint = sender_token_account.mint == payment_schedule.mint
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA delegate that spends scheduled payments - validated via seeds
+    #[account(
+        seeds = [b"schedule_delegate", authority.key().as_ref()],
+        bump
+    )]
+    pub schedule_delegate: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Cancel a payment schedule and release its unused allowance
+pub fn cancel_payment_schedule(ctx: Context<CancelPaymentSchedule>) -> Result<()> {
+    let payment_schedule = &ctx.accounts.payment_schedule;
+    let sender_token_account = &ctx.accounts.sender_token_account;
+
+    if sender_token_account.delegate == Some(ctx.accounts.schedule_delegate.key()).into() {
+        let unused_allowance = payment_schedule
+            .allowance_per_payment()
+            .checked_mul(payment_schedule.remaining_payments as u64)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        let new_allowance = sender_token_account
+            .delegated_amount
+            .saturating_sub(unused_allowance);
+
+        if new_allowance == 0 {
+            let cpi_accounts = Revoke {
+                source: ctx.accounts.sender_token_account.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token::revoke(cpi_ctx)?;
+        } else {
+            let cpi_accounts = Approve {
+                to: ctx.accounts.sender_token_account.to_account_info(),
+                delegate: ctx.accounts.schedule_delegate.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token::approve(cpi_ctx, new_allowance)?;
+        }
+    }
+
+    msg!(
+        "Payment schedule cancelled with {} payments remaining",
+        payment_schedule.remaining_payments
+    );
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Check the band and staleness settings of a reference rate
+fn validate_reference_rate_config(max_deviation_bps: u16, max_age: i64) -> Result<()> {
+    require!(
+        max_deviation_bps > 0 && max_deviation_bps as u64 <= BASIS_POINTS_DIVISOR && max_age > 0,
+        CrossPayError::InvalidReferenceRate
+    );
+
+    Ok(())
+}
+
+/// Context for the platform admin creating a currency pair's reference rate
+#[derive(Accounts)]
+#[instruction(base: CurrencyCode, quote: CurrencyCode)]
+pub struct CreateReferenceRate<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ReferenceRate::LEN,
+        seeds = [b"reference_rate", base.code.as_ref(), quote.code.as_ref()],
+        bump
+    )]
+    pub reference_rate: Account<'info, ReferenceRate>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a reference rate for a pair, fed by `oracle`
+/// Provider rates in the pair are rejected until the oracle posts a first rate.
+pub fn create_reference_rate(
+    ctx: Context<CreateReferenceRate>,
+    base: CurrencyCode,
+    quote: CurrencyCode,
+    oracle: Pubkey,
+    max_deviation_bps: u16,
+    max_age: i64,
+) -> Result<()> {
+    require!(
+        base.is_valid() && quote.is_valid() && base != quote,
+        CrossPayError::InvalidCurrencyCode
+    );
+    validate_reference_rate_config(max_deviation_bps, max_age)?;
+
+    let reference_rate = &mut ctx.accounts.reference_rate;
+
+    reference_rate.base = base;
+    reference_rate.quote = quote;
+    reference_rate.oracle = oracle;
+    reference_rate.rate = 0;
+    reference_rate.updated_at = 0;
+    reference_rate.max_deviation_bps = max_deviation_bps;
+    reference_rate.max_age = max_age;
+    reference_rate.bump = ctx.bumps.reference_rate;
+
+    msg!(
+        "Reference rate created - Oracle: {}, Band: {} bps, Max age: {}s",
+        oracle,
+        max_deviation_bps,
+        max_age
+    );
+
+    Ok(())
+}
+
+/// Context for the platform admin reconfiguring a reference rate
+#[derive(Accounts)]
+pub struct UpdateReferenceRateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"reference_rate",
+            reference_rate.base.code.as_ref(),
+            reference_rate.quote.code.as_ref()
+        ],
+        bump = reference_rate.bump
+    )]
+    pub reference_rate: Account<'info, ReferenceRate>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Replace the oracle, deviation band and max age of a reference rate
+pub fn update_reference_rate_config(
+    ctx: Context<UpdateReferenceRateConfig>,
+    oracle: Pubkey,
+    max_deviation_bps: u16,
+    max_age: i64,
+) -> Result<()> {
+    validate_reference_rate_config(max_deviation_bps, max_age)?;
+
+    let reference_rate = &mut ctx.accounts.reference_rate;
+
+    reference_rate.oracle = oracle;
+    reference_rate.max_deviation_bps = max_deviation_bps;
+    reference_rate.max_age = max_age;
+
+    msg!(
+        "Reference rate config updated - Oracle: {}, Band: {} bps, Max age: {}s",
+        oracle,
+        max_deviation_bps,
+        max_age
+    );
+
+    Ok(())
+}
+
+/// Context for the oracle posting a new reference rate
+#[derive(Accounts)]
+pub struct UpdateReferenceRate<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"reference_rate",
+            reference_rate.base.code.as_ref(),
+            reference_rate.quote.code.as_ref()
+        ],
+        bump = reference_rate.bump,
+        has_one = oracle @ CrossPayError::Unauthorized
+    )]
+    pub reference_rate: Account<'info, ReferenceRate>,
+
+    pub oracle: Signer<'info>,
+}
+
+/// Post the current reference rate for a pair (oracle only)
+pub fn update_reference_rate(ctx: Context<UpdateReferenceRate>, rate: u64) -> Result<()> {
+    require!(rate > 0, CrossPayError::InvalidReferenceRate);
+
+    let reference_rate = &mut ctx.accounts.reference_rate;
+    let clock = Clock::get()?;
+
+    reference_rate.rate = rate;
+    reference_rate.updated_at = clock.unix_timestamp;
+
+    msg!("Reference rate updated: {}", rate);
+
+    Ok(())
+}
@@ -0,0 +1,319 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for creating a payment stream funded into escrow
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreateStream<'info> {
+    #[account(
+        seeds = [b"user_profile", sender.key().as_ref()],
+        bump = sender_profile.bump,
+        has_one = authority,
+        constraint = sender_profile.kyc_verified @ CrossPayError::KycNotVerified,
+        constraint = !sender_profile.is_frozen @ CrossPayError::AccountFrozen
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"user_profile", receiver.key().as_ref()],
+        bump = receiver_profile.bump
+    )]
+    pub receiver_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Corridor PDA for the sender and receiver countries - may be unconfigured
+    #[account(
+        mut,
+        seeds = [
+            b"corridor",
+            sender_profile.country_code.as_bytes(),
+            receiver_profile.country_code.as_bytes()
+        ],
+        bump
+    )]
+    pub corridor: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", sender.key().as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the receiver - must not exist
+    #[account(
+        seeds = [b"denied_address", receiver.key().as_ref()],
+        bump,
+        constraint = receiver_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub receiver_denial: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PaymentStream::LEN,
+        seeds = [
+            b"payment_stream",
+            sender.key().as_ref(),
+            receiver.key().as_ref(),
+            &nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"stream_escrow", payment_stream.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = payment_stream
+    )]
+    pub stream_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == authority.key(),
+        constraint = sender_token_account.mint == mint.key()
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Fee schedule PDA - may be unconfigured
+    #[account(
+        seeds = [b"fee_schedule"],
+        bump
+    )]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Fee config PDA for the mint - may be unconfigured
+    #[account(
+        seeds = [b"mint_fee_config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_fee_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: Receiver pubkey - validated via receiver_profile seeds
+    pub receiver: UncheckedAccount<'info>,
+
+    /// CHECK: Sender pubkey - used for PDA derivation
+    pub sender: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Create a payment stream that vests linearly between start and end time
+pub fn create_stream(
+    ctx: Context<CreateStream>,
+    nonce: u64,
+    amount: u64,
+    start_time: i64,
+    end_time: i64,
+    travel_rule: Option<TravelRuleRecord>,
+) -> Result<()> {
+    require!(amount > 0, CrossPayError::InvalidAmount);
+    require_keys_neq!(
+        ctx.accounts.sender.key(),
+        ctx.accounts.receiver.key(),
+        CrossPayError::InvalidStreamWindow
+    );
+    require!(end_time > start_time, CrossPayError::InvalidStreamWindow);
+    ctx.accounts.platform_config.check_unheld(amount)?;
+    if let Some(travel_rule) = &travel_rule {
+        travel_rule.validate()?;
+    }
+    ctx.accounts
+        .platform_config
+        .check_travel_rule(amount, travel_rule.as_ref())?;
+
+    let clock = Clock::get()?;
+    require!(
+        end_time > clock.unix_timestamp,
+        CrossPayError::InvalidStreamWindow
+    );
+    require!(
+        ctx.accounts.sender_token_account.amount >= amount,
+        CrossPayError::InsufficientBalance
+    );
+    let (_, platform_fee) = FeeResolver::load(
+        &ctx.accounts.fee_schedule,
+        &ctx.accounts.mint_fee_config,
+        ctx.accounts.sender_profile.volume_at(clock.unix_timestamp),
+    )?
+    .resolve(Corridor::load(&ctx.accounts.corridor)?.as_ref(), amount)?;
+    require!(platform_fee <= amount, CrossPayError::InvalidFeeCalculation);
+    Corridor::check_and_record(&ctx.accounts.corridor, amount, clock.unix_timestamp)?;
+
+    // Fund the escrow with the full gross amount up front
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.sender_token_account.to_account_info(),
+        to: ctx.accounts.stream_escrow.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    let payment_stream = &mut ctx.accounts.payment_stream;
+
+    payment_stream.sender = ctx.accounts.sender.key();
+    payment_stream.receiver = ctx.accounts.receiver.key();
+    payment_stream.mint = ctx.accounts.mint.key();
+    payment_stream.deposited_amount = amount;
+    payment_stream.withdrawn_amount = 0;
+    payment_stream.refunded_amount = 0;
+    payment_stream.platform_fee = platform_fee;
+    payment_stream.start_time = start_time;
+    payment_stream.end_time = end_time;
+    payment_stream.status = StreamStatus::Active;
+    payment_stream.created_at = clock.unix_timestamp;
+    payment_stream.nonce = nonce;
+    payment_stream.bump = ctx.bumps.payment_stream;
+    payment_stream.travel_rule = travel_rule;
+
+    msg!(
+        "Stream created: {} tokens to {} from {} to {}",
+        amount,
+        payment_stream.receiver,
+        start_time,
+        end_time
+    );
+
+    Ok(())
+}
+
+/// Context for cancelling a payment stream
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"payment_stream",
+            payment_stream.sender.as_ref(),
+            payment_stream.receiver.as_ref(),
+            &payment_stream.nonce.to_le_bytes()
+        ],
+        bump = payment_stream.bump,
+        constraint = payment_stream.sender == authority.key() @ CrossPayError::Unauthorized,
+        constraint = payment_stream.status == StreamStatus::Active @ CrossPayError::InvalidStreamStatus
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+
+    #[account(
+        mut,
+        seeds = [b"stream_escrow", payment_stream.key().as_ref()],
+        bump
+    )]
+    pub stream_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == authority.key(),
+        constraint = sender_token_account.mint == payment_stream.mint
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Cancel a stream and reclaim the unvested remainder
+/// The receiver can still withdraw whatever had vested before cancellation
+pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+    let payment_stream = &mut ctx.accounts.payment_stream;
+    let clock = Clock::get()?;
+
+    let vested = calculate_vested_amount(
+        payment_stream.deposited_amount,
+        payment_stream.start_time,
+        payment_stream.end_time,
+        clock.unix_timestamp,
+    );
+    let unvested = payment_stream.deposited_amount.saturating_sub(vested);
+    require!(unvested > 0, CrossPayError::InvalidStreamStatus);
+
+    let sender_key = payment_stream.sender;
+    let receiver_key = payment_stream.receiver;
+    let nonce_bytes = payment_stream.nonce.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"payment_stream",
+        sender_key.as_ref(),
+        receiver_key.as_ref(),
+        &nonce_bytes,
+        &[payment_stream.bump],
+    ]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.stream_escrow.to_account_info(),
+        to: ctx.accounts.sender_token_account.to_account_info(),
+        authority: payment_stream.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, unvested)?;
+
+    // With nothing left for the receiver the escrow is closed straight away
+    if vested == payment_stream.withdrawn_amount {
+        close_stream_escrow(
+            &mut ctx.accounts.stream_escrow,
+            payment_stream.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+    }
+
+    payment_stream.refunded_amount = unvested;
+    payment_stream.status = StreamStatus::Cancelled;
+
+    msg!(
+        "Stream cancelled: {} tokens returned, {} vested to receiver",
+        unvested,
+        vested
+    );
+
+    Ok(())
+}
+
+/// Close an emptied stream escrow and return its rent to the sender
+/// Stray tokens sent to the escrow keep it open rather than failing the instruction.
+pub(crate) fn close_stream_escrow<'info>(
+    stream_escrow: &mut Account<'info, TokenAccount>,
+    payment_stream: AccountInfo<'info>,
+    sender: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    stream_escrow.reload()?;
+    if stream_escrow.amount > 0 {
+        return Ok(());
+    }
+
+    let cpi_accounts = CloseAccount {
+        account: stream_escrow.to_account_info(),
+        destination: sender,
+        authority: payment_stream,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+    token::close_account(cpi_ctx)
+}
@@ -0,0 +1,239 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::instructions::confirm_transfer::{pay_referral_reward, pay_treasury_fee};
+use crate::state::*;
+
+/// Context for executing a due scheduled payment (permissionless crank)
+#[derive(Accounts)]
+pub struct ExecuteScheduledPayment<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"payment_schedule",
+            payment_schedule.sender.as_ref(),
+            payment_schedule.receiver.as_ref(),
+            &payment_schedule.nonce.to_le_bytes()
+        ],
+        bump = payment_schedule.bump,
+        constraint = payment_schedule.remaining_payments > 0 @ CrossPayError::ScheduleCompleted
+    )]
+    pub payment_schedule: Account<'info, PaymentSchedule>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", payment_schedule.sender.as_ref()],
+        bump = sender_profile.bump,
+        constraint = !sender_profile.is_frozen @ CrossPayError::AccountFrozen
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", payment_schedule.receiver.as_ref()],
+        bump = receiver_profile.bump
+    )]
+    pub receiver_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Corridor PDA for the sender and receiver countries - may be unconfigured
+    #[account(
+        mut,
+        seeds = [
+            b"corridor",
+            sender_profile.country_code.as_bytes(),
+            receiver_profile.country_code.as_bytes()
+        ],
+        bump
+    )]
+    pub corridor: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", payment_schedule.sender.as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the receiver - must not exist
+    #[account(
+        seeds = [b"denied_address", payment_schedule.receiver.as_ref()],
+        bump,
+        constraint = receiver_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub receiver_denial: UncheckedAccount<'info>,
+
+    /// Owned by the profile authority that approved the schedule delegate
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == sender_profile.authority,
+        constraint = sender_token_account.mint == payment_schedule.mint,
+        constraint = sender_token_account.delegate == Some(schedule_delegate.key()).into() @ CrossPayError::ScheduleNotDelegated
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == payment_schedule.receiver,
+        constraint = receiver_token_account.mint == payment_schedule.mint
+    )]
+    pub receiver_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA delegate that spends scheduled payments - validated via seeds
+    #[account(
+        seeds = [b"schedule_delegate", payment_schedule.sender.as_ref()],
+        bump
+    )]
+    pub schedule_delegate: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == payment_schedule.mint
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Rewards PDA of the receiver's referrer - required when the schedule
+    /// carries a referral fee; validated in the instruction
+    #[account(mut)]
+    pub referral_rewards: Option<UncheckedAccount<'info>>,
+
+    /// Only required when the schedule carries a referral fee
+    #[account(
+        mut,
+        seeds = [b"referral_vault", payment_schedule.mint.as_ref()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Anyone may crank a due payment
+    pub executor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Execute the next scheduled payment once it is due
+pub fn execute_scheduled_payment(ctx: Context<ExecuteScheduledPayment>) -> Result<()> {
+    let payment_schedule = &mut ctx.accounts.payment_schedule;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= payment_schedule.next_due,
+        CrossPayError::PaymentNotDue
+    );
+    // The hold threshold is only enforced at creation, so lowering it later
+    // doesn't strand schedules the sender has already funded
+    ctx.accounts.platform_config.check_travel_rule(
+        payment_schedule.amount,
+        payment_schedule.travel_rule.as_ref(),
+    )?;
+    Corridor::check_and_record(
+        &ctx.accounts.corridor,
+        payment_schedule.amount,
+        clock.unix_timestamp,
+    )?;
+
+    // The fee was priced at creation; only check the stored split is consistent
+    require!(
+        payment_schedule
+            .net_amount
+            .checked_add(payment_schedule.platform_fee)
+            == Some(payment_schedule.amount),
+        CrossPayError::InvalidFeeCalculation
+    );
+
+    require!(
+        ctx.accounts.sender_token_account.amount >= payment_schedule.amount,
+        CrossPayError::InsufficientBalance
+    );
+
+    let sender_key = payment_schedule.sender;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"schedule_delegate",
+        sender_key.as_ref(),
+        &[ctx.bumps.schedule_delegate],
+    ]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.sender_token_account.to_account_info(),
+        to: ctx.accounts.receiver_token_account.to_account_info(),
+        authority: ctx.accounts.schedule_delegate.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, payment_schedule.net_amount)?;
+
+    // The referral fee was locked in at creation, when the balance was already open
+    let referral_rewards = ctx.accounts.referral_rewards.as_ref().map(|info| info.as_ref());
+    if payment_schedule.referral_fee > 0 {
+        let referrer = ctx
+            .accounts
+            .receiver_profile
+            .referrer
+            .ok_or(CrossPayError::ReferralAccountsRequired)?;
+        require!(
+            referral_rewards.map(|info| info.key())
+                == Some(ReferralRewards::address(&referrer, &payment_schedule.mint)),
+            CrossPayError::ReferralAccountsRequired
+        );
+    }
+    pay_referral_reward(
+        payment_schedule.referral_fee,
+        referral_rewards,
+        ctx.accounts.referral_vault.as_ref(),
+        ctx.accounts.sender_token_account.to_account_info(),
+        ctx.accounts.schedule_delegate.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+    pay_treasury_fee(
+        payment_schedule.platform_fee,
+        payment_schedule.referral_fee,
+        &ctx.accounts.platform_fee_account,
+        ctx.accounts.sender_token_account.to_account_info(),
+        ctx.accounts.schedule_delegate.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    // Advance from the previous due date so late cranks don't drift the schedule
+    payment_schedule.next_due = payment_schedule
+        .next_due
+        .checked_add(payment_schedule.interval)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    payment_schedule.remaining_payments -= 1;
+    payment_schedule.payments_made = payment_schedule
+        .payments_made
+        .checked_add(1)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    ctx.accounts.sender_profile.record_sent(payment_schedule.amount, clock.unix_timestamp)?;
+
+    ctx.accounts.receiver_profile.total_received = ctx
+        .accounts
+        .receiver_profile
+        .total_received
+        .checked_add(payment_schedule.net_amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    msg!(
+        "Scheduled payment executed: {} tokens sent, {} received (fee: {}), {} remaining",
+        payment_schedule.amount,
+        payment_schedule.net_amount,
+        payment_schedule.platform_fee,
+        payment_schedule.remaining_payments
+    );
+
+    Ok(())
+}
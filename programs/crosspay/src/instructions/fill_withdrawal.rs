@@ -0,0 +1,360 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::instructions::finalize_withdrawal::pay_withdrawal;
+use crate::state::*;
+
+/// Context for assigning part of a withdrawal to a liquidity provider
+#[derive(Accounts)]
+#[instruction(provider_key: Pubkey, amount: u64)]
+pub struct AddWithdrawalFill<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = matches!(
+            withdrawal_request.status,
+            WithdrawalStatus::Pending | WithdrawalStatus::PartiallyFilled
+        ) @ CrossPayError::InvalidWithdrawalStatus,
+        constraint = !withdrawal_request.is_rfq() @ CrossPayError::RfqWithdrawal,
+        has_one = freelancer
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        init,
+        payer = freelancer,
+        space = WithdrawalFill::LEN,
+        seeds = [
+            b"withdrawal_fill",
+            withdrawal_request.key().as_ref(),
+            &[withdrawal_request.fill_count]
+        ],
+        bump
+    )]
+    pub withdrawal_fill: Account<'info, WithdrawalFill>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_provider", provider_key.as_ref()],
+        bump = liquidity_provider.bump,
+        constraint = liquidity_provider.is_active @ CrossPayError::ProviderNotActive,
+        constraint = !liquidity_provider.is_frozen @ CrossPayError::AccountFrozen,
+        constraint = liquidity_provider.currency == withdrawal_request.currency @ CrossPayError::CurrencyMismatch,
+        constraint = liquidity_provider.free_liquidity() >= amount @ CrossPayError::InsufficientLiquidity,
+        constraint = liquidity_provider.trust_score >= withdrawal_request.min_trust_score @ CrossPayError::TrustScoreTooLow,
+        constraint = liquidity_provider.supports(withdrawal_request.payout_method) @ CrossPayError::PayoutMethodNotSupported
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Reference rate PDA for the payout currency - may be unconfigured;
+    /// validated in ReferenceRate::load_pair
+    pub reference_rate: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub freelancer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Assign `amount` of the withdrawal to a provider, locking in its rate and fees
+/// Used when no single provider has the liquidity to fill the whole withdrawal.
+pub fn add_withdrawal_fill(
+    ctx: Context<AddWithdrawalFill>,
+    provider_key: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    let clock = Clock::get()?;
+
+    require!(
+        withdrawal_request.fill_count < MAX_WITHDRAWAL_FILLS,
+        CrossPayError::TooManyFills
+    );
+    let filled_amount = withdrawal_request
+        .filled_amount
+        .checked_add(amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    require!(
+        amount > 0 && filled_amount <= withdrawal_request.amount,
+        CrossPayError::InvalidFillAmount
+    );
+    if let Some(reference_rate) =
+        ReferenceRate::load_pair(&ctx.accounts.reference_rate, &withdrawal_request.currency)?
+    {
+        reference_rate.check_rate(liquidity_provider.exchange_rate, clock.unix_timestamp)?;
+    }
+
+    // Each fill is priced on its own portion, so fixed fees apply per fill
+    let (provider_fee, platform_fee) = calculate_withdrawal_fees(
+        amount,
+        liquidity_provider.fee_bps,
+        liquidity_provider.fixed_fee,
+        ctx.accounts.platform_config.offramp_fee_bps,
+    )?;
+    let net_amount = amount - provider_fee - platform_fee;
+
+    // Hold the provider's liquidity so concurrent fills can't overcommit it
+    liquidity_provider.reserve(amount)?;
+
+    let withdrawal_fill = &mut ctx.accounts.withdrawal_fill;
+
+    withdrawal_fill.withdrawal_request = withdrawal_request.key();
+    withdrawal_fill.index = withdrawal_request.fill_count;
+    withdrawal_fill.provider = provider_key;
+    withdrawal_fill.amount = amount;
+    withdrawal_fill.provider_fee = provider_fee;
+    withdrawal_fill.platform_fee = platform_fee;
+    withdrawal_fill.net_amount = net_amount;
+    withdrawal_fill.exchange_rate = liquidity_provider.exchange_rate;
+    withdrawal_fill.status = FillStatus::Pending;
+    withdrawal_fill.created_at = clock.unix_timestamp;
+    withdrawal_fill.completed_at = None;
+    withdrawal_fill.bump = ctx.bumps.withdrawal_fill;
+    withdrawal_fill.expires_at = clock
+        .unix_timestamp
+        .checked_add(WITHDRAWAL_FILL_PERIOD)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    // The parent carries the totals across all of its fills
+    withdrawal_request.filled_amount = filled_amount;
+    withdrawal_request.fill_count += 1;
+    withdrawal_request.provider_fee = withdrawal_request
+        .provider_fee
+        .checked_add(provider_fee)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    withdrawal_request.platform_fee = withdrawal_request
+        .platform_fee
+        .checked_add(platform_fee)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    withdrawal_request.net_amount = withdrawal_request
+        .amount
+        .checked_sub(withdrawal_request.provider_fee)
+        .and_then(|net| net.checked_sub(withdrawal_request.platform_fee))
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    withdrawal_request.status = WithdrawalStatus::PartiallyFilled;
+
+    msg!(
+        "Withdrawal fill {} assigned to {}: {} tokens ({} of {} filled)",
+        withdrawal_fill.index,
+        provider_key,
+        amount,
+        filled_amount,
+        withdrawal_request.amount
+    );
+
+    Ok(())
+}
+
+/// Context for finalizing one fill of a partially filled withdrawal
+#[derive(Accounts)]
+pub struct FinalizeWithdrawalFill<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::PartiallyFilled @ CrossPayError::InvalidWithdrawalStatus,
+        has_one = freelancer
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_fill",
+            withdrawal_request.key().as_ref(),
+            &[withdrawal_fill.index]
+        ],
+        bump = withdrawal_fill.bump,
+        constraint = withdrawal_fill.status == FillStatus::Pending @ CrossPayError::InvalidWithdrawalStatus
+    )]
+    pub withdrawal_fill: Account<'info, WithdrawalFill>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_provider", withdrawal_fill.provider.as_ref()],
+        bump = liquidity_provider.bump,
+        constraint = liquidity_provider.authority == provider_authority.key()
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    #[account(
+        mut,
+        constraint = freelancer_token_account.owner == freelancer.key(),
+        constraint = freelancer_token_account.mint == withdrawal_request.mint
+    )]
+    pub freelancer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == provider_authority.key(),
+        constraint = provider_token_account.mint == withdrawal_request.mint
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == withdrawal_request.mint
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
+    pub freelancer: Signer<'info>,
+
+    #[account(mut)]
+    pub provider_authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Finalize one fill after its fiat is received
+/// The withdrawal completes once it is fully allocated and every fill is final.
+pub fn finalize_withdrawal_fill(ctx: Context<FinalizeWithdrawalFill>) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let withdrawal_fill = &mut ctx.accounts.withdrawal_fill;
+    let clock = Clock::get()?;
+
+    pay_withdrawal(
+        &ctx.accounts.token_program,
+        &ctx.accounts.freelancer_token_account,
+        &ctx.accounts.provider_token_account,
+        &ctx.accounts.platform_fee_account,
+        &ctx.accounts.freelancer,
+        withdrawal_fill.amount,
+        withdrawal_fill.platform_fee,
+    )?;
+
+    withdrawal_fill.status = FillStatus::Completed;
+    withdrawal_fill.completed_at = Some(clock.unix_timestamp);
+
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    liquidity_provider.release_reserved(withdrawal_fill.amount);
+    liquidity_provider.record_completed(withdrawal_fill.amount)?;
+
+    withdrawal_request.completed_fills += 1;
+    if withdrawal_request.completed_fills + withdrawal_request.cancelled_fills
+        == withdrawal_request.fill_count
+        && withdrawal_request.filled_amount == withdrawal_request.amount
+    {
+        withdrawal_request.status = WithdrawalStatus::Completed;
+        withdrawal_request.completed_at = Some(clock.unix_timestamp);
+    }
+
+    msg!(
+        "Withdrawal fill {} finalized: {} tokens ({} of {} fills complete)",
+        withdrawal_fill.index,
+        withdrawal_fill.amount,
+        withdrawal_request.completed_fills,
+        withdrawal_request.fill_count
+    );
+
+    Ok(())
+}
+
+/// Context for cancelling a pending fill
+/// The fill's provider may cancel at any time; anyone may once it has timed out.
+#[derive(Accounts)]
+pub struct CancelWithdrawalFill<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::PartiallyFilled @ CrossPayError::InvalidWithdrawalStatus
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_fill",
+            withdrawal_request.key().as_ref(),
+            &[withdrawal_fill.index]
+        ],
+        bump = withdrawal_fill.bump,
+        constraint = withdrawal_fill.status == FillStatus::Pending @ CrossPayError::InvalidWithdrawalStatus
+    )]
+    pub withdrawal_fill: Account<'info, WithdrawalFill>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_provider", withdrawal_fill.provider.as_ref()],
+        bump = liquidity_provider.bump
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Cancel a pending fill, releasing the provider's liquidity and returning its
+/// portion of the withdrawal to the unfilled remainder
+pub fn cancel_withdrawal_fill(ctx: Context<CancelWithdrawalFill>) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let withdrawal_fill = &mut ctx.accounts.withdrawal_fill;
+    let clock = Clock::get()?;
+
+    if ctx.accounts.authority.key() != withdrawal_fill.provider {
+        require!(
+            clock.unix_timestamp >= withdrawal_fill.expires_at,
+            CrossPayError::FillNotExpired
+        );
+    }
+
+    withdrawal_fill.status = FillStatus::Cancelled;
+    ctx.accounts
+        .liquidity_provider
+        .release_reserved(withdrawal_fill.amount);
+
+    withdrawal_request.filled_amount = withdrawal_request
+        .filled_amount
+        .checked_sub(withdrawal_fill.amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    withdrawal_request.provider_fee = withdrawal_request
+        .provider_fee
+        .checked_sub(withdrawal_fill.provider_fee)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    withdrawal_request.platform_fee = withdrawal_request
+        .platform_fee
+        .checked_sub(withdrawal_fill.platform_fee)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    withdrawal_request.net_amount = withdrawal_request
+        .amount
+        .checked_sub(withdrawal_request.provider_fee)
+        .and_then(|net| net.checked_sub(withdrawal_request.platform_fee))
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    withdrawal_request.cancelled_fills += 1;
+    // With nothing left allocated the withdrawal is open again
+    if withdrawal_request.filled_amount == 0 {
+        withdrawal_request.status = WithdrawalStatus::Pending;
+    }
+
+    msg!(
+        "Withdrawal fill {} cancelled: {} tokens returned ({} of {} filled)",
+        withdrawal_fill.index,
+        withdrawal_fill.amount,
+        withdrawal_request.filled_amount,
+        withdrawal_request.amount
+    );
+
+    Ok(())
+}
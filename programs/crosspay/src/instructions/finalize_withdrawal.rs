@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
+use crate::constants::*;
 use crate::errors::CrossPayError;
 use crate::state::*;
 
@@ -45,6 +46,13 @@ pub struct FinalizeWithdrawal<'info> {
     )]
     pub provider_token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == withdrawal_request.mint
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
     pub freelancer: Signer<'info>,
 
     #[account(mut)]
@@ -59,30 +67,68 @@ pub fn finalize_withdrawal(ctx: Context<FinalizeWithdrawal>) -> Result<()> {
     let liquidity_provider = &mut ctx.accounts.liquidity_provider;
     let clock = Clock::get()?;
 
-    // Transfer tokens from freelancer to liquidity provider via CPI
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.freelancer_token_account.to_account_info(),
-        to: ctx.accounts.provider_token_account.to_account_info(),
-        authority: ctx.accounts.freelancer.to_account_info(),
-    };
-
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-
-    token::transfer(cpi_ctx, withdrawal_request.amount)?;
+    pay_withdrawal(
+        &ctx.accounts.token_program,
+        &ctx.accounts.freelancer_token_account,
+        &ctx.accounts.provider_token_account,
+        &ctx.accounts.platform_fee_account,
+        &ctx.accounts.freelancer,
+        withdrawal_request.amount,
+        withdrawal_request.platform_fee,
+    )?;
 
     // Update withdrawal status
     withdrawal_request.status = WithdrawalStatus::Completed;
     withdrawal_request.completed_at = Some(clock.unix_timestamp);
 
     // Update liquidity provider stats
-    liquidity_provider.total_volume += withdrawal_request.amount;
-    liquidity_provider.completed_transactions += 1;
-    liquidity_provider.available_liquidity = liquidity_provider
-        .available_liquidity
-        .saturating_sub(withdrawal_request.amount);
+    liquidity_provider.record_completed(withdrawal_request.amount)?;
 
-    msg!("Withdrawal finalized: {} tokens", withdrawal_request.amount);
+    msg!(
+        "Withdrawal finalized: {} tokens (provider fee: {}, platform fee: {}, net: {})",
+        withdrawal_request.amount,
+        withdrawal_request.provider_fee,
+        withdrawal_request.platform_fee,
+        withdrawal_request.net_amount
+    );
+
+    Ok(())
+}
+
+/// Move a withdrawal (or fill) of `amount` tokens from the freelancer
+///
+/// The provider receives everything except the platform's off-ramp fee;
+/// it keeps its own fee and pays out the net amount in fiat.
+pub(crate) fn pay_withdrawal<'info>(
+    token_program: &Program<'info, Token>,
+    freelancer_token_account: &Account<'info, TokenAccount>,
+    provider_token_account: &Account<'info, TokenAccount>,
+    platform_fee_account: &Account<'info, TokenAccount>,
+    freelancer: &Signer<'info>,
+    amount: u64,
+    platform_fee: u64,
+) -> Result<()> {
+    let provider_amount = amount
+        .checked_sub(platform_fee)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    let cpi_accounts = Transfer {
+        from: freelancer_token_account.to_account_info(),
+        to: provider_token_account.to_account_info(),
+        authority: freelancer.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, provider_amount)?;
+
+    if platform_fee > 0 {
+        let fee_cpi_accounts = Transfer {
+            from: freelancer_token_account.to_account_info(),
+            to: platform_fee_account.to_account_info(),
+            authority: freelancer.to_account_info(),
+        };
+        let fee_cpi_ctx = CpiContext::new(token_program.to_account_info(), fee_cpi_accounts);
+        token::transfer(fee_cpi_ctx, platform_fee)?;
+    }
 
     Ok(())
 }
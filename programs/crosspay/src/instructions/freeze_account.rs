@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for a compliance officer freezing or unfreezing a user profile
+#[derive(Accounts)]
+pub struct SetUserFrozen<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user_profile.authority.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"compliance_officer", officer.key().as_ref()],
+        bump = compliance_officer.bump,
+        has_one = officer @ CrossPayError::Unauthorized
+    )]
+    pub compliance_officer: Account<'info, ComplianceOfficer>,
+
+    pub officer: Signer<'info>,
+}
+
+/// Freeze a user profile, blocking any new requests, or lift the freeze
+pub fn set_user_frozen(
+    ctx: Context<SetUserFrozen>,
+    frozen: bool,
+    reason: ComplianceReason,
+) -> Result<()> {
+    require!(
+        !frozen || reason != ComplianceReason::None,
+        CrossPayError::InvalidComplianceReason
+    );
+
+    let user_profile = &mut ctx.accounts.user_profile;
+
+    user_profile.is_frozen = frozen;
+    user_profile.freeze_reason = if frozen {
+        reason
+    } else {
+        ComplianceReason::None
+    };
+
+    msg!(
+        "User {} frozen: {} by {}",
+        user_profile.authority,
+        frozen,
+        ctx.accounts.officer.key()
+    );
+
+    Ok(())
+}
+
+/// Context for a compliance officer freezing or unfreezing a liquidity provider
+#[derive(Accounts)]
+pub struct SetProviderFrozen<'info> {
+    #[account(
+        mut,
+        seeds = [b"liquidity_provider", liquidity_provider.authority.as_ref()],
+        bump = liquidity_provider.bump
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    #[account(
+        seeds = [b"compliance_officer", officer.key().as_ref()],
+        bump = compliance_officer.bump,
+        has_one = officer @ CrossPayError::Unauthorized
+    )]
+    pub compliance_officer: Account<'info, ComplianceOfficer>,
+
+    pub officer: Signer<'info>,
+}
+
+/// Freeze a liquidity provider so it cannot be selected, or lift the freeze
+pub fn set_provider_frozen(
+    ctx: Context<SetProviderFrozen>,
+    frozen: bool,
+    reason: ComplianceReason,
+) -> Result<()> {
+    require!(
+        !frozen || reason != ComplianceReason::None,
+        CrossPayError::InvalidComplianceReason
+    );
+
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+
+    liquidity_provider.is_frozen = frozen;
+    liquidity_provider.freeze_reason = if frozen {
+        reason
+    } else {
+        ComplianceReason::None
+    };
+
+    msg!(
+        "Provider {} frozen: {} by {}",
+        liquidity_provider.authority,
+        frozen,
+        ctx.accounts.officer.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for the platform admin creating the fee schedule
+#[derive(Accounts)]
+pub struct InitializeFeeSchedule<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = FeeSchedule::LEN,
+        seeds = [b"fee_schedule"],
+        bump
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the volume-based fee schedule
+pub fn initialize_fee_schedule(
+    ctx: Context<InitializeFeeSchedule>,
+    tiers: Vec<FeeTier>,
+) -> Result<()> {
+    FeeSchedule::validate_tiers(&tiers)?;
+
+    let fee_schedule = &mut ctx.accounts.fee_schedule;
+
+    fee_schedule.tiers = tiers;
+    fee_schedule.bump = ctx.bumps.fee_schedule;
+
+    msg!(
+        "Fee schedule initialized with {} tiers",
+        fee_schedule.tiers.len()
+    );
+
+    Ok(())
+}
+
+/// Context for the platform admin replacing the fee tiers
+#[derive(Accounts)]
+pub struct UpdateFeeTiers<'info> {
+    #[account(
+        mut,
+        seeds = [b"fee_schedule"],
+        bump = fee_schedule.bump
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Replace the fee tiers
+pub fn update_fee_tiers(ctx: Context<UpdateFeeTiers>, tiers: Vec<FeeTier>) -> Result<()> {
+    FeeSchedule::validate_tiers(&tiers)?;
+
+    let fee_schedule = &mut ctx.accounts.fee_schedule;
+
+    fee_schedule.tiers = tiers;
+
+    msg!("Fee tiers updated: {} tiers", fee_schedule.tiers.len());
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for the platform admin creating a mint's fee config
+#[derive(Accounts)]
+pub struct InitializeMintFeeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = MintFeeConfig::LEN,
+        seeds = [b"mint_fee_config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_fee_config: Account<'info, MintFeeConfig>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Configure the fee floor, ceiling and rounding for a mint
+pub fn initialize_mint_fee_config(
+    ctx: Context<InitializeMintFeeConfig>,
+    min_fee: u64,
+    max_fee: u64,
+    rounding: FeeRounding,
+) -> Result<()> {
+    require!(
+        max_fee == 0 || min_fee <= max_fee,
+        CrossPayError::InvalidMintFeeConfig
+    );
+
+    let mint_fee_config = &mut ctx.accounts.mint_fee_config;
+
+    mint_fee_config.mint = ctx.accounts.mint.key();
+    mint_fee_config.min_fee = min_fee;
+    mint_fee_config.max_fee = max_fee;
+    mint_fee_config.rounding = rounding;
+    mint_fee_config.bump = ctx.bumps.mint_fee_config;
+
+    msg!(
+        "Mint fee config initialized for {} - Min: {}, Max: {}",
+        mint_fee_config.mint,
+        min_fee,
+        max_fee
+    );
+
+    Ok(())
+}
+
+/// Context for the platform admin updating a mint's fee config
+#[derive(Accounts)]
+pub struct UpdateMintFeeConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"mint_fee_config", mint_fee_config.mint.as_ref()],
+        bump = mint_fee_config.bump
+    )]
+    pub mint_fee_config: Account<'info, MintFeeConfig>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Update the fee floor, ceiling and rounding for a mint
+pub fn update_mint_fee_config(
+    ctx: Context<UpdateMintFeeConfig>,
+    min_fee: u64,
+    max_fee: u64,
+    rounding: FeeRounding,
+) -> Result<()> {
+    require!(
+        max_fee == 0 || min_fee <= max_fee,
+        CrossPayError::InvalidMintFeeConfig
+    );
+
+    let mint_fee_config = &mut ctx.accounts.mint_fee_config;
+
+    mint_fee_config.min_fee = min_fee;
+    mint_fee_config.max_fee = max_fee;
+    mint_fee_config.rounding = rounding;
+
+    msg!(
+        "Mint fee config updated for {} - Min: {}, Max: {}",
+        mint_fee_config.mint,
+        min_fee,
+        max_fee
+    );
+
+    Ok(())
+}
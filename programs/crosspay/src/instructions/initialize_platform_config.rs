@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::program::Crosspay;
+use crate::state::*;
+
+/// Context for creating the singleton platform configuration
+/// Only the program's upgrade authority may initialize it
+#[derive(Accounts)]
+pub struct InitializePlatformConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = PlatformConfig::LEN,
+        seeds = [b"platform_config"],
+        bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ CrossPayError::Unauthorized
+    )]
+    pub program: Program<'info, Crosspay>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ CrossPayError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initialize the platform configuration with the signer as admin
+pub fn initialize_platform_config(
+    ctx: Context<InitializePlatformConfig>,
+    large_transfer_threshold: u64,
+    hold_period: i64,
+) -> Result<()> {
+    require!(
+        (0..=MAX_HOLD_PERIOD).contains(&hold_period),
+        CrossPayError::InvalidHoldPeriod
+    );
+
+    let platform_config = &mut ctx.accounts.platform_config;
+
+    platform_config.admin = ctx.accounts.authority.key();
+    platform_config.large_transfer_threshold = large_transfer_threshold;
+    platform_config.hold_period = hold_period;
+    platform_config.bump = ctx.bumps.platform_config;
+    platform_config.travel_rule_threshold = 0;
+    platform_config.referral_share_bps = 0;
+    platform_config.offramp_fee_bps = 0;
+
+    msg!(
+        "Platform config initialized - Admin: {}",
+        platform_config.admin
+    );
+
+    Ok(())
+}
+
+/// Context for updating the large-transfer hold settings
+#[derive(Accounts)]
+pub struct UpdateHoldSettings<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Update the threshold and cool-off period for large transfers
+pub fn update_hold_settings(
+    ctx: Context<UpdateHoldSettings>,
+    large_transfer_threshold: u64,
+    hold_period: i64,
+) -> Result<()> {
+    require!(
+        (0..=MAX_HOLD_PERIOD).contains(&hold_period),
+        CrossPayError::InvalidHoldPeriod
+    );
+
+    let platform_config = &mut ctx.accounts.platform_config;
+
+    platform_config.large_transfer_threshold = large_transfer_threshold;
+    platform_config.hold_period = hold_period;
+
+    msg!(
+        "Hold settings updated - Threshold: {}, Period: {}s",
+        large_transfer_threshold,
+        hold_period
+    );
+
+    Ok(())
+}
+
+/// Context for updating the travel-rule threshold
+#[derive(Accounts)]
+pub struct UpdateTravelRuleThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the amount at or above which transfers must carry travel-rule data
+pub fn update_travel_rule_threshold(
+    ctx: Context<UpdateTravelRuleThreshold>,
+    travel_rule_threshold: u64,
+) -> Result<()> {
+    ctx.accounts.platform_config.travel_rule_threshold = travel_rule_threshold;
+
+    msg!("Travel-rule threshold updated: {}", travel_rule_threshold);
+
+    Ok(())
+}
+
+/// Context for updating the referral share of platform fees
+#[derive(Accounts)]
+pub struct UpdateReferralShare<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the share of each platform fee credited to the receiver's referrer
+pub fn update_referral_share(
+    ctx: Context<UpdateReferralShare>,
+    referral_share_bps: u16,
+) -> Result<()> {
+    require!(
+        referral_share_bps as u64 <= BASIS_POINTS_DIVISOR,
+        CrossPayError::InvalidReferralShare
+    );
+
+    ctx.accounts.platform_config.referral_share_bps = referral_share_bps;
+
+    msg!("Referral share updated: {} bps", referral_share_bps);
+
+    Ok(())
+}
+
+/// Context for updating the platform fee on withdrawals
+#[derive(Accounts)]
+pub struct UpdateOfframpFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the platform's off-ramp fee, charged on withdrawals at provider selection
+pub fn update_offramp_fee(ctx: Context<UpdateOfframpFee>, offramp_fee_bps: u16) -> Result<()> {
+    require!(
+        offramp_fee_bps <= MAX_WITHDRAWAL_FEE_BPS,
+        CrossPayError::InvalidWithdrawalFee
+    );
+
+    ctx.accounts.platform_config.offramp_fee_bps = offramp_fee_bps;
+
+    msg!("Off-ramp fee updated: {} bps", offramp_fee_bps);
+
+    Ok(())
+}
+
+/// Context for creating the holding vault for a mint (permissionless)
+#[derive(Accounts)]
+pub struct InitializeHoldVault<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"hold_vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = platform_config
+    )]
+    pub hold_vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Create the vault that holds large transfers during their cool-off
+pub fn initialize_hold_vault(ctx: Context<InitializeHoldVault>) -> Result<()> {
+    msg!(
+        "Hold vault initialized for mint: {}",
+        ctx.accounts.mint.key()
+    );
+
+    Ok(())
+}
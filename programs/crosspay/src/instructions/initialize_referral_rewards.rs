@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::*;
+
+/// Context for creating the vault funding referral rewards for a mint (permissionless)
+#[derive(Accounts)]
+pub struct InitializeReferralVault<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"referral_vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = platform_config
+    )]
+    pub referral_vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Create the vault that holds referral rewards until they are claimed
+pub fn initialize_referral_vault(ctx: Context<InitializeReferralVault>) -> Result<()> {
+    msg!(
+        "Referral vault initialized for mint: {}",
+        ctx.accounts.mint.key()
+    );
+
+    Ok(())
+}
+
+/// Context for opening a referrer's rewards balance in a mint (permissionless)
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct InitializeReferralRewards<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = ReferralRewards::LEN,
+        seeds = [b"referral_rewards", referrer.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub referral_rewards: Account<'info, ReferralRewards>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Open the balance that fee-charging payments credit a referrer's rewards to
+pub fn initialize_referral_rewards(
+    ctx: Context<InitializeReferralRewards>,
+    referrer: Pubkey,
+) -> Result<()> {
+    let referral_rewards = &mut ctx.accounts.referral_rewards;
+
+    referral_rewards.referrer = referrer;
+    referral_rewards.mint = ctx.accounts.mint.key();
+    referral_rewards.claimable = 0;
+    referral_rewards.total_earned = 0;
+    referral_rewards.bump = ctx.bumps.referral_rewards;
+
+    msg!(
+        "Referral rewards opened for {} in mint {}",
+        referrer,
+        referral_rewards.mint
+    );
+
+    Ok(())
+}
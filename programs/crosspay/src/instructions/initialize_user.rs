@@ -1,3 +1,4 @@
+use crate::constants::is_valid_country_code;
 use crate::errors::CrossPayError;
 use crate::state::*;
 use anchor_lang::prelude::*;
@@ -14,6 +15,13 @@ pub struct InitializeUser<'info> {
     )]
     pub user_profile: Account<'info, UserProfile>,
 
+    /// Only required when a referrer is given; they must already be registered
+    #[account(
+        seeds = [b"user_profile", referrer_profile.authority.as_ref()],
+        bump = referrer_profile.bump
+    )]
+    pub referrer_profile: Option<Account<'info, UserProfile>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -25,8 +33,28 @@ pub fn initialize_user(
     ctx: Context<InitializeUser>,
     role: UserRole,
     country_code: String,
+    referrer: Option<Pubkey>,
 ) -> Result<()> {
-    require!(country_code.len() <= 3, CrossPayError::InvalidCountryCode);
+    require!(
+        is_valid_country_code(&country_code),
+        CrossPayError::InvalidCountryCode
+    );
+    require!(
+        referrer != Some(ctx.accounts.authority.key()),
+        CrossPayError::InvalidReferrer
+    );
+    if let Some(referrer) = referrer {
+        let referrer_profile = ctx
+            .accounts
+            .referrer_profile
+            .as_ref()
+            .ok_or(CrossPayError::InvalidReferrer)?;
+        require_keys_eq!(
+            referrer_profile.authority,
+            referrer,
+            CrossPayError::InvalidReferrer
+        );
+    }
 
     let user_profile = &mut ctx.accounts.user_profile;
     let clock = Clock::get()?;
@@ -40,6 +68,9 @@ pub fn initialize_user(
     user_profile.total_sent = 0;
     user_profile.total_received = 0;
     user_profile.bump = ctx.bumps.user_profile;
+    user_profile.referrer = referrer;
+    user_profile.transfer_nonce = 0;
+    user_profile.withdrawal_nonce = 0;
 
     msg!(
         "User profile initialized for: {}",
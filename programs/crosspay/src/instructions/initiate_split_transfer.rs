@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for initiating a transfer split across several receivers
+///
+/// The lead receiver (first split entry) is used for PDA derivation so
+/// split transfers share the TransferRequest address space.
+#[derive(Accounts)]
+pub struct InitiateSplitTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", sender.key().as_ref()],
+        bump = sender_profile.bump,
+        has_one = authority,
+        constraint = sender_profile.kyc_verified @ CrossPayError::KycNotVerified,
+        constraint = !sender_profile.is_frozen @ CrossPayError::AccountFrozen
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TransferRequest::LEN,
+        seeds = [
+            b"transfer_request",
+            sender.key().as_ref(),
+            lead_receiver.key().as_ref(),
+            &sender_profile.transfer_nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+
+    #[account(mut)]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Fee schedule PDA - may be unconfigured
+    #[account(
+        seeds = [b"fee_schedule"],
+        bump
+    )]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Fee config PDA for the mint - may be unconfigured
+    #[account(
+        seeds = [b"mint_fee_config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_fee_config: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", sender.key().as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Lead receiver pubkey - must match the first split entry
+    pub lead_receiver: UncheckedAccount<'info>,
+
+    /// CHECK: Sender pubkey - used for PDA derivation
+    pub sender: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Initiate a transfer whose net amount is shared by basis-point splits
+pub fn initiate_split_transfer(
+    ctx: Context<InitiateSplitTransfer>,
+    amount: u64,
+    splits: Vec<SplitShare>,
+) -> Result<()> {
+    require!(amount > 0, CrossPayError::InvalidAmount);
+    require!(
+        ctx.accounts.sender_token_account.amount >= amount,
+        CrossPayError::InsufficientBalance
+    );
+
+    require!(
+        splits.len() >= 2 && splits.len() <= MAX_SPLIT_RECEIVERS,
+        CrossPayError::InvalidSplit
+    );
+    require_keys_eq!(
+        splits[0].receiver,
+        ctx.accounts.lead_receiver.key(),
+        CrossPayError::InvalidSplit
+    );
+
+    let sender = ctx.accounts.sender.key();
+    for (i, split) in splits.iter().enumerate() {
+        require!(split.share_bps > 0, CrossPayError::InvalidSplit);
+        require_keys_neq!(split.receiver, sender, CrossPayError::InvalidSplit);
+        require!(
+            splits[..i]
+                .iter()
+                .all(|other| other.receiver != split.receiver),
+            CrossPayError::InvalidSplit
+        );
+    }
+
+    let clock = Clock::get()?;
+
+    // Fee tier follows the sender's volume over the current window. Receivers may
+    // sit in different corridors, so a split is priced without corridor overrides.
+    let sender_volume = ctx.accounts.sender_profile.volume_at(clock.unix_timestamp);
+    let (fee_bps, platform_fee) = FeeResolver::load(
+        &ctx.accounts.fee_schedule,
+        &ctx.accounts.mint_fee_config,
+        sender_volume,
+    )?
+    .resolve(None, amount)?;
+    let net_amount = amount
+        .checked_sub(platform_fee)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    // Rejects shares that don't sum to exactly 10000 bps
+    let shares_bps: Vec<u16> = splits.iter().map(|split| split.share_bps).collect();
+    calculate_split_amounts(net_amount, &shares_bps).ok_or(CrossPayError::InvalidSplit)?;
+
+    let transfer_request = &mut ctx.accounts.transfer_request;
+
+    transfer_request.sender = sender;
+    transfer_request.receiver = ctx.accounts.lead_receiver.key();
+    transfer_request.amount = amount;
+    transfer_request.net_amount = net_amount;
+    transfer_request.platform_fee = platform_fee;
+    transfer_request.fee_bearer = FeeBearer::Receiver;
+    transfer_request.fee_bps = fee_bps;
+    transfer_request.mint = ctx.accounts.mint.key();
+    transfer_request.status = TransferStatus::Pending;
+    transfer_request.created_at = clock.unix_timestamp;
+    transfer_request.completed_at = None;
+    transfer_request.nonce = ctx.accounts.sender_profile.next_transfer_nonce()?;
+    transfer_request.bump = ctx.bumps.transfer_request;
+    transfer_request.referral_reward = 0;
+    transfer_request.refunded_amount = 0;
+    transfer_request.refund_count = 0;
+    transfer_request.splits = splits;
+    transfer_request.release_at = None;
+    transfer_request.travel_rule = None;
+
+    msg!(
+        "Split transfer initiated: {} tokens across {} receivers",
+        amount,
+        transfer_request.splits.len()
+    );
+
+    Ok(())
+}
@@ -2,17 +2,18 @@ use crate::errors::CrossPayError;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
-use crate::constants::{calculate_net_amount, calculate_platform_fee};
 
 /// Context for initiating a transfer
 #[derive(Accounts)]
 #[instruction(amount: u64, receiver_key: Pubkey)]
 pub struct InitiateTransfer<'info> {
     #[account(
+        mut,
         seeds = [b"user_profile", sender.key().as_ref()],
         bump = sender_profile.bump,
         has_one = authority,
-        constraint = sender_profile.kyc_verified @ CrossPayError::KycNotVerified
+        constraint = sender_profile.kyc_verified @ CrossPayError::KycNotVerified,
+        constraint = !sender_profile.is_frozen @ CrossPayError::AccountFrozen
     )]
     pub sender_profile: Account<'info, UserProfile>,
 
@@ -24,12 +25,59 @@ pub struct InitiateTransfer<'info> {
             b"transfer_request",
             sender.key().as_ref(),
             receiver_key.as_ref(),
-            &sender_profile.total_sent.to_le_bytes()
+            &sender_profile.transfer_nonce.to_le_bytes()
         ],
         bump
     )]
     pub transfer_request: Account<'info, TransferRequest>,
 
+    #[account(
+        seeds = [b"user_profile", receiver_key.as_ref()],
+        bump = receiver_profile.bump
+    )]
+    pub receiver_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Corridor PDA for the sender and receiver countries - may be unconfigured
+    #[account(
+        seeds = [
+            b"corridor",
+            sender_profile.country_code.as_bytes(),
+            receiver_profile.country_code.as_bytes()
+        ],
+        bump
+    )]
+    pub corridor: UncheckedAccount<'info>,
+
+    /// CHECK: Fee schedule PDA - may be unconfigured
+    #[account(
+        seeds = [b"fee_schedule"],
+        bump
+    )]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Fee config PDA for the mint - may be unconfigured
+    #[account(
+        seeds = [b"mint_fee_config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_fee_config: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", sender.key().as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the receiver - must not exist
+    #[account(
+        seeds = [b"denied_address", receiver_key.as_ref()],
+        bump,
+        constraint = receiver_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub receiver_denial: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub sender_token_account: Account<'info, TokenAccount>,
 
@@ -53,31 +101,55 @@ pub fn initiate_transfer(
     ctx: Context<InitiateTransfer>,
     amount: u64,
     receiver: Pubkey,
+    fee_bearer: FeeBearer,
 ) -> Result<()> {
     require!(amount > 0, CrossPayError::InvalidAmount);
+
+    let clock = Clock::get()?;
+
+    // The corridor may disable the route, bound the amount or override the fee
+    let corridor = Corridor::load(&ctx.accounts.corridor)?;
+    if let Some(corridor) = &corridor {
+        corridor.check_transfer(amount, clock.unix_timestamp)?;
+    }
+
+    // Fee tier follows the sender's volume over the current window
+    let sender_volume = ctx.accounts.sender_profile.volume_at(clock.unix_timestamp);
+    let (fee_bps, platform_fee) = FeeResolver::load(
+        &ctx.accounts.fee_schedule,
+        &ctx.accounts.mint_fee_config,
+        sender_volume,
+    )?
+    .resolve(corridor.as_ref(), amount)?;
+
+    let transfer_request = &mut ctx.accounts.transfer_request;
+
+    // Calculate net amount
+    let (net_amount, sender_debit) = fee_bearer.price(amount, platform_fee)?;
     require!(
-        ctx.accounts.sender_token_account.amount >= amount,
+        ctx.accounts.sender_token_account.amount >= sender_debit,
         CrossPayError::InsufficientBalance
     );
 
-    let transfer_request = &mut ctx.accounts.transfer_request;
-    let clock = Clock::get()?;
-
-    // Calculate fee and net amount
-    let platform_fee = calculate_platform_fee(amount);
-    let net_amount = calculate_net_amount(amount);
-    
     transfer_request.sender = ctx.accounts.sender.key();
     transfer_request.receiver = receiver;
     transfer_request.amount = amount;           // Gross amount
     transfer_request.net_amount = net_amount;   // Amount receiver gets
     transfer_request.platform_fee = platform_fee;
+    transfer_request.fee_bps = fee_bps;
+    transfer_request.fee_bearer = fee_bearer;
     transfer_request.mint = ctx.accounts.mint.key();
     transfer_request.status = TransferStatus::Pending;
     transfer_request.created_at = clock.unix_timestamp;
     transfer_request.completed_at = None;
-    transfer_request.nonce = ctx.accounts.sender_profile.total_sent;
+    transfer_request.nonce = ctx.accounts.sender_profile.next_transfer_nonce()?;
     transfer_request.bump = ctx.bumps.transfer_request;
+    transfer_request.referral_reward = 0;
+    transfer_request.refunded_amount = 0;
+    transfer_request.refund_count = 0;
+    transfer_request.splits = Vec::new();
+    transfer_request.release_at = None;
+    transfer_request.travel_rule = None;
 
     msg!("Transfer initiated: {} tokens to {}", amount, receiver);
 
@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for a freelancer setting a target rate on their withdrawal
+#[derive(Accounts)]
+pub struct SetLimitOrder<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::Pending @ CrossPayError::InvalidWithdrawalStatus,
+        constraint = !withdrawal_request.is_rfq() @ CrossPayError::RfqWithdrawal,
+        has_one = freelancer
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    pub freelancer: Signer<'info>,
+}
+
+/// Only convert once a provider pays out at least `min_rate` after fees
+/// An `expires_at` of 0 keeps the order open indefinitely; a `min_rate` of 0
+/// (with no expiry) cancels the limit.
+pub fn set_limit_order(ctx: Context<SetLimitOrder>, min_rate: u64, expires_at: i64) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let clock = Clock::get()?;
+
+    require!(
+        expires_at == 0 || (min_rate > 0 && expires_at > clock.unix_timestamp),
+        CrossPayError::InvalidLimitOrder
+    );
+
+    withdrawal_request.min_rate = min_rate;
+    withdrawal_request.order_expires_at = expires_at;
+
+    msg!(
+        "Limit order set - Min rate: {}, Expires at: {}",
+        min_rate,
+        expires_at
+    );
+
+    Ok(())
+}
+
+/// Context for binding a limit order to a qualifying provider (permissionless crank)
+#[derive(Accounts)]
+pub struct MatchLimitOrder<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::Pending @ CrossPayError::InvalidWithdrawalStatus,
+        constraint = withdrawal_request.min_rate > 0 @ CrossPayError::NotLimitOrder
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        seeds = [b"liquidity_provider", liquidity_provider.authority.as_ref()],
+        bump = liquidity_provider.bump,
+        constraint = liquidity_provider.is_active @ CrossPayError::ProviderNotActive,
+        constraint = !liquidity_provider.is_frozen @ CrossPayError::AccountFrozen,
+        constraint = liquidity_provider.currency == withdrawal_request.currency @ CrossPayError::CurrencyMismatch,
+        constraint = liquidity_provider.free_liquidity() >= withdrawal_request.amount @ CrossPayError::InsufficientLiquidity,
+        constraint = liquidity_provider.trust_score >= withdrawal_request.min_trust_score @ CrossPayError::TrustScoreTooLow,
+        constraint = liquidity_provider.supports(withdrawal_request.payout_method) @ CrossPayError::PayoutMethodNotSupported
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Reference rate PDA for the payout currency - may be unconfigured;
+    /// validated in ReferenceRate::load_pair
+    pub reference_rate: UncheckedAccount<'info>,
+}
+
+/// Bind a limit order to a provider whose payout after fees meets or beats the target
+pub fn match_limit_order(ctx: Context<MatchLimitOrder>) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let liquidity_provider = &ctx.accounts.liquidity_provider;
+    let clock = Clock::get()?;
+
+    require!(
+        !withdrawal_request.order_expired(clock.unix_timestamp),
+        CrossPayError::LimitOrderExpired
+    );
+    if let Some(reference_rate) =
+        ReferenceRate::load_pair(&ctx.accounts.reference_rate, &withdrawal_request.currency)?
+    {
+        reference_rate.check_rate(liquidity_provider.exchange_rate, clock.unix_timestamp)?;
+    }
+
+    require!(
+        withdrawal_request.accepts_rate(
+            liquidity_provider.exchange_rate,
+            liquidity_provider.fee_bps,
+            liquidity_provider.fixed_fee,
+            ctx.accounts.platform_config.offramp_fee_bps,
+        )?,
+        CrossPayError::RateBelowLimit
+    );
+
+    withdrawal_request.select_provider(
+        liquidity_provider.authority,
+        liquidity_provider.exchange_rate,
+        liquidity_provider.fee_bps,
+        liquidity_provider.fixed_fee,
+        ctx.accounts.platform_config.offramp_fee_bps,
+    )?;
+
+    msg!(
+        "Limit order matched with {} - Rate: {} (target {}), Net: {}",
+        liquidity_provider.authority,
+        liquidity_provider.exchange_rate,
+        withdrawal_request.min_rate,
+        withdrawal_request.net_amount
+    );
+
+    Ok(())
+}
+
+/// Context for expiring an unfilled limit order (permissionless crank)
+#[derive(Accounts)]
+pub struct ExpireLimitOrder<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::Pending @ CrossPayError::InvalidWithdrawalStatus,
+        constraint = withdrawal_request.min_rate > 0 @ CrossPayError::NotLimitOrder
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+}
+
+/// Close out a limit order that found no provider before its expiry
+pub fn expire_limit_order(ctx: Context<ExpireLimitOrder>) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let clock = Clock::get()?;
+
+    require!(
+        withdrawal_request.order_expired(clock.unix_timestamp),
+        CrossPayError::LimitOrderNotExpired
+    );
+
+    withdrawal_request.status = WithdrawalStatus::Expired;
+
+    msg!(
+        "Limit order expired unfilled - target rate {}",
+        withdrawal_request.min_rate
+    );
+
+    Ok(())
+}
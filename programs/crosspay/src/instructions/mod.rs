@@ -1,15 +1,81 @@
+pub mod accept_withdrawal;
+pub mod add_compliance_officer;
+pub mod add_denied_address;
+pub mod approve_milestone;
+pub mod attach_travel_rule;
+pub mod batch_transfer;
+pub mod claim_referral_rewards;
+pub mod claim_transfer;
+pub mod confirm_split_transfer;
 pub mod confirm_transfer;
+pub mod create_claimable_transfer;
+pub mod create_contract;
+pub mod create_corridor;
+pub mod create_invoice;
+pub mod create_payment_schedule;
+pub mod create_reference_rate;
+pub mod create_stream;
+pub mod execute_scheduled_payment;
+pub mod fill_withdrawal;
 pub mod finalize_withdrawal;
+pub mod freeze_account;
+pub mod initialize_fee_schedule;
+pub mod initialize_mint_fee_config;
+pub mod initialize_platform_config;
+pub mod initialize_referral_rewards;
 pub mod initialize_user;
+pub mod initiate_split_transfer;
 pub mod initiate_transfer;
+pub mod match_limit_order;
+pub mod pay_invoice;
+pub mod place_compliance_hold;
+pub mod refund_transfer;
 pub mod register_liquidity_provider;
+pub mod release_held_transfer;
 pub mod request_withdrawal;
+pub mod select_best_quote;
 pub mod select_provider;
+pub mod submit_milestone;
+pub mod submit_quote;
+pub mod withdraw_from_stream;
 
+pub use accept_withdrawal::*;
+pub use add_compliance_officer::*;
+pub use add_denied_address::*;
+pub use approve_milestone::*;
+pub use attach_travel_rule::*;
+pub use batch_transfer::*;
+pub use claim_referral_rewards::*;
+pub use claim_transfer::*;
+pub use confirm_split_transfer::*;
 pub use confirm_transfer::*;
+pub use create_claimable_transfer::*;
+pub use create_contract::*;
+pub use create_corridor::*;
+pub use create_invoice::*;
+pub use create_payment_schedule::*;
+pub use create_reference_rate::*;
+pub use create_stream::*;
+pub use execute_scheduled_payment::*;
+pub use fill_withdrawal::*;
 pub use finalize_withdrawal::*;
+pub use freeze_account::*;
+pub use initialize_fee_schedule::*;
+pub use initialize_mint_fee_config::*;
+pub use initialize_platform_config::*;
+pub use initialize_referral_rewards::*;
 pub use initialize_user::*;
+pub use initiate_split_transfer::*;
 pub use initiate_transfer::*;
+pub use match_limit_order::*;
+pub use pay_invoice::*;
+pub use place_compliance_hold::*;
+pub use refund_transfer::*;
 pub use register_liquidity_provider::*;
+pub use release_held_transfer::*;
 pub use request_withdrawal::*;
+pub use select_best_quote::*;
 pub use select_provider::*;
+pub use submit_milestone::*;
+pub use submit_quote::*;
+pub use withdraw_from_stream::*;
@@ -0,0 +1,288 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::instructions::confirm_transfer::{pay_referral_reward, pay_treasury_fee};
+use crate::state::*;
+
+/// Context for paying (part of) an invoice
+///
+/// Each payment is recorded as a completed TransferRequest so invoices
+/// share the same audit trail as regular transfers.
+#[derive(Accounts)]
+pub struct PayInvoice<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"invoice",
+            invoice.issuer.as_ref(),
+            invoice.payer.as_ref(),
+            &invoice.nonce.to_le_bytes()
+        ],
+        bump = invoice.bump,
+        constraint = invoice.payer == authority.key() @ CrossPayError::Unauthorized,
+        constraint = matches!(
+            invoice.status,
+            InvoiceStatus::Open | InvoiceStatus::PartiallyPaid | InvoiceStatus::Overdue
+        ) @ CrossPayError::InvalidInvoiceStatus
+    )]
+    pub invoice: Account<'info, Invoice>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", authority.key().as_ref()],
+        bump = payer_profile.bump,
+        has_one = authority,
+        constraint = payer_profile.kyc_verified @ CrossPayError::KycNotVerified,
+        constraint = !payer_profile.is_frozen @ CrossPayError::AccountFrozen
+    )]
+    pub payer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", invoice.issuer.as_ref()],
+        bump = issuer_profile.bump
+    )]
+    pub issuer_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Corridor PDA for the sender and receiver countries - may be unconfigured
+    #[account(
+        mut,
+        seeds = [
+            b"corridor",
+            payer_profile.country_code.as_bytes(),
+            issuer_profile.country_code.as_bytes()
+        ],
+        bump
+    )]
+    pub corridor: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the payer - must not exist
+    #[account(
+        seeds = [b"denied_address", authority.key().as_ref()],
+        bump,
+        constraint = payer_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub payer_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the issuer - must not exist
+    #[account(
+        seeds = [b"denied_address", invoice.issuer.as_ref()],
+        bump,
+        constraint = issuer_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub issuer_denial: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TransferRequest::LEN,
+        seeds = [
+            b"transfer_request",
+            authority.key().as_ref(),
+            invoice.issuer.as_ref(),
+            &payer_profile.transfer_nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == authority.key(),
+        constraint = payer_token_account.mint == invoice.mint
+    )]
+    pub payer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = issuer_token_account.owner == invoice.issuer,
+        constraint = issuer_token_account.mint == invoice.mint
+    )]
+    pub issuer_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Fee schedule PDA - may be unconfigured
+    #[account(
+        seeds = [b"fee_schedule"],
+        bump
+    )]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    /// CHECK: Fee config PDA for the mint - may be unconfigured
+    #[account(
+        seeds = [b"mint_fee_config", invoice.mint.as_ref()],
+        bump
+    )]
+    pub mint_fee_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == invoice.mint
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Rewards PDA of the receiver's referrer - required when the receiver
+    /// was referred, may be unopened; validated in ReferralRewards::share_of
+    #[account(mut)]
+    pub referral_rewards: Option<UncheckedAccount<'info>>,
+
+    /// Only required when a referral reward is credited
+    #[account(
+        mut,
+        seeds = [b"referral_vault", invoice.mint.as_ref()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pay `amount` towards an invoice in a single instruction
+/// With `FeeBearer::Sender` the issuer receives all of `amount` and the fee is
+/// charged to the payer on top.
+pub fn pay_invoice(
+    ctx: Context<PayInvoice>,
+    amount: u64,
+    fee_bearer: FeeBearer,
+    travel_rule: Option<TravelRuleRecord>,
+) -> Result<()> {
+    let invoice = &mut ctx.accounts.invoice;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, CrossPayError::InvalidAmount);
+    let outstanding = invoice.amount.saturating_sub(invoice.amount_paid);
+    require!(amount <= outstanding, CrossPayError::InvoiceOverpayment);
+    ctx.accounts.platform_config.check_unheld(amount)?;
+    if let Some(travel_rule) = &travel_rule {
+        travel_rule.validate()?;
+    }
+    ctx.accounts
+        .platform_config
+        .check_travel_rule(amount, travel_rule.as_ref())?;
+    let (fee_bps, platform_fee) = FeeResolver::load(
+        &ctx.accounts.fee_schedule,
+        &ctx.accounts.mint_fee_config,
+        ctx.accounts.payer_profile.volume_at(clock.unix_timestamp),
+    )?
+    .resolve(Corridor::load(&ctx.accounts.corridor)?.as_ref(), amount)?;
+    let (net_amount, payer_debit) = fee_bearer.price(amount, platform_fee)?;
+    require!(
+        ctx.accounts.payer_token_account.amount >= payer_debit,
+        CrossPayError::InsufficientBalance
+    );
+    Corridor::check_and_record(&ctx.accounts.corridor, amount, clock.unix_timestamp)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.payer_token_account.to_account_info(),
+        to: ctx.accounts.issuer_token_account.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, net_amount)?;
+
+    let referral_rewards = ctx.accounts.referral_rewards.as_ref().map(|info| info.as_ref());
+    let referral_reward = ReferralRewards::share_of(
+        referral_rewards,
+        ctx.accounts.issuer_profile.referrer,
+        &invoice.mint,
+        platform_fee,
+        ctx.accounts.platform_config.referral_share_bps,
+    )?;
+    pay_referral_reward(
+        referral_reward,
+        referral_rewards,
+        ctx.accounts.referral_vault.as_ref(),
+        ctx.accounts.payer_token_account.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        &[],
+    )?;
+    pay_treasury_fee(
+        platform_fee,
+        referral_reward,
+        &ctx.accounts.platform_fee_account,
+        ctx.accounts.payer_token_account.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        &[],
+    )?;
+
+    // Record the payment as a completed transfer
+    let transfer_request = &mut ctx.accounts.transfer_request;
+    transfer_request.sender = ctx.accounts.authority.key();
+    transfer_request.receiver = invoice.issuer;
+    transfer_request.amount = amount;
+    transfer_request.net_amount = net_amount;
+    transfer_request.platform_fee = platform_fee;
+    transfer_request.fee_bearer = fee_bearer;
+    transfer_request.fee_bps = fee_bps;
+    transfer_request.mint = invoice.mint;
+    transfer_request.status = TransferStatus::Completed;
+    transfer_request.created_at = clock.unix_timestamp;
+    transfer_request.completed_at = Some(clock.unix_timestamp);
+    transfer_request.nonce = ctx.accounts.payer_profile.next_transfer_nonce()?;
+    transfer_request.bump = ctx.bumps.transfer_request;
+    transfer_request.splits = Vec::new();
+    transfer_request.release_at = None;
+    transfer_request.travel_rule = travel_rule;
+    transfer_request.referral_reward = referral_reward;
+    transfer_request.refunded_amount = 0;
+    transfer_request.refund_count = 0;
+
+    invoice.amount_paid = invoice
+        .amount_paid
+        .checked_add(amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    invoice.last_transfer = Some(transfer_request.key());
+    invoice.payment_count = invoice
+        .payment_count
+        .checked_add(1)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    let is_late = clock.unix_timestamp > invoice.due_date;
+    invoice.status = if invoice.amount_paid < invoice.amount {
+        if is_late {
+            InvoiceStatus::Overdue
+        } else {
+            InvoiceStatus::PartiallyPaid
+        }
+    } else {
+        invoice.paid_at = Some(clock.unix_timestamp);
+        if is_late {
+            InvoiceStatus::PaidLate
+        } else {
+            InvoiceStatus::Paid
+        }
+    };
+
+    ctx.accounts.payer_profile.record_sent(payer_debit, clock.unix_timestamp)?;
+
+    ctx.accounts.issuer_profile.total_received = ctx
+        .accounts
+        .issuer_profile
+        .total_received
+        .checked_add(net_amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    msg!(
+        "Invoice payment: {} tokens sent, {} received (fee: {}), {} outstanding",
+        amount,
+        net_amount,
+        platform_fee,
+        invoice.amount - invoice.amount_paid
+    );
+
+    Ok(())
+}
@@ -0,0 +1,307 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Record a new compliance hold against a pending request
+fn open_hold(
+    compliance_hold: &mut ComplianceHold,
+    target: Pubkey,
+    officer: Pubkey,
+    reason: ComplianceReason,
+    bump: u8,
+) -> Result<()> {
+    require!(
+        reason != ComplianceReason::None,
+        CrossPayError::InvalidComplianceReason
+    );
+
+    compliance_hold.target = target;
+    compliance_hold.officer = officer;
+    compliance_hold.reason = reason;
+    compliance_hold.created_at = Clock::get()?.unix_timestamp;
+    compliance_hold.bump = bump;
+    compliance_hold.status = HoldStatus::Open;
+    compliance_hold.resolution = ComplianceReason::None;
+    compliance_hold.resolved_by = None;
+    compliance_hold.resolved_at = None;
+
+    Ok(())
+}
+
+/// Record how an open hold was resolved
+/// A rejection must carry a reason code; a release may be recorded as `None`.
+fn resolve_hold(
+    compliance_hold: &mut ComplianceHold,
+    officer: Pubkey,
+    release: bool,
+    resolution: ComplianceReason,
+) -> Result<()> {
+    require!(
+        release || resolution != ComplianceReason::None,
+        CrossPayError::InvalidComplianceReason
+    );
+
+    compliance_hold.status = if release {
+        HoldStatus::Released
+    } else {
+        HoldStatus::Rejected
+    };
+    compliance_hold.resolution = resolution;
+    compliance_hold.resolved_by = Some(officer);
+    compliance_hold.resolved_at = Some(Clock::get()?.unix_timestamp);
+
+    Ok(())
+}
+
+/// Context for a compliance officer holding a pending transfer
+#[derive(Accounts)]
+pub struct HoldTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"transfer_request",
+            transfer_request.sender.as_ref(),
+            transfer_request.receiver.as_ref(),
+            &transfer_request.nonce.to_le_bytes()
+        ],
+        bump = transfer_request.bump,
+        constraint = transfer_request.status == TransferStatus::Pending @ CrossPayError::InvalidTransferStatus
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+
+    #[account(
+        init,
+        payer = officer,
+        space = ComplianceHold::LEN,
+        seeds = [b"compliance_hold", transfer_request.key().as_ref()],
+        bump
+    )]
+    pub compliance_hold: Account<'info, ComplianceHold>,
+
+    #[account(
+        seeds = [b"compliance_officer", officer.key().as_ref()],
+        bump = compliance_officer.bump,
+        has_one = officer @ CrossPayError::Unauthorized
+    )]
+    pub compliance_officer: Account<'info, ComplianceOfficer>,
+
+    #[account(mut)]
+    pub officer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Place a pending transfer under compliance review
+pub fn hold_transfer(ctx: Context<HoldTransfer>, reason: ComplianceReason) -> Result<()> {
+    let transfer_request = &mut ctx.accounts.transfer_request;
+
+    open_hold(
+        &mut ctx.accounts.compliance_hold,
+        transfer_request.key(),
+        ctx.accounts.officer.key(),
+        reason,
+        ctx.bumps.compliance_hold,
+    )?;
+
+    transfer_request.status = TransferStatus::UnderReview;
+
+    msg!(
+        "Transfer of {} tokens to {} placed under review",
+        transfer_request.amount,
+        transfer_request.receiver
+    );
+
+    Ok(())
+}
+
+/// Context for a compliance officer resolving a transfer hold
+#[derive(Accounts)]
+pub struct ResolveTransferHold<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"transfer_request",
+            transfer_request.sender.as_ref(),
+            transfer_request.receiver.as_ref(),
+            &transfer_request.nonce.to_le_bytes()
+        ],
+        bump = transfer_request.bump,
+        constraint = transfer_request.status == TransferStatus::UnderReview @ CrossPayError::InvalidTransferStatus
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"compliance_hold", transfer_request.key().as_ref()],
+        bump = compliance_hold.bump,
+        constraint = compliance_hold.status == HoldStatus::Open @ CrossPayError::ComplianceHoldNotActive
+    )]
+    pub compliance_hold: Account<'info, ComplianceHold>,
+
+    #[account(
+        seeds = [b"compliance_officer", officer.key().as_ref()],
+        bump = compliance_officer.bump,
+        has_one = officer @ CrossPayError::Unauthorized
+    )]
+    pub compliance_officer: Account<'info, ComplianceOfficer>,
+
+    pub officer: Signer<'info>,
+}
+
+/// Return a held transfer to pending, or reject it outright
+/// The hold record is kept with the resolution, so a released transfer can't be held again.
+pub fn resolve_transfer_hold(
+    ctx: Context<ResolveTransferHold>,
+    release: bool,
+    resolution: ComplianceReason,
+) -> Result<()> {
+    let transfer_request = &mut ctx.accounts.transfer_request;
+
+    resolve_hold(
+        &mut ctx.accounts.compliance_hold,
+        ctx.accounts.officer.key(),
+        release,
+        resolution,
+    )?;
+
+    transfer_request.status = if release {
+        TransferStatus::Pending
+    } else {
+        TransferStatus::Failed
+    };
+
+    msg!(
+        "Transfer hold resolved - Released: {}, Reason: {}, Resolution: {}",
+        release,
+        ctx.accounts.compliance_hold.reason as u8,
+        resolution as u8
+    );
+
+    Ok(())
+}
+
+/// Context for a compliance officer holding a pending withdrawal
+#[derive(Accounts)]
+pub struct HoldWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::Pending @ CrossPayError::InvalidWithdrawalStatus
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        init,
+        payer = officer,
+        space = ComplianceHold::LEN,
+        seeds = [b"compliance_hold", withdrawal_request.key().as_ref()],
+        bump
+    )]
+    pub compliance_hold: Account<'info, ComplianceHold>,
+
+    #[account(
+        seeds = [b"compliance_officer", officer.key().as_ref()],
+        bump = compliance_officer.bump,
+        has_one = officer @ CrossPayError::Unauthorized
+    )]
+    pub compliance_officer: Account<'info, ComplianceOfficer>,
+
+    #[account(mut)]
+    pub officer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Place a pending withdrawal under compliance review
+pub fn hold_withdrawal(ctx: Context<HoldWithdrawal>, reason: ComplianceReason) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+
+    open_hold(
+        &mut ctx.accounts.compliance_hold,
+        withdrawal_request.key(),
+        ctx.accounts.officer.key(),
+        reason,
+        ctx.bumps.compliance_hold,
+    )?;
+
+    withdrawal_request.status = WithdrawalStatus::UnderReview;
+
+    msg!(
+        "Withdrawal of {} tokens by {} placed under review",
+        withdrawal_request.amount,
+        withdrawal_request.freelancer
+    );
+
+    Ok(())
+}
+
+/// Context for a compliance officer resolving a withdrawal hold
+#[derive(Accounts)]
+pub struct ResolveWithdrawalHold<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::UnderReview @ CrossPayError::InvalidWithdrawalStatus
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"compliance_hold", withdrawal_request.key().as_ref()],
+        bump = compliance_hold.bump,
+        constraint = compliance_hold.status == HoldStatus::Open @ CrossPayError::ComplianceHoldNotActive
+    )]
+    pub compliance_hold: Account<'info, ComplianceHold>,
+
+    #[account(
+        seeds = [b"compliance_officer", officer.key().as_ref()],
+        bump = compliance_officer.bump,
+        has_one = officer @ CrossPayError::Unauthorized
+    )]
+    pub compliance_officer: Account<'info, ComplianceOfficer>,
+
+    pub officer: Signer<'info>,
+}
+
+/// Return a held withdrawal to pending, or reject it outright
+/// The hold record is kept with the resolution, so a released withdrawal can't be held again.
+pub fn resolve_withdrawal_hold(
+    ctx: Context<ResolveWithdrawalHold>,
+    release: bool,
+    resolution: ComplianceReason,
+) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+
+    resolve_hold(
+        &mut ctx.accounts.compliance_hold,
+        ctx.accounts.officer.key(),
+        release,
+        resolution,
+    )?;
+
+    withdrawal_request.status = if release {
+        WithdrawalStatus::Pending
+    } else {
+        WithdrawalStatus::Failed
+    };
+
+    msg!(
+        "Withdrawal hold resolved - Released: {}, Reason: {}, Resolution: {}",
+        release,
+        ctx.accounts.compliance_hold.reason as u8,
+        resolution as u8
+    );
+
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for a receiver refunding part or all of a completed transfer
+#[derive(Accounts)]
+pub struct RefundTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"transfer_request",
+            transfer_request.sender.as_ref(),
+            transfer_request.receiver.as_ref(),
+            &transfer_request.nonce.to_le_bytes()
+        ],
+        bump = transfer_request.bump,
+        constraint = transfer_request.receiver == authority.key() @ CrossPayError::Unauthorized,
+        constraint = transfer_request.status == TransferStatus::Completed @ CrossPayError::InvalidTransferStatus,
+        constraint = transfer_request.splits.is_empty() @ CrossPayError::InvalidSplit
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TransferRefund::LEN,
+        seeds = [
+            b"transfer_refund",
+            transfer_request.key().as_ref(),
+            &transfer_request.refund_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub transfer_refund: Account<'info, TransferRefund>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", transfer_request.sender.as_ref()],
+        bump = sender_profile.bump
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", authority.key().as_ref()],
+        bump = receiver_profile.bump,
+        has_one = authority
+    )]
+    pub receiver_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == transfer_request.sender,
+        constraint = sender_token_account.mint == transfer_request.mint
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == authority.key(),
+        constraint = receiver_token_account.mint == transfer_request.mint
+    )]
+    pub receiver_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", transfer_request.sender.as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the receiver - must not exist
+    #[account(
+        seeds = [b"denied_address", authority.key().as_ref()],
+        bump,
+        constraint = receiver_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub receiver_denial: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Rewards PDA of the receiver's referrer - only required when refunding
+    /// the fee of a transfer that paid a referral reward
+    #[account(mut)]
+    pub referral_rewards: Option<UncheckedAccount<'info>>,
+
+    /// Only required when refunding the fee of a transfer that paid a referral reward
+    #[account(
+        mut,
+        seeds = [b"referral_vault", transfer_request.mint.as_ref()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Return `amount` of a completed transfer to its sender
+/// Partial refunds add up until the whole net amount is back with the sender.
+/// With `refund_fee`, the referral share of the fee is returned pro rata from the
+/// referral vault; the rest of the fee is kept by the treasury.
+pub fn refund_transfer(ctx: Context<RefundTransfer>, amount: u64, refund_fee: bool) -> Result<()> {
+    let transfer_request = &mut ctx.accounts.transfer_request;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, CrossPayError::InvalidAmount);
+    let refunded_amount = transfer_request
+        .refunded_amount
+        .checked_add(amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    require!(
+        refunded_amount <= transfer_request.net_amount,
+        CrossPayError::RefundExceedsTransfer
+    );
+    require!(
+        ctx.accounts.receiver_token_account.amount >= amount,
+        CrossPayError::InsufficientBalance
+    );
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.receiver_token_account.to_account_info(),
+        to: ctx.accounts.sender_token_account.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    // Priced on the running total so the refunds' shares add up to the whole reward
+    let fee_refunded = if refund_fee {
+        let already_refunded =
+            referral_share_of(transfer_request, transfer_request.refunded_amount)?;
+        referral_share_of(transfer_request, refunded_amount)?
+            .checked_sub(already_refunded)
+            .ok_or(CrossPayError::ArithmeticOverflow)?
+    } else {
+        0
+    };
+    if fee_refunded > 0 {
+        let referrer = ctx
+            .accounts
+            .receiver_profile
+            .referrer
+            .ok_or(CrossPayError::ReferralAccountsRequired)?;
+        let referral_rewards = ctx
+            .accounts
+            .referral_rewards
+            .as_ref()
+            .ok_or(CrossPayError::ReferralAccountsRequired)?;
+        require_keys_eq!(
+            referral_rewards.key(),
+            ReferralRewards::address(&referrer, &transfer_request.mint),
+            CrossPayError::ReferralAccountsRequired
+        );
+        let referral_vault = ctx
+            .accounts
+            .referral_vault
+            .as_ref()
+            .ok_or(CrossPayError::ReferralAccountsRequired)?;
+
+        ReferralRewards::debit(referral_rewards, fee_refunded)?;
+
+        let signer_seeds: &[&[&[u8]]] =
+            &[&[b"platform_config", &[ctx.accounts.platform_config.bump]]];
+        let cpi_accounts = Transfer {
+            from: referral_vault.to_account_info(),
+            to: ctx.accounts.sender_token_account.to_account_info(),
+            authority: ctx.accounts.platform_config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, fee_refunded)?;
+    }
+
+    let transfer_refund = &mut ctx.accounts.transfer_refund;
+    transfer_refund.transfer_request = transfer_request.key();
+    transfer_refund.sender = transfer_request.sender;
+    transfer_refund.receiver = transfer_request.receiver;
+    transfer_refund.mint = transfer_request.mint;
+    transfer_refund.amount = amount;
+    transfer_refund.fee_refunded = fee_refunded;
+    transfer_refund.index = transfer_request.refund_count;
+    transfer_refund.created_at = clock.unix_timestamp;
+    transfer_refund.bump = ctx.bumps.transfer_refund;
+
+    transfer_request.refunded_amount = refunded_amount;
+    transfer_request.refund_count = transfer_request
+        .refund_count
+        .checked_add(1)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    if refunded_amount == transfer_request.net_amount {
+        transfer_request.status = TransferStatus::Refunded;
+    }
+
+    let sent_reversed = amount
+        .checked_add(fee_refunded)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    ctx.accounts.sender_profile.reverse_sent(sent_reversed)?;
+
+    ctx.accounts.receiver_profile.total_received = ctx
+        .accounts
+        .receiver_profile
+        .total_received
+        .checked_sub(amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    msg!(
+        "Transfer refunded: {} tokens returned (fee refunded: {}, {} of {} refunded)",
+        amount,
+        fee_refunded,
+        refunded_amount,
+        transfer_request.net_amount
+    );
+
+    Ok(())
+}
+
+/// Referral share of the fee attributable to `refunded` of the net amount
+fn referral_share_of(transfer_request: &TransferRequest, refunded: u64) -> Result<u64> {
+    let share = (transfer_request.referral_reward as u128)
+        .checked_mul(refunded as u128)
+        .ok_or(CrossPayError::ArithmeticOverflow)?
+        / transfer_request.net_amount as u128;
+    Ok(share as u64)
+}
@@ -6,6 +6,7 @@ use crate::state::*;
 
 /// Context for registering a new liquidity provider
 #[derive(Accounts)]
+#[instruction(location: String, exchange_rate: u64, currency: CurrencyCode)]
 pub struct RegisterLiquidityProvider<'info> {
     #[account(
         init,
@@ -16,6 +17,18 @@ pub struct RegisterLiquidityProvider<'info> {
     )]
     pub liquidity_provider: Account<'info, LiquidityProvider>,
 
+    /// CHECK: Deny-list PDA for the provider - must not exist
+    #[account(
+        seeds = [b"denied_address", authority.key().as_ref()],
+        bump,
+        constraint = provider_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub provider_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Reference rate PDA for the payout currency - may be unconfigured;
+    /// validated in ReferenceRate::load_pair
+    pub reference_rate: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -27,26 +40,49 @@ pub fn register_liquidity_provider(
     ctx: Context<RegisterLiquidityProvider>,
     location: String,
     exchange_rate: u64,
+    currency: CurrencyCode,
+    fee_bps: u16,
+    fixed_fee: u64,
+    payout_methods: Vec<PayoutMethod>,
 ) -> Result<()> {
     require!(
         location.len() <= MAX_LOCATION_LEN,
         CrossPayError::InvalidLocation
     );
     require!(exchange_rate > 0, CrossPayError::InvalidAmount);
+    require!(currency.is_valid(), CrossPayError::InvalidCurrencyCode);
+    require!(
+        fee_bps <= MAX_WITHDRAWAL_FEE_BPS && fixed_fee <= MAX_WITHDRAWAL_FIXED_FEE,
+        CrossPayError::InvalidWithdrawalFee
+    );
+    require!(
+        !payout_methods.is_empty(),
+        CrossPayError::InvalidPayoutMethods
+    );
 
-    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
     let clock = Clock::get()?;
+    if let Some(reference_rate) = ReferenceRate::load_pair(&ctx.accounts.reference_rate, &currency)?
+    {
+        reference_rate.check_rate(exchange_rate, clock.unix_timestamp)?;
+    }
+
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
 
     liquidity_provider.authority = ctx.accounts.authority.key();
     liquidity_provider.location = location;
     liquidity_provider.exchange_rate = exchange_rate;
     liquidity_provider.available_liquidity = 0;
+    liquidity_provider.reserved_liquidity = 0;
     liquidity_provider.total_volume = 0;
     liquidity_provider.completed_transactions = 0;
     liquidity_provider.trust_score = DEFAULT_TRUST_SCORE;
     liquidity_provider.is_active = true;
     liquidity_provider.created_at = clock.unix_timestamp;
     liquidity_provider.bump = ctx.bumps.liquidity_provider;
+    liquidity_provider.currency = currency;
+    liquidity_provider.fee_bps = fee_bps;
+    liquidity_provider.fixed_fee = fixed_fee;
+    liquidity_provider.payout_methods = PayoutMethod::mask_of(&payout_methods);
 
     msg!(
         "Liquidity provider registered: {}",
@@ -89,3 +125,78 @@ pub fn update_provider_availability(
 
     Ok(())
 }
+
+/// Context for updating a liquidity provider's declared fees
+#[derive(Accounts)]
+pub struct UpdateProviderFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"liquidity_provider", authority.key().as_ref()],
+        bump = liquidity_provider.bump,
+        has_one = authority
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Update the fee a liquidity provider charges on withdrawals
+/// Only affects withdrawals that select the provider afterwards
+pub fn update_provider_fees(
+    ctx: Context<UpdateProviderFees>,
+    fee_bps: u16,
+    fixed_fee: u64,
+) -> Result<()> {
+    require!(
+        fee_bps <= MAX_WITHDRAWAL_FEE_BPS && fixed_fee <= MAX_WITHDRAWAL_FIXED_FEE,
+        CrossPayError::InvalidWithdrawalFee
+    );
+
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+
+    liquidity_provider.fee_bps = fee_bps;
+    liquidity_provider.fixed_fee = fixed_fee;
+
+    msg!(
+        "Provider fees updated - Rate: {} bps, Fixed: {}",
+        fee_bps,
+        fixed_fee
+    );
+
+    Ok(())
+}
+
+/// Context for updating the payout methods a liquidity provider supports
+#[derive(Accounts)]
+pub struct UpdateProviderPayoutMethods<'info> {
+    #[account(
+        mut,
+        seeds = [b"liquidity_provider", authority.key().as_ref()],
+        bump = liquidity_provider.bump,
+        has_one = authority
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Replace the payout methods a liquidity provider supports
+pub fn update_provider_payout_methods(
+    ctx: Context<UpdateProviderPayoutMethods>,
+    payout_methods: Vec<PayoutMethod>,
+) -> Result<()> {
+    require!(
+        !payout_methods.is_empty(),
+        CrossPayError::InvalidPayoutMethods
+    );
+
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    liquidity_provider.payout_methods = PayoutMethod::mask_of(&payout_methods);
+
+    msg!(
+        "Provider payout methods updated: {:#05b}",
+        liquidity_provider.payout_methods
+    );
+
+    Ok(())
+}
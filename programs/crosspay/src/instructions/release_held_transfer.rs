@@ -0,0 +1,413 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::instructions::confirm_transfer::{pay_referral_reward, pay_treasury_fee};
+use crate::state::*;
+
+/// Move tokens out of a hold vault, signed by the platform config PDA
+fn pay_from_hold_vault<'info>(
+    platform_config: &Account<'info, PlatformConfig>,
+    hold_vault: &Account<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[b"platform_config", &[platform_config.bump]]];
+
+    let cpi_accounts = Transfer {
+        from: hold_vault.to_account_info(),
+        to,
+        authority: platform_config.to_account_info(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, amount)
+}
+
+/// Pay the referral reward escrowed with a held transfer once it is released
+fn pay_withheld_referral_reward<'info>(
+    transfer_request: &TransferRequest,
+    receiver_profile: &UserProfile,
+    platform_config: &Account<'info, PlatformConfig>,
+    hold_vault: &Account<'info, TokenAccount>,
+    referral_rewards: Option<&AccountInfo<'info>>,
+    referral_vault: Option<&Account<'info, TokenAccount>>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let reward = transfer_request.referral_reward;
+    if reward == 0 {
+        return Ok(());
+    }
+    let referrer = receiver_profile
+        .referrer
+        .ok_or(CrossPayError::ReferralAccountsRequired)?;
+    let info = referral_rewards.ok_or(CrossPayError::ReferralAccountsRequired)?;
+    require_keys_eq!(
+        info.key(),
+        ReferralRewards::address(&referrer, &transfer_request.mint),
+        CrossPayError::ReferralAccountsRequired
+    );
+
+    let signer_seeds: &[&[&[u8]]] = &[&[b"platform_config", &[platform_config.bump]]];
+    pay_referral_reward(
+        reward,
+        Some(info),
+        referral_vault,
+        hold_vault.to_account_info(),
+        platform_config.to_account_info(),
+        token_program.to_account_info(),
+        signer_seeds,
+    )
+}
+
+/// Pay the treasury its part of the fee escrowed with a held transfer once it is released
+fn pay_withheld_treasury_fee<'info>(
+    transfer_request: &TransferRequest,
+    platform_config: &Account<'info, PlatformConfig>,
+    hold_vault: &Account<'info, TokenAccount>,
+    platform_fee_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[b"platform_config", &[platform_config.bump]]];
+    pay_treasury_fee(
+        transfer_request.platform_fee,
+        transfer_request.referral_reward,
+        platform_fee_account,
+        hold_vault.to_account_info(),
+        platform_config.to_account_info(),
+        token_program.to_account_info(),
+        signer_seeds,
+    )
+}
+
+/// Context for releasing a held transfer after its cool-off (permissionless crank)
+#[derive(Accounts)]
+pub struct ReleaseHeldTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"transfer_request",
+            transfer_request.sender.as_ref(),
+            transfer_request.receiver.as_ref(),
+            &transfer_request.nonce.to_le_bytes()
+        ],
+        bump = transfer_request.bump,
+        constraint = transfer_request.status == TransferStatus::Held @ CrossPayError::InvalidTransferStatus
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"hold_vault", transfer_request.mint.as_ref()],
+        bump
+    )]
+    pub hold_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", transfer_request.receiver.as_ref()],
+        bump = receiver_profile.bump
+    )]
+    pub receiver_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == transfer_request.receiver,
+        constraint = receiver_token_account.mint == transfer_request.mint
+    )]
+    pub receiver_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == transfer_request.mint
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Rewards PDA of the receiver's referrer - only required when the
+    /// transfer withheld a referral reward; validated in pay_withheld_referral_reward
+    #[account(mut)]
+    pub referral_rewards: Option<UncheckedAccount<'info>>,
+
+    /// Only required when the transfer withheld a referral reward
+    #[account(
+        mut,
+        seeds = [b"referral_vault", transfer_request.mint.as_ref()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Release a held transfer to its receiver once the cool-off has passed
+pub fn release_held_transfer(ctx: Context<ReleaseHeldTransfer>) -> Result<()> {
+    let transfer_request = &mut ctx.accounts.transfer_request;
+    let clock = Clock::get()?;
+
+    let release_at = transfer_request
+        .release_at
+        .ok_or(CrossPayError::InvalidTransferStatus)?;
+    require!(
+        clock.unix_timestamp >= release_at,
+        CrossPayError::HoldPeriodActive
+    );
+
+    pay_from_hold_vault(
+        &ctx.accounts.platform_config,
+        &ctx.accounts.hold_vault,
+        ctx.accounts.receiver_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+        transfer_request.net_amount,
+    )?;
+    pay_withheld_referral_reward(
+        transfer_request,
+        &ctx.accounts.receiver_profile,
+        &ctx.accounts.platform_config,
+        &ctx.accounts.hold_vault,
+        ctx.accounts
+            .referral_rewards
+            .as_ref()
+            .map(|info| info.as_ref()),
+        ctx.accounts.referral_vault.as_ref(),
+        &ctx.accounts.token_program,
+    )?;
+    pay_withheld_treasury_fee(
+        transfer_request,
+        &ctx.accounts.platform_config,
+        &ctx.accounts.hold_vault,
+        &ctx.accounts.platform_fee_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    transfer_request.status = TransferStatus::Completed;
+    transfer_request.completed_at = Some(clock.unix_timestamp);
+
+    ctx.accounts.receiver_profile.total_received = ctx
+        .accounts
+        .receiver_profile
+        .total_received
+        .checked_add(transfer_request.net_amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    msg!(
+        "Held transfer released: {} tokens to {}",
+        transfer_request.net_amount,
+        transfer_request.receiver
+    );
+
+    Ok(())
+}
+
+/// Context for the sender raising a fraud claim during the cool-off
+#[derive(Accounts)]
+pub struct RaiseFraudClaim<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"transfer_request",
+            transfer_request.sender.as_ref(),
+            transfer_request.receiver.as_ref(),
+            &transfer_request.nonce.to_le_bytes()
+        ],
+        bump = transfer_request.bump,
+        constraint = transfer_request.sender == authority.key() @ CrossPayError::Unauthorized,
+        constraint = transfer_request.status == TransferStatus::Held @ CrossPayError::InvalidTransferStatus
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Freeze a held transfer pending review by the platform admin
+pub fn raise_fraud_claim(ctx: Context<RaiseFraudClaim>) -> Result<()> {
+    let transfer_request = &mut ctx.accounts.transfer_request;
+    let clock = Clock::get()?;
+
+    let release_at = transfer_request
+        .release_at
+        .ok_or(CrossPayError::InvalidTransferStatus)?;
+    require!(
+        clock.unix_timestamp < release_at,
+        CrossPayError::HoldPeriodEnded
+    );
+
+    transfer_request.status = TransferStatus::Disputed;
+
+    msg!(
+        "Fraud claim raised on transfer of {} tokens to {}",
+        transfer_request.amount,
+        transfer_request.receiver
+    );
+
+    Ok(())
+}
+
+/// Context for the platform admin resolving a fraud claim
+#[derive(Accounts)]
+pub struct ResolveFraudClaim<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"transfer_request",
+            transfer_request.sender.as_ref(),
+            transfer_request.receiver.as_ref(),
+            &transfer_request.nonce.to_le_bytes()
+        ],
+        bump = transfer_request.bump,
+        constraint = transfer_request.status == TransferStatus::Disputed @ CrossPayError::InvalidTransferStatus
+    )]
+    pub transfer_request: Account<'info, TransferRequest>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump,
+        has_one = admin @ CrossPayError::Unauthorized
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"hold_vault", transfer_request.mint.as_ref()],
+        bump
+    )]
+    pub hold_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", transfer_request.sender.as_ref()],
+        bump = sender_profile.bump
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", transfer_request.receiver.as_ref()],
+        bump = receiver_profile.bump
+    )]
+    pub receiver_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == transfer_request.sender,
+        constraint = sender_token_account.mint == transfer_request.mint
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == transfer_request.receiver,
+        constraint = receiver_token_account.mint == transfer_request.mint
+    )]
+    pub receiver_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == transfer_request.mint
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Rewards PDA of the receiver's referrer - only required when the
+    /// transfer withheld a referral reward; validated in pay_withheld_referral_reward
+    #[account(mut)]
+    pub referral_rewards: Option<UncheckedAccount<'info>>,
+
+    /// Only required when the transfer withheld a referral reward
+    #[account(
+        mut,
+        seeds = [b"referral_vault", transfer_request.mint.as_ref()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Return a disputed transfer to the sender, or release it to the receiver
+pub fn resolve_fraud_claim(ctx: Context<ResolveFraudClaim>, refund_sender: bool) -> Result<()> {
+    let transfer_request = &mut ctx.accounts.transfer_request;
+    let clock = Clock::get()?;
+
+    if refund_sender {
+        // The whole fee was escrowed with the net amount, so it all goes back
+        let escrowed = transfer_request
+            .sender_debit()
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        pay_from_hold_vault(
+            &ctx.accounts.platform_config,
+            &ctx.accounts.hold_vault,
+            ctx.accounts.sender_token_account.to_account_info(),
+            &ctx.accounts.token_program,
+            escrowed,
+        )?;
+
+        transfer_request.status = TransferStatus::Cancelled;
+        transfer_request.referral_reward = 0;
+
+        // Nothing of the fee left the sender, so everything recorded as sent is reversed
+        ctx.accounts.sender_profile.reverse_sent(escrowed)?;
+
+        msg!(
+            "Fraud claim upheld: {} tokens returned to {}",
+            escrowed,
+            transfer_request.sender
+        );
+    } else {
+        pay_from_hold_vault(
+            &ctx.accounts.platform_config,
+            &ctx.accounts.hold_vault,
+            ctx.accounts.receiver_token_account.to_account_info(),
+            &ctx.accounts.token_program,
+            transfer_request.net_amount,
+        )?;
+        pay_withheld_referral_reward(
+            transfer_request,
+            &ctx.accounts.receiver_profile,
+            &ctx.accounts.platform_config,
+            &ctx.accounts.hold_vault,
+            ctx.accounts
+                .referral_rewards
+                .as_ref()
+                .map(|info| info.as_ref()),
+            ctx.accounts.referral_vault.as_ref(),
+            &ctx.accounts.token_program,
+        )?;
+        pay_withheld_treasury_fee(
+            transfer_request,
+            &ctx.accounts.platform_config,
+            &ctx.accounts.hold_vault,
+            &ctx.accounts.platform_fee_account,
+            &ctx.accounts.token_program,
+        )?;
+
+        transfer_request.status = TransferStatus::Completed;
+
+        ctx.accounts.receiver_profile.total_received = ctx
+            .accounts
+            .receiver_profile
+            .total_received
+            .checked_add(transfer_request.net_amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        msg!(
+            "Fraud claim rejected: {} tokens released to {}",
+            transfer_request.net_amount,
+            transfer_request.receiver
+        );
+    }
+
+    transfer_request.completed_at = Some(clock.unix_timestamp);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 
+use crate::constants::*;
 use crate::errors::CrossPayError;
 use crate::state::*;
 
@@ -9,9 +10,11 @@ use crate::state::*;
 #[instruction(amount: u64)]
 pub struct RequestWithdrawal<'info> {
     #[account(
+        mut,
         seeds = [b"user_profile", freelancer.key().as_ref()],
         bump = freelancer_profile.bump,
-        has_one = authority
+        has_one = authority,
+        constraint = !freelancer_profile.is_frozen @ CrossPayError::AccountFrozen
     )]
     pub freelancer_profile: Account<'info, UserProfile>,
 
@@ -22,12 +25,20 @@ pub struct RequestWithdrawal<'info> {
         seeds = [
             b"withdrawal_request",
             freelancer.key().as_ref(),
-            &freelancer_profile.total_received.to_le_bytes()
+            &freelancer_profile.withdrawal_nonce.to_le_bytes()
         ],
         bump
     )]
     pub withdrawal_request: Account<'info, WithdrawalRequest>,
 
+    /// CHECK: Deny-list PDA for the freelancer - must not exist
+    #[account(
+        seeds = [b"denied_address", freelancer.key().as_ref()],
+        bump,
+        constraint = freelancer_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub freelancer_denial: UncheckedAccount<'info>,
+
     #[account(
         mut,
         constraint = freelancer_token_account.owner == authority.key(),
@@ -46,12 +57,26 @@ pub struct RequestWithdrawal<'info> {
 }
 
 /// Request a withdrawal to local currency
+/// A non-zero `quote_window` opens it to quotes from providers for that many
+/// seconds instead of the freelancer picking a provider directly.
 pub fn request_withdrawal(
     ctx: Context<RequestWithdrawal>,
     amount: u64,
     payout_method: PayoutMethod,
+    currency: CurrencyCode,
+    quote_window: i64,
+    min_trust_score: u16,
 ) -> Result<()> {
     require!(amount > 0, CrossPayError::InvalidAmount);
+    require!(currency.is_valid(), CrossPayError::InvalidCurrencyCode);
+    require!(
+        (0..=MAX_QUOTE_WINDOW).contains(&quote_window),
+        CrossPayError::InvalidQuoteWindow
+    );
+    require!(
+        min_trust_score <= MAX_TRUST_SCORE,
+        CrossPayError::InvalidTrustScore
+    );
 
     let withdrawal_request = &mut ctx.accounts.withdrawal_request;
     let clock = Clock::get()?;
@@ -64,10 +89,40 @@ pub fn request_withdrawal(
     withdrawal_request.status = WithdrawalStatus::Pending;
     withdrawal_request.created_at = clock.unix_timestamp;
     withdrawal_request.completed_at = None;
-    withdrawal_request.nonce = ctx.accounts.freelancer_profile.total_received;
+    withdrawal_request.nonce = ctx.accounts.freelancer_profile.next_withdrawal_nonce()?;
     withdrawal_request.bump = ctx.bumps.withdrawal_request;
+    withdrawal_request.currency = currency;
+    withdrawal_request.provider_fee = 0;
+    withdrawal_request.platform_fee = 0;
+    withdrawal_request.net_amount = amount;
+    withdrawal_request.exchange_rate = 0;
+    withdrawal_request.quote_deadline = if quote_window > 0 {
+        clock
+            .unix_timestamp
+            .checked_add(quote_window)
+            .ok_or(CrossPayError::ArithmeticOverflow)?
+    } else {
+        0
+    };
+    withdrawal_request.min_trust_score = min_trust_score;
+    withdrawal_request.quote_count = 0;
+    withdrawal_request.filled_amount = 0;
+    withdrawal_request.fill_count = 0;
+    withdrawal_request.completed_fills = 0;
+    withdrawal_request.lock_expires_at = 0;
+    withdrawal_request.min_rate = 0;
+    withdrawal_request.order_expires_at = 0;
+    withdrawal_request.quotes_ranked = 0;
+    withdrawal_request.best_quote = None;
+    withdrawal_request.cancelled_fills = 0;
+    withdrawal_request.last_locker = None;
+    withdrawal_request.provider_accepts = false;
 
-    msg!("Withdrawal requested: {} tokens", amount);
+    msg!(
+        "Withdrawal requested: {} tokens (quote deadline: {})",
+        amount,
+        withdrawal_request.quote_deadline
+    );
 
     Ok(())
 }
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CrossPayError;
+use crate::instructions::select_provider::load_liquidity_provider;
+use crate::state::*;
+
+/// Context for ranking quotes once the window closes (permissionless crank)
+///
+/// Open quotes are supplied through `remaining_accounts` as pairs of
+/// `[quote (mut), liquidity_provider]`, spread over as many calls as needed.
+/// Each quote is ranked exactly once, so the crank can't skip a better offer.
+#[derive(Accounts)]
+pub struct SelectBestQuote<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::Pending @ CrossPayError::InvalidWithdrawalStatus,
+        constraint = withdrawal_request.is_rfq() @ CrossPayError::NotRfqWithdrawal
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Reference rate PDA for the payout currency - may be unconfigured;
+    /// validated in ReferenceRate::load_pair
+    pub reference_rate: UncheckedAccount<'info>,
+}
+
+/// Rank a page of quotes, keeping the one paying the freelancer the most local currency
+/// Quotes outside the reference band are ranked but never selected.
+/// The call ranking the last quote selects the best one; if none was eligible
+/// the withdrawal falls back to direct provider selection.
+pub fn select_best_quote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SelectBestQuote<'info>>,
+) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let offramp_fee_bps = ctx.accounts.platform_config.offramp_fee_bps;
+    let clock = Clock::get()?;
+    let reference_rate =
+        ReferenceRate::load_pair(&ctx.accounts.reference_rate, &withdrawal_request.currency)?;
+
+    require!(
+        clock.unix_timestamp >= withdrawal_request.quote_deadline,
+        CrossPayError::QuoteWindowOpen
+    );
+    require!(
+        ctx.remaining_accounts.len() % 2 == 0,
+        CrossPayError::InvalidQuote
+    );
+
+    for accounts in ctx.remaining_accounts.chunks(2) {
+        let mut quote = Account::<Quote>::try_from(&accounts[0])?;
+        let liquidity_provider = load_liquidity_provider(&accounts[1], ctx.program_id)?;
+
+        require_keys_eq!(
+            quote.withdrawal_request,
+            withdrawal_request.key(),
+            CrossPayError::InvalidQuote
+        );
+        require_keys_eq!(
+            liquidity_provider.authority,
+            quote.provider,
+            CrossPayError::InvalidQuote
+        );
+        // Persist the flag now so a quote listed twice is rejected
+        require!(!quote.ranked, CrossPayError::InvalidQuote);
+        quote.ranked = true;
+        quote.exit(ctx.program_id)?;
+        withdrawal_request.quotes_ranked = withdrawal_request
+            .quotes_ranked
+            .checked_add(1)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        if !liquidity_provider.can_fill(withdrawal_request) {
+            continue;
+        }
+        if reference_rate.as_ref().is_some_and(|reference| {
+            reference
+                .check_rate(quote.exchange_rate, clock.unix_timestamp)
+                .is_err()
+        }) {
+            continue;
+        }
+        let Ok(payout) = withdrawal_request.payout_at(
+            quote.exchange_rate,
+            quote.fee_bps,
+            quote.fixed_fee,
+            offramp_fee_bps,
+        ) else {
+            continue;
+        };
+        // Ties go to the quote ranked first
+        if withdrawal_request
+            .best_quote
+            .is_none_or(|best| payout > best.payout)
+        {
+            withdrawal_request.best_quote = Some(BestQuote {
+                provider: quote.provider,
+                exchange_rate: quote.exchange_rate,
+                fee_bps: quote.fee_bps,
+                fixed_fee: quote.fixed_fee,
+                payout,
+            });
+        }
+    }
+
+    if withdrawal_request.quotes_ranked < withdrawal_request.quote_count {
+        msg!(
+            "Quotes ranked: {} of {}",
+            withdrawal_request.quotes_ranked,
+            withdrawal_request.quote_count
+        );
+        return Ok(());
+    }
+
+    let Some(best) = withdrawal_request.best_quote.take() else {
+        // Nobody quoted, or no quoting provider still qualifies
+        withdrawal_request.quote_deadline = 0;
+        msg!("No eligible quote - withdrawal reopened for direct provider selection");
+        return Ok(());
+    };
+
+    withdrawal_request.select_provider(
+        best.provider,
+        best.exchange_rate,
+        best.fee_bps,
+        best.fixed_fee,
+        offramp_fee_bps,
+    )?;
+
+    msg!(
+        "Best quote selected from {} - Rate: {}, Net: {}",
+        best.provider,
+        best.exchange_rate,
+        withdrawal_request.net_amount
+    );
+
+    Ok(())
+}
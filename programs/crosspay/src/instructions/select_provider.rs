@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::*;
 use crate::errors::CrossPayError;
 use crate::state::*;
 
@@ -24,26 +25,218 @@ pub struct SelectProvider<'info> {
         seeds = [b"liquidity_provider", provider_key.as_ref()],
         bump = liquidity_provider.bump,
         constraint = liquidity_provider.is_active @ CrossPayError::ProviderNotActive,
-        constraint = liquidity_provider.available_liquidity >= withdrawal_request.amount @ CrossPayError::InsufficientLiquidity
+        constraint = !liquidity_provider.is_frozen @ CrossPayError::AccountFrozen,
+        constraint = liquidity_provider.currency == withdrawal_request.currency @ CrossPayError::CurrencyMismatch,
+        constraint = liquidity_provider.free_liquidity() >= withdrawal_request.amount @ CrossPayError::InsufficientLiquidity,
+        constraint = liquidity_provider.trust_score >= withdrawal_request.min_trust_score @ CrossPayError::TrustScoreTooLow,
+        constraint = liquidity_provider.supports(withdrawal_request.payout_method) @ CrossPayError::PayoutMethodNotSupported
     )]
     pub liquidity_provider: Account<'info, LiquidityProvider>,
 
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Reference rate PDA for the payout currency - may be unconfigured;
+    /// validated in ReferenceRate::load_pair
+    pub reference_rate: UncheckedAccount<'info>,
+
     pub freelancer: Signer<'info>,
 }
 
 /// Select a liquidity provider for the withdrawal
-pub fn select_provider(ctx: Context<SelectProvider>, provider_key: Pubkey) -> Result<()> {
+/// `min_net_amount` guards against the provider raising its fees before this lands.
+pub fn select_provider(
+    ctx: Context<SelectProvider>,
+    provider_key: Pubkey,
+    min_net_amount: u64,
+) -> Result<()> {
     let withdrawal_request = &mut ctx.accounts.withdrawal_request;
 
     require!(
         withdrawal_request.selected_provider.is_none(),
         CrossPayError::ProviderAlreadySelected
     );
+    require!(!withdrawal_request.is_rfq(), CrossPayError::RfqWithdrawal);
 
-    withdrawal_request.selected_provider = Some(provider_key);
-    withdrawal_request.status = WithdrawalStatus::ProviderSelected;
+    // The provider's rate may have drifted from the market since it registered
+    let liquidity_provider = &ctx.accounts.liquidity_provider;
+    if let Some(reference_rate) =
+        ReferenceRate::load_pair(&ctx.accounts.reference_rate, &withdrawal_request.currency)?
+    {
+        reference_rate.check_rate(
+            liquidity_provider.exchange_rate,
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
 
-    msg!("Liquidity provider selected: {}", provider_key);
+    // Fees are locked in now so later changes by the provider or the
+    // platform can't alter a withdrawal the freelancer has committed to
+    withdrawal_request.select_provider(
+        provider_key,
+        liquidity_provider.exchange_rate,
+        liquidity_provider.fee_bps,
+        liquidity_provider.fixed_fee,
+        ctx.accounts.platform_config.offramp_fee_bps,
+    )?;
+    require!(
+        withdrawal_request.net_amount >= min_net_amount,
+        CrossPayError::NetAmountTooLow
+    );
+
+    msg!(
+        "Liquidity provider selected: {} (provider fee: {}, platform fee: {}, net: {})",
+        provider_key,
+        withdrawal_request.provider_fee,
+        withdrawal_request.platform_fee,
+        withdrawal_request.net_amount
+    );
 
     Ok(())
 }
+
+/// Context for routing a withdrawal to the best available provider
+///
+/// Candidate `LiquidityProvider` accounts are supplied through `remaining_accounts`.
+#[derive(Accounts)]
+pub struct RouteWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::Pending @ CrossPayError::InvalidWithdrawalStatus,
+        constraint = !withdrawal_request.is_rfq() @ CrossPayError::RfqWithdrawal,
+        has_one = freelancer
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Reference rate PDA for the payout currency - may be unconfigured;
+    /// validated in ReferenceRate::load_pair
+    pub reference_rate: UncheckedAccount<'info>,
+
+    pub freelancer: Signer<'info>,
+}
+
+/// Pick the candidate provider paying the freelancer the most local currency
+/// Candidates that can't fill the withdrawal, quote outside the reference band,
+/// or aren't in `location` when given, are passed over. Fails if even the best leaves less than `min_net_amount`.
+pub fn route_withdrawal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RouteWithdrawal<'info>>,
+    location: Option<String>,
+    min_net_amount: u64,
+) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let offramp_fee_bps = ctx.accounts.platform_config.offramp_fee_bps;
+    let reference_rate =
+        ReferenceRate::load_pair(&ctx.accounts.reference_rate, &withdrawal_request.currency)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        withdrawal_request.selected_provider.is_none(),
+        CrossPayError::ProviderAlreadySelected
+    );
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_ROUTE_CANDIDATES,
+        CrossPayError::InvalidProviderAccount
+    );
+
+    let mut best: Option<(Account<LiquidityProvider>, u128)> = None;
+
+    for account in ctx.remaining_accounts.iter() {
+        let liquidity_provider = load_liquidity_provider(account, ctx.program_id)?;
+
+        if !liquidity_provider.can_fill(withdrawal_request) {
+            continue;
+        }
+        if reference_rate.as_ref().is_some_and(|reference| {
+            reference
+                .check_rate(liquidity_provider.exchange_rate, now)
+                .is_err()
+        }) {
+            continue;
+        }
+        if location
+            .as_ref()
+            .is_some_and(|location| *location != liquidity_provider.location)
+        {
+            continue;
+        }
+        let Ok(payout) = withdrawal_request.payout_at(
+            liquidity_provider.exchange_rate,
+            liquidity_provider.fee_bps,
+            liquidity_provider.fixed_fee,
+            offramp_fee_bps,
+        ) else {
+            continue;
+        };
+
+        // Ties go to the candidate listed first
+        if best
+            .as_ref()
+            .is_none_or(|(_, best_payout)| payout > *best_payout)
+        {
+            best = Some((liquidity_provider, payout));
+        }
+    }
+
+    let (liquidity_provider, _) = best.ok_or(CrossPayError::NoEligibleProvider)?;
+
+    withdrawal_request.select_provider(
+        liquidity_provider.authority,
+        liquidity_provider.exchange_rate,
+        liquidity_provider.fee_bps,
+        liquidity_provider.fixed_fee,
+        offramp_fee_bps,
+    )?;
+    require!(
+        withdrawal_request.net_amount >= min_net_amount,
+        CrossPayError::NetAmountTooLow
+    );
+
+    msg!(
+        "Withdrawal routed to {} - Rate: {}, Net: {}",
+        liquidity_provider.authority,
+        liquidity_provider.exchange_rate,
+        withdrawal_request.net_amount
+    );
+
+    Ok(())
+}
+
+/// Load a `LiquidityProvider` passed through `remaining_accounts`, checking it
+/// is the canonical PDA of its authority
+pub(crate) fn load_liquidity_provider<'info>(
+    info: &'info AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<Account<'info, LiquidityProvider>> {
+    let liquidity_provider = Account::<LiquidityProvider>::try_from(info)?;
+
+    let expected_provider = Pubkey::create_program_address(
+        &[
+            b"liquidity_provider",
+            liquidity_provider.authority.as_ref(),
+            &[liquidity_provider.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| CrossPayError::InvalidProviderAccount)?;
+    require_keys_eq!(
+        expected_provider,
+        liquidity_provider.key(),
+        CrossPayError::InvalidProviderAccount
+    );
+
+    Ok(liquidity_provider)
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for the freelancer submitting a milestone for review
+#[derive(Accounts)]
+pub struct SubmitMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"contract",
+            contract.client.as_ref(),
+            contract.freelancer.as_ref(),
+            &contract.nonce.to_le_bytes()
+        ],
+        bump = contract.bump,
+        has_one = freelancer,
+        constraint = contract.status == ContractStatus::Active @ CrossPayError::InvalidContractStatus
+    )]
+    pub contract: Account<'info, Contract>,
+
+    pub freelancer: Signer<'info>,
+}
+
+/// Mark a pending milestone as submitted
+/// Submissions close at the contract deadline, once the client may reclaim the milestone.
+pub fn submit_milestone(ctx: Context<SubmitMilestone>, index: u8) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp < ctx.accounts.contract.deadline,
+        CrossPayError::ContractExpired
+    );
+
+    let milestone = ctx
+        .accounts
+        .contract
+        .milestones
+        .get_mut(index as usize)
+        .ok_or(CrossPayError::InvalidMilestones)?;
+
+    require!(
+        milestone.status == MilestoneStatus::Pending,
+        CrossPayError::InvalidMilestoneStatus
+    );
+
+    milestone.status = MilestoneStatus::Submitted;
+
+    msg!("Milestone {} submitted", index);
+
+    Ok(())
+}
+
+/// Context for contesting a submitted milestone
+#[derive(Accounts)]
+pub struct DisputeMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"contract",
+            contract.client.as_ref(),
+            contract.freelancer.as_ref(),
+            &contract.nonce.to_le_bytes()
+        ],
+        bump = contract.bump,
+        constraint = contract.status == ContractStatus::Active @ CrossPayError::InvalidContractStatus,
+        constraint = authority.key() == contract.client
+            || authority.key() == contract.freelancer @ CrossPayError::Unauthorized
+    )]
+    pub contract: Account<'info, Contract>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Escalate a submitted milestone to the arbiter
+pub fn dispute_milestone(ctx: Context<DisputeMilestone>, index: u8) -> Result<()> {
+    let milestone = ctx
+        .accounts
+        .contract
+        .milestones
+        .get_mut(index as usize)
+        .ok_or(CrossPayError::InvalidMilestones)?;
+
+    require!(
+        milestone.status == MilestoneStatus::Submitted,
+        CrossPayError::InvalidMilestoneStatus
+    );
+
+    milestone.status = MilestoneStatus::Disputed;
+
+    msg!(
+        "Milestone {} disputed by {}",
+        index,
+        ctx.accounts.authority.key()
+    );
+
+    Ok(())
+}
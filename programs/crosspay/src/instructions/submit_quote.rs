@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::state::*;
+
+/// Context for a liquidity provider quoting on a withdrawal
+#[derive(Accounts)]
+pub struct SubmitQuote<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::Pending @ CrossPayError::InvalidWithdrawalStatus,
+        constraint = withdrawal_request.is_rfq() @ CrossPayError::NotRfqWithdrawal
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Quote::LEN,
+        seeds = [b"quote", withdrawal_request.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub quote: Account<'info, Quote>,
+
+    #[account(
+        seeds = [b"liquidity_provider", authority.key().as_ref()],
+        bump = liquidity_provider.bump,
+        has_one = authority,
+        constraint = liquidity_provider.is_active @ CrossPayError::ProviderNotActive,
+        constraint = !liquidity_provider.is_frozen @ CrossPayError::AccountFrozen,
+        constraint = liquidity_provider.currency == withdrawal_request.currency @ CrossPayError::CurrencyMismatch,
+        constraint = liquidity_provider.free_liquidity() >= withdrawal_request.amount @ CrossPayError::InsufficientLiquidity,
+        constraint = liquidity_provider.trust_score >= withdrawal_request.min_trust_score @ CrossPayError::TrustScoreTooLow,
+        constraint = liquidity_provider.supports(withdrawal_request.payout_method) @ CrossPayError::PayoutMethodNotSupported
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Reference rate PDA for the payout currency - may be unconfigured;
+    /// validated in ReferenceRate::load_pair
+    pub reference_rate: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Submit a rate and fee for a withdrawal during its quote window
+pub fn submit_quote(
+    ctx: Context<SubmitQuote>,
+    exchange_rate: u64,
+    fee_bps: u16,
+    fixed_fee: u64,
+) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp < withdrawal_request.quote_deadline,
+        CrossPayError::QuoteWindowClosed
+    );
+    require!(exchange_rate > 0, CrossPayError::InvalidAmount);
+    if let Some(reference_rate) =
+        ReferenceRate::load_pair(&ctx.accounts.reference_rate, &withdrawal_request.currency)?
+    {
+        reference_rate.check_rate(exchange_rate, clock.unix_timestamp)?;
+    }
+    require!(
+        fee_bps <= MAX_WITHDRAWAL_FEE_BPS && fixed_fee <= MAX_WITHDRAWAL_FIXED_FEE,
+        CrossPayError::InvalidWithdrawalFee
+    );
+    // Reject quotes whose fees would swallow the whole withdrawal
+    calculate_withdrawal_fees(
+        withdrawal_request.amount,
+        fee_bps,
+        fixed_fee,
+        ctx.accounts.platform_config.offramp_fee_bps,
+    )?;
+
+    let quote = &mut ctx.accounts.quote;
+
+    quote.withdrawal_request = withdrawal_request.key();
+    quote.provider = ctx.accounts.authority.key();
+    quote.exchange_rate = exchange_rate;
+    quote.fee_bps = fee_bps;
+    quote.fixed_fee = fixed_fee;
+    quote.created_at = clock.unix_timestamp;
+    quote.bump = ctx.bumps.quote;
+    quote.ranked = false;
+
+    withdrawal_request.quote_count = withdrawal_request
+        .quote_count
+        .checked_add(1)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    msg!(
+        "Quote submitted by {} - Rate: {}, Fee: {} bps + {}",
+        quote.provider,
+        exchange_rate,
+        fee_bps,
+        fixed_fee
+    );
+
+    Ok(())
+}
+
+/// Context for a liquidity provider withdrawing its quote
+#[derive(Accounts)]
+pub struct WithdrawQuote<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"quote", withdrawal_request.key().as_ref(), authority.key().as_ref()],
+        bump = quote.bump,
+        constraint = quote.provider == authority.key() @ CrossPayError::InvalidQuote
+    )]
+    pub quote: Account<'info, Quote>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Withdraw a quote and reclaim its rent
+/// Once a withdrawal has moved on, its quotes are only closed for rent.
+pub fn withdraw_quote(ctx: Context<WithdrawQuote>) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+
+    if withdrawal_request.status == WithdrawalStatus::Pending && withdrawal_request.is_rfq() {
+        // Quotes are frozen once the window closes so the crank ranks each exactly once
+        require!(
+            Clock::get()?.unix_timestamp < withdrawal_request.quote_deadline,
+            CrossPayError::QuoteWindowClosed
+        );
+        withdrawal_request.quote_count = withdrawal_request
+            .quote_count
+            .checked_sub(1)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+    }
+
+    msg!(
+        "Quote withdrawn by {} from withdrawal {}",
+        ctx.accounts.authority.key(),
+        withdrawal_request.key()
+    );
+
+    Ok(())
+}
+
+/// Context for a freelancer accepting a quote
+#[derive(Accounts)]
+pub struct AcceptQuote<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"withdrawal_request",
+            withdrawal_request.freelancer.as_ref(),
+            &withdrawal_request.nonce.to_le_bytes()
+        ],
+        bump = withdrawal_request.bump,
+        constraint = withdrawal_request.status == WithdrawalStatus::Pending @ CrossPayError::InvalidWithdrawalStatus,
+        constraint = withdrawal_request.is_rfq() @ CrossPayError::NotRfqWithdrawal,
+        has_one = freelancer
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(
+        seeds = [b"quote", withdrawal_request.key().as_ref(), quote.provider.as_ref()],
+        bump = quote.bump
+    )]
+    pub quote: Account<'info, Quote>,
+
+    // The provider must still qualify, as its standing may have changed since quoting
+    #[account(
+        seeds = [b"liquidity_provider", quote.provider.as_ref()],
+        bump = liquidity_provider.bump,
+        constraint = liquidity_provider.can_fill(&withdrawal_request) @ CrossPayError::NoEligibleProvider
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Reference rate PDA for the payout currency - may be unconfigured;
+    /// validated in ReferenceRate::load_pair
+    pub reference_rate: UncheckedAccount<'info>,
+
+    pub freelancer: Signer<'info>,
+}
+
+/// Accept a quote, locking in its rate and fees
+/// The quoted rate must still sit within the reference band.
+pub fn accept_quote(ctx: Context<AcceptQuote>) -> Result<()> {
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+    let quote = &ctx.accounts.quote;
+
+    if let Some(reference_rate) =
+        ReferenceRate::load_pair(&ctx.accounts.reference_rate, &withdrawal_request.currency)?
+    {
+        reference_rate.check_rate(quote.exchange_rate, Clock::get()?.unix_timestamp)?;
+    }
+
+    withdrawal_request.select_provider(
+        quote.provider,
+        quote.exchange_rate,
+        quote.fee_bps,
+        quote.fixed_fee,
+        ctx.accounts.platform_config.offramp_fee_bps,
+    )?;
+
+    msg!(
+        "Quote accepted from {} - Rate: {}, Net: {}",
+        quote.provider,
+        quote.exchange_rate,
+        withdrawal_request.net_amount
+    );
+
+    Ok(())
+}
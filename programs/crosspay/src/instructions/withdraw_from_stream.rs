@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+use crate::instructions::confirm_transfer::pay_referral_reward;
+use crate::instructions::create_stream::close_stream_escrow;
+use crate::state::*;
+
+/// Context for withdrawing the vested portion of a stream
+#[derive(Accounts)]
+pub struct WithdrawFromStream<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"payment_stream",
+            payment_stream.sender.as_ref(),
+            payment_stream.receiver.as_ref(),
+            &payment_stream.nonce.to_le_bytes()
+        ],
+        bump = payment_stream.bump,
+        constraint = payment_stream.receiver == authority.key() @ CrossPayError::Unauthorized,
+        constraint = payment_stream.status != StreamStatus::Completed @ CrossPayError::InvalidStreamStatus
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+
+    #[account(
+        mut,
+        seeds = [b"stream_escrow", payment_stream.key().as_ref()],
+        bump
+    )]
+    pub stream_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", payment_stream.sender.as_ref()],
+        bump = sender_profile.bump
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", payment_stream.receiver.as_ref()],
+        bump = receiver_profile.bump
+    )]
+    pub receiver_profile: Account<'info, UserProfile>,
+
+    /// CHECK: Deny-list PDA for the sender - must not exist
+    #[account(
+        seeds = [b"denied_address", payment_stream.sender.as_ref()],
+        bump,
+        constraint = sender_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub sender_denial: UncheckedAccount<'info>,
+
+    /// CHECK: Deny-list PDA for the receiver - must not exist
+    #[account(
+        seeds = [b"denied_address", payment_stream.receiver.as_ref()],
+        bump,
+        constraint = receiver_denial.data_is_empty() @ CrossPayError::SanctionedAddress
+    )]
+    pub receiver_denial: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == authority.key(),
+        constraint = receiver_token_account.mint == payment_stream.mint
+    )]
+    pub receiver_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"platform_config"],
+        bump = platform_config.bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        constraint = platform_fee_account.owner == PLATFORM_FEE_RECIPIENT,
+        constraint = platform_fee_account.mint == payment_stream.mint
+    )]
+    pub platform_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Rewards PDA of the receiver's referrer - required when the receiver
+    /// was referred, may be unopened; validated in ReferralRewards::share_of
+    #[account(mut)]
+    pub referral_rewards: Option<UncheckedAccount<'info>>,
+
+    /// Only required when a referral reward is credited
+    #[account(
+        mut,
+        seeds = [b"referral_vault", payment_stream.mint.as_ref()],
+        bump
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Stream sender - receives the escrow rent once the stream is fully withdrawn
+    #[account(mut, address = payment_stream.sender)]
+    pub sender: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraw everything vested so far, less the platform fee
+pub fn withdraw_from_stream(ctx: Context<WithdrawFromStream>) -> Result<()> {
+    let payment_stream = &mut ctx.accounts.payment_stream;
+    let clock = Clock::get()?;
+
+    // A cancelled stream has vested exactly what was not refunded
+    let vested = if payment_stream.status == StreamStatus::Cancelled {
+        payment_stream
+            .deposited_amount
+            .saturating_sub(payment_stream.refunded_amount)
+    } else {
+        calculate_vested_amount(
+            payment_stream.deposited_amount,
+            payment_stream.start_time,
+            payment_stream.end_time,
+            clock.unix_timestamp,
+        )
+    };
+    let amount = vested.saturating_sub(payment_stream.withdrawn_amount);
+    require!(amount > 0, CrossPayError::NothingToWithdraw);
+    // The stream as a whole is the transfer, not each withdrawal from it
+    ctx.accounts.platform_config.check_travel_rule(
+        payment_stream.deposited_amount,
+        payment_stream.travel_rule.as_ref(),
+    )?;
+
+    // The fee was priced on the whole deposit at creation and accrues with withdrawals
+    let released = payment_stream
+        .withdrawn_amount
+        .checked_add(amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+    let platform_fee = payment_stream
+        .fee_due(released)
+        .saturating_sub(payment_stream.fee_due(payment_stream.withdrawn_amount));
+    let net_amount = amount
+        .checked_sub(platform_fee)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    let sender_key = payment_stream.sender;
+    let receiver_key = payment_stream.receiver;
+    let nonce_bytes = payment_stream.nonce.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"payment_stream",
+        sender_key.as_ref(),
+        receiver_key.as_ref(),
+        &nonce_bytes,
+        &[payment_stream.bump],
+    ]];
+
+    // Transfer net amount to receiver
+    let receiver_cpi_accounts = Transfer {
+        from: ctx.accounts.stream_escrow.to_account_info(),
+        to: ctx.accounts.receiver_token_account.to_account_info(),
+        authority: payment_stream.to_account_info(),
+    };
+    let receiver_cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        receiver_cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(receiver_cpi_ctx, net_amount)?;
+
+    // The receiver's referrer shares in the fee; the treasury takes the rest
+    let referral_rewards = ctx.accounts.referral_rewards.as_ref().map(|info| info.as_ref());
+    let referral_reward = ReferralRewards::share_of(
+        referral_rewards,
+        ctx.accounts.receiver_profile.referrer,
+        &payment_stream.mint,
+        platform_fee,
+        ctx.accounts.platform_config.referral_share_bps,
+    )?;
+    pay_referral_reward(
+        referral_reward,
+        referral_rewards,
+        ctx.accounts.referral_vault.as_ref(),
+        ctx.accounts.stream_escrow.to_account_info(),
+        payment_stream.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+    let treasury_fee = platform_fee - referral_reward;
+
+    // Transfer the rest of the platform fee to the treasury (only if > 0)
+    if treasury_fee > 0 {
+        let fee_cpi_accounts = Transfer {
+            from: ctx.accounts.stream_escrow.to_account_info(),
+            to: ctx.accounts.platform_fee_account.to_account_info(),
+            authority: payment_stream.to_account_info(),
+        };
+        let fee_cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            fee_cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(fee_cpi_ctx, treasury_fee)?;
+    }
+
+    payment_stream.withdrawn_amount = payment_stream
+        .withdrawn_amount
+        .checked_add(amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    let fully_withdrawn = payment_stream.withdrawn_amount
+        == payment_stream
+            .deposited_amount
+            .saturating_sub(payment_stream.refunded_amount);
+    if fully_withdrawn {
+        payment_stream.status = StreamStatus::Completed;
+        close_stream_escrow(
+            &mut ctx.accounts.stream_escrow,
+            payment_stream.to_account_info(),
+            ctx.accounts.sender.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+    }
+
+    ctx.accounts.sender_profile.record_sent(amount, clock.unix_timestamp)?;
+
+    ctx.accounts.receiver_profile.total_received = ctx
+        .accounts
+        .receiver_profile
+        .total_received
+        .checked_add(net_amount)
+        .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+    msg!(
+        "Stream withdrawal: {} tokens released, {} received (fee: {})",
+        amount,
+        net_amount,
+        platform_fee
+    );
+
+    Ok(())
+}
@@ -3,10 +3,14 @@ use anchor_lang::prelude::*;
 pub mod constants;
 pub mod errors;
 pub mod instructions;
+pub mod iso;
 pub mod state;
 
 use instructions::*;
-use state::{PayoutMethod, UserRole};
+use state::{
+    ComplianceReason, CurrencyCode, FeeBearer, FeeRounding, FeeTier, PayoutMethod, SplitShare,
+    TravelRuleRecord, UserRole,
+};
 
 declare_id!("4fy5wximsVYsVYwLp5VrgjqfUq8NyEXG1nisKuwkS8Vq");
 
@@ -14,13 +18,218 @@ declare_id!("4fy5wximsVYsVYwLp5VrgjqfUq8NyEXG1nisKuwkS8Vq");
 pub mod crosspay {
     use super::*;
 
+    /// Initialize the platform configuration (upgrade authority only)
+    pub fn initialize_platform_config(
+        ctx: Context<InitializePlatformConfig>,
+        large_transfer_threshold: u64,
+        hold_period: i64,
+    ) -> Result<()> {
+        instructions::initialize_platform_config(ctx, large_transfer_threshold, hold_period)
+    }
+
+    /// Update the large-transfer hold threshold and cool-off period
+    pub fn update_hold_settings(
+        ctx: Context<UpdateHoldSettings>,
+        large_transfer_threshold: u64,
+        hold_period: i64,
+    ) -> Result<()> {
+        instructions::update_hold_settings(ctx, large_transfer_threshold, hold_period)
+    }
+
+    /// Update the amount above which transfers need travel-rule data
+    pub fn update_travel_rule_threshold(
+        ctx: Context<UpdateTravelRuleThreshold>,
+        travel_rule_threshold: u64,
+    ) -> Result<()> {
+        instructions::update_travel_rule_threshold(ctx, travel_rule_threshold)
+    }
+
+    /// Create the volume-based fee schedule (platform admin only)
+    pub fn initialize_fee_schedule(
+        ctx: Context<InitializeFeeSchedule>,
+        tiers: Vec<FeeTier>,
+    ) -> Result<()> {
+        instructions::initialize_fee_schedule(ctx, tiers)
+    }
+
+    /// Replace the volume-based fee tiers (platform admin only)
+    pub fn update_fee_tiers(ctx: Context<UpdateFeeTiers>, tiers: Vec<FeeTier>) -> Result<()> {
+        instructions::update_fee_tiers(ctx, tiers)
+    }
+
+    /// Configure the fee floor, ceiling and rounding for a mint (platform admin only)
+    pub fn initialize_mint_fee_config(
+        ctx: Context<InitializeMintFeeConfig>,
+        min_fee: u64,
+        max_fee: u64,
+        rounding: FeeRounding,
+    ) -> Result<()> {
+        instructions::initialize_mint_fee_config(ctx, min_fee, max_fee, rounding)
+    }
+
+    /// Update the fee floor, ceiling and rounding for a mint (platform admin only)
+    pub fn update_mint_fee_config(
+        ctx: Context<UpdateMintFeeConfig>,
+        min_fee: u64,
+        max_fee: u64,
+        rounding: FeeRounding,
+    ) -> Result<()> {
+        instructions::update_mint_fee_config(ctx, min_fee, max_fee, rounding)
+    }
+
+    /// Set the share of platform fees paid to referrers (platform admin only)
+    pub fn update_referral_share(
+        ctx: Context<UpdateReferralShare>,
+        referral_share_bps: u16,
+    ) -> Result<()> {
+        instructions::update_referral_share(ctx, referral_share_bps)
+    }
+
+    /// Set the platform fee charged on withdrawals (admin only)
+    pub fn update_offramp_fee(ctx: Context<UpdateOfframpFee>, offramp_fee_bps: u16) -> Result<()> {
+        instructions::update_offramp_fee(ctx, offramp_fee_bps)
+    }
+
+    /// Create the vault funding referral rewards for a mint
+    pub fn initialize_referral_vault(ctx: Context<InitializeReferralVault>) -> Result<()> {
+        instructions::initialize_referral_vault(ctx)
+    }
+
+    /// Open a referrer's rewards balance for a mint
+    pub fn initialize_referral_rewards(
+        ctx: Context<InitializeReferralRewards>,
+        referrer: Pubkey,
+    ) -> Result<()> {
+        instructions::initialize_referral_rewards(ctx, referrer)
+    }
+
+    /// Claim accrued referral rewards
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        instructions::claim_referral_rewards(ctx)
+    }
+
+    /// Create the vault holding large transfers for a mint
+    pub fn initialize_hold_vault(ctx: Context<InitializeHoldVault>) -> Result<()> {
+        instructions::initialize_hold_vault(ctx)
+    }
+
+    /// Appoint a compliance officer (platform admin only)
+    pub fn add_compliance_officer(
+        ctx: Context<AddComplianceOfficer>,
+        officer: Pubkey,
+    ) -> Result<()> {
+        instructions::add_compliance_officer(ctx, officer)
+    }
+
+    /// Remove a compliance officer (platform admin only)
+    pub fn remove_compliance_officer(ctx: Context<RemoveComplianceOfficer>) -> Result<()> {
+        instructions::remove_compliance_officer(ctx)
+    }
+
+    /// Add an address to the sanctions deny-list (platform admin only)
+    pub fn add_denied_address(
+        ctx: Context<AddDeniedAddress>,
+        address: Pubkey,
+        reason: ComplianceReason,
+    ) -> Result<()> {
+        instructions::add_denied_address(ctx, address, reason)
+    }
+
+    /// Remove an address from the sanctions deny-list (platform admin only)
+    pub fn remove_denied_address(ctx: Context<RemoveDeniedAddress>) -> Result<()> {
+        instructions::remove_denied_address(ctx)
+    }
+
+    /// Freeze or unfreeze a user profile (compliance officer only)
+    pub fn set_user_frozen(
+        ctx: Context<SetUserFrozen>,
+        frozen: bool,
+        reason: ComplianceReason,
+    ) -> Result<()> {
+        instructions::set_user_frozen(ctx, frozen, reason)
+    }
+
+    /// Freeze or unfreeze a liquidity provider (compliance officer only)
+    pub fn set_provider_frozen(
+        ctx: Context<SetProviderFrozen>,
+        frozen: bool,
+        reason: ComplianceReason,
+    ) -> Result<()> {
+        instructions::set_provider_frozen(ctx, frozen, reason)
+    }
+
+    /// Place a pending transfer under compliance review
+    pub fn hold_transfer(ctx: Context<HoldTransfer>, reason: ComplianceReason) -> Result<()> {
+        instructions::hold_transfer(ctx, reason)
+    }
+
+    /// Release or reject a transfer under compliance review
+    pub fn resolve_transfer_hold(
+        ctx: Context<ResolveTransferHold>,
+        release: bool,
+        resolution: ComplianceReason,
+    ) -> Result<()> {
+        instructions::resolve_transfer_hold(ctx, release, resolution)
+    }
+
+    /// Place a pending withdrawal under compliance review
+    pub fn hold_withdrawal(ctx: Context<HoldWithdrawal>, reason: ComplianceReason) -> Result<()> {
+        instructions::hold_withdrawal(ctx, reason)
+    }
+
+    /// Release or reject a withdrawal under compliance review
+    pub fn resolve_withdrawal_hold(
+        ctx: Context<ResolveWithdrawalHold>,
+        release: bool,
+        resolution: ComplianceReason,
+    ) -> Result<()> {
+        instructions::resolve_withdrawal_hold(ctx, release, resolution)
+    }
+
+    /// Configure a transfer corridor between two countries (platform admin only)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_corridor(
+        ctx: Context<CreateCorridor>,
+        sender_country: String,
+        receiver_country: String,
+        enabled: bool,
+        fee_bps: Option<u16>,
+        min_amount: u64,
+        max_amount: u64,
+        daily_cap: u64,
+    ) -> Result<()> {
+        instructions::create_corridor(
+            ctx,
+            sender_country,
+            receiver_country,
+            enabled,
+            fee_bps,
+            min_amount,
+            max_amount,
+            daily_cap,
+        )
+    }
+
+    /// Update a transfer corridor's settings (platform admin only)
+    pub fn update_corridor(
+        ctx: Context<UpdateCorridor>,
+        enabled: bool,
+        fee_bps: Option<u16>,
+        min_amount: u64,
+        max_amount: u64,
+        daily_cap: u64,
+    ) -> Result<()> {
+        instructions::update_corridor(ctx, enabled, fee_bps, min_amount, max_amount, daily_cap)
+    }
+
     /// Initialize a user profile (sender or receiver)
     pub fn initialize_user(
         ctx: Context<InitializeUser>,
         role: UserRole,
         country_code: String,
+        referrer: Option<Pubkey>,
     ) -> Result<()> {
-        instructions::initialize_user(ctx, role, country_code)
+        instructions::initialize_user(ctx, role, country_code, referrer)
     }
 
     /// Update KYC verification status
@@ -37,8 +246,18 @@ pub mod crosspay {
         ctx: Context<InitiateTransfer>,
         amount: u64,
         receiver: Pubkey,
+        fee_bearer: FeeBearer,
     ) -> Result<()> {
-        instructions::initiate_transfer(ctx, amount, receiver)
+        instructions::initiate_transfer(ctx, amount, receiver, fee_bearer)
+    }
+
+    /// Attach travel-rule data to a pending transfer
+    pub fn attach_travel_rule(
+        ctx: Context<AttachTravelRule>,
+        data_hash: [u8; 32],
+        blob_uri: String,
+    ) -> Result<()> {
+        instructions::attach_travel_rule(ctx, data_hash, blob_uri)
     }
 
     /// Confirm and execute the transfer
@@ -46,13 +265,282 @@ pub mod crosspay {
         instructions::confirm_transfer(ctx)
     }
 
+    /// Release a held transfer after its cool-off (permissionless crank)
+    pub fn release_held_transfer(ctx: Context<ReleaseHeldTransfer>) -> Result<()> {
+        instructions::release_held_transfer(ctx)
+    }
+
+    /// Raise a fraud claim on a held transfer during its cool-off
+    pub fn raise_fraud_claim(ctx: Context<RaiseFraudClaim>) -> Result<()> {
+        instructions::raise_fraud_claim(ctx)
+    }
+
+    /// Resolve a fraud claim (platform admin only)
+    pub fn resolve_fraud_claim(ctx: Context<ResolveFraudClaim>, refund_sender: bool) -> Result<()> {
+        instructions::resolve_fraud_claim(ctx, refund_sender)
+    }
+
+    /// Refund a completed transfer back to its sender
+    pub fn refund_transfer(
+        ctx: Context<RefundTransfer>,
+        amount: u64,
+        refund_fee: bool,
+    ) -> Result<()> {
+        instructions::refund_transfer(ctx, amount, refund_fee)
+    }
+
+    /// Initiate a transfer split across several receivers by percentage
+    pub fn initiate_split_transfer(
+        ctx: Context<InitiateSplitTransfer>,
+        amount: u64,
+        splits: Vec<SplitShare>,
+    ) -> Result<()> {
+        instructions::initiate_split_transfer(ctx, amount, splits)
+    }
+
+    /// Confirm a split transfer and pay each receiver its share
+    pub fn confirm_split_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfirmSplitTransfer<'info>>,
+    ) -> Result<()> {
+        instructions::confirm_split_transfer(ctx)
+    }
+
+    /// Pay many receivers from one sender in a single instruction
+    pub fn batch_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchTransfer<'info>>,
+        amounts: Vec<u64>,
+        travel_rule: Option<TravelRuleRecord>,
+    ) -> Result<()> {
+        instructions::batch_transfer(ctx, amounts, travel_rule)
+    }
+
+    /// Create a recurring payment schedule
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_payment_schedule(
+        ctx: Context<CreatePaymentSchedule>,
+        nonce: u64,
+        amount: u64,
+        interval: i64,
+        total_payments: u32,
+        first_payment_at: i64,
+        travel_rule: Option<TravelRuleRecord>,
+    ) -> Result<()> {
+        instructions::create_payment_schedule(
+            ctx,
+            nonce,
+            amount,
+            interval,
+            total_payments,
+            first_payment_at,
+            travel_rule,
+        )
+    }
+
+    /// Execute a due scheduled payment (permissionless crank)
+    pub fn execute_scheduled_payment(ctx: Context<ExecuteScheduledPayment>) -> Result<()> {
+        instructions::execute_scheduled_payment(ctx)
+    }
+
+    /// Cancel a payment schedule and release its allowance
+    pub fn cancel_payment_schedule(ctx: Context<CancelPaymentSchedule>) -> Result<()> {
+        instructions::cancel_payment_schedule(ctx)
+    }
+
+    /// Create a payment stream that vests per second
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        nonce: u64,
+        amount: u64,
+        start_time: i64,
+        end_time: i64,
+        travel_rule: Option<TravelRuleRecord>,
+    ) -> Result<()> {
+        instructions::create_stream(ctx, nonce, amount, start_time, end_time, travel_rule)
+    }
+
+    /// Withdraw the vested portion of a stream
+    pub fn withdraw_from_stream(ctx: Context<WithdrawFromStream>) -> Result<()> {
+        instructions::withdraw_from_stream(ctx)
+    }
+
+    /// Cancel a stream and reclaim the unvested remainder
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        instructions::cancel_stream(ctx)
+    }
+
+    /// Create a milestone contract and fund its escrow
+    pub fn create_contract(
+        ctx: Context<CreateContract>,
+        nonce: u64,
+        arbiter: Pubkey,
+        amounts: Vec<u64>,
+        description_hashes: Vec<[u8; 32]>,
+        deadline: i64,
+        travel_rule: Option<TravelRuleRecord>,
+    ) -> Result<()> {
+        instructions::create_contract(
+            ctx,
+            nonce,
+            arbiter,
+            amounts,
+            description_hashes,
+            deadline,
+            travel_rule,
+        )
+    }
+
+    /// Submit a milestone for client review
+    pub fn submit_milestone(ctx: Context<SubmitMilestone>, index: u8) -> Result<()> {
+        instructions::submit_milestone(ctx, index)
+    }
+
+    /// Approve a submitted milestone and release its funds
+    pub fn approve_milestone(ctx: Context<SettleMilestone>, index: u8) -> Result<()> {
+        instructions::approve_milestone(ctx, index)
+    }
+
+    /// Escalate a submitted milestone to the arbiter
+    pub fn dispute_milestone(ctx: Context<DisputeMilestone>, index: u8) -> Result<()> {
+        instructions::dispute_milestone(ctx, index)
+    }
+
+    /// Refund an unsubmitted milestone (freelancer, or client after the deadline)
+    pub fn cancel_milestone(ctx: Context<SettleMilestone>, index: u8) -> Result<()> {
+        instructions::cancel_milestone(ctx, index)
+    }
+
+    /// Resolve a disputed milestone (arbiter only)
+    pub fn resolve_milestone(
+        ctx: Context<SettleMilestone>,
+        index: u8,
+        release_to_freelancer: bool,
+    ) -> Result<()> {
+        instructions::resolve_milestone(ctx, index, release_to_freelancer)
+    }
+
+    /// Issue an invoice to a payer
+    pub fn create_invoice(
+        ctx: Context<CreateInvoice>,
+        nonce: u64,
+        amount: u64,
+        due_date: i64,
+        reference_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::create_invoice(ctx, nonce, amount, due_date, reference_hash)
+    }
+
+    /// Pay all or part of an invoice
+    pub fn pay_invoice(
+        ctx: Context<PayInvoice>,
+        amount: u64,
+        fee_bearer: FeeBearer,
+        travel_rule: Option<TravelRuleRecord>,
+    ) -> Result<()> {
+        instructions::pay_invoice(ctx, amount, fee_bearer, travel_rule)
+    }
+
+    /// Flag an unpaid invoice past its due date as overdue
+    pub fn mark_invoice_overdue(ctx: Context<MarkInvoiceOverdue>) -> Result<()> {
+        instructions::mark_invoice_overdue(ctx)
+    }
+
+    /// Escrow a transfer for a recipient who has not onboarded yet
+    pub fn create_claimable_transfer(
+        ctx: Context<CreateClaimableTransfer>,
+        nonce: u64,
+        amount: u64,
+        claim_hash: [u8; 32],
+        expires_at: i64,
+        travel_rule: Option<TravelRuleRecord>,
+    ) -> Result<()> {
+        instructions::create_claimable_transfer(
+            ctx,
+            nonce,
+            amount,
+            claim_hash,
+            expires_at,
+            travel_rule,
+        )
+    }
+
+    /// Commit to a claimable transfer's secret ahead of claiming it
+    pub fn commit_claim(ctx: Context<CommitClaim>, commitment: [u8; 32]) -> Result<()> {
+        instructions::commit_claim(ctx, commitment)
+    }
+
+    /// Claim an escrowed transfer by revealing its secret
+    pub fn claim_transfer(ctx: Context<ClaimTransfer>, secret: Vec<u8>) -> Result<()> {
+        instructions::claim_transfer(ctx, secret)
+    }
+
+    /// Reclaim an expired, unclaimed transfer
+    pub fn reclaim_transfer(ctx: Context<ReclaimTransfer>) -> Result<()> {
+        instructions::reclaim_transfer(ctx)
+    }
+
+    /// Create the reference rate for a currency pair (platform admin only)
+    pub fn create_reference_rate(
+        ctx: Context<CreateReferenceRate>,
+        base: CurrencyCode,
+        quote: CurrencyCode,
+        oracle: Pubkey,
+        max_deviation_bps: u16,
+        max_age: i64,
+    ) -> Result<()> {
+        instructions::create_reference_rate(ctx, base, quote, oracle, max_deviation_bps, max_age)
+    }
+
+    /// Update a reference rate's oracle, band and max age (platform admin only)
+    pub fn update_reference_rate_config(
+        ctx: Context<UpdateReferenceRateConfig>,
+        oracle: Pubkey,
+        max_deviation_bps: u16,
+        max_age: i64,
+    ) -> Result<()> {
+        instructions::update_reference_rate_config(ctx, oracle, max_deviation_bps, max_age)
+    }
+
+    /// Post a new reference rate (oracle only)
+    pub fn update_reference_rate(ctx: Context<UpdateReferenceRate>, rate: u64) -> Result<()> {
+        instructions::update_reference_rate(ctx, rate)
+    }
+
     /// Register as a liquidity provider
     pub fn register_liquidity_provider(
         ctx: Context<RegisterLiquidityProvider>,
         location: String,
         exchange_rate: u64,
+        currency: CurrencyCode,
+        fee_bps: u16,
+        fixed_fee: u64,
+        payout_methods: Vec<PayoutMethod>,
+    ) -> Result<()> {
+        instructions::register_liquidity_provider(
+            ctx,
+            location,
+            exchange_rate,
+            currency,
+            fee_bps,
+            fixed_fee,
+            payout_methods,
+        )
+    }
+
+    /// Update the fees a liquidity provider charges on withdrawals
+    pub fn update_provider_fees(
+        ctx: Context<UpdateProviderFees>,
+        fee_bps: u16,
+        fixed_fee: u64,
+    ) -> Result<()> {
+        instructions::update_provider_fees(ctx, fee_bps, fixed_fee)
+    }
+
+    /// Update the payout methods a liquidity provider supports
+    pub fn update_provider_payout_methods(
+        ctx: Context<UpdateProviderPayoutMethods>,
+        payout_methods: Vec<PayoutMethod>,
     ) -> Result<()> {
-        instructions::register_liquidity_provider(ctx, location, exchange_rate)
+        instructions::update_provider_payout_methods(ctx, payout_methods)
     }
 
     /// Update liquidity provider availability
@@ -69,17 +557,123 @@ pub mod crosspay {
         ctx: Context<RequestWithdrawal>,
         amount: u64,
         payout_method: PayoutMethod,
+        currency: CurrencyCode,
+        quote_window: i64,
+        min_trust_score: u16,
     ) -> Result<()> {
-        instructions::request_withdrawal(ctx, amount, payout_method)
+        instructions::request_withdrawal(
+            ctx,
+            amount,
+            payout_method,
+            currency,
+            quote_window,
+            min_trust_score,
+        )
     }
 
     /// Select a liquidity provider for withdrawal
-    pub fn select_provider(ctx: Context<SelectProvider>, provider_key: Pubkey) -> Result<()> {
-        instructions::select_provider(ctx, provider_key)
+    pub fn select_provider(
+        ctx: Context<SelectProvider>,
+        provider_key: Pubkey,
+        min_net_amount: u64,
+    ) -> Result<()> {
+        instructions::select_provider(ctx, provider_key, min_net_amount)
+    }
+
+    /// Route a withdrawal to the best of the candidate providers
+    pub fn route_withdrawal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RouteWithdrawal<'info>>,
+        location: Option<String>,
+        min_net_amount: u64,
+    ) -> Result<()> {
+        instructions::route_withdrawal(ctx, location, min_net_amount)
+    }
+
+    /// Quote a rate and fee on a withdrawal open for quotes
+    pub fn submit_quote(
+        ctx: Context<SubmitQuote>,
+        exchange_rate: u64,
+        fee_bps: u16,
+        fixed_fee: u64,
+    ) -> Result<()> {
+        instructions::submit_quote(ctx, exchange_rate, fee_bps, fixed_fee)
+    }
+
+    /// Withdraw a quote and reclaim its rent
+    pub fn withdraw_quote(ctx: Context<WithdrawQuote>) -> Result<()> {
+        instructions::withdraw_quote(ctx)
+    }
+
+    /// Accept a quote on a withdrawal
+    pub fn accept_quote(ctx: Context<AcceptQuote>) -> Result<()> {
+        instructions::accept_quote(ctx)
+    }
+
+    /// Rank a page of quotes after the window closes, selecting the best once all are ranked (permissionless crank)
+    pub fn select_best_quote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SelectBestQuote<'info>>,
+    ) -> Result<()> {
+        instructions::select_best_quote(ctx)
     }
 
     /// Finalize withdrawal after fiat received
     pub fn finalize_withdrawal(ctx: Context<FinalizeWithdrawal>) -> Result<()> {
         instructions::finalize_withdrawal(ctx)
     }
+
+    /// Open a withdrawal to, or close it off from, direct provider acceptance
+    pub fn set_provider_acceptance(
+        ctx: Context<SetProviderAcceptance>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_provider_acceptance(ctx, enabled)
+    }
+
+    /// Claim an open withdrawal as a liquidity provider
+    pub fn accept_withdrawal(ctx: Context<AcceptWithdrawal>) -> Result<()> {
+        instructions::accept_withdrawal(ctx)
+    }
+
+    /// Reopen a locked withdrawal (freelancer anytime, anyone once the lock times out)
+    pub fn release_withdrawal_lock(ctx: Context<ReleaseWithdrawalLock>) -> Result<()> {
+        instructions::release_withdrawal_lock(ctx)
+    }
+
+    /// Set a target minimum all-in rate and expiry on a withdrawal
+    pub fn set_limit_order(
+        ctx: Context<SetLimitOrder>,
+        min_rate: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::set_limit_order(ctx, min_rate, expires_at)
+    }
+
+    /// Match a limit order with a provider meeting its rate after fees (permissionless crank)
+    pub fn match_limit_order(ctx: Context<MatchLimitOrder>) -> Result<()> {
+        instructions::match_limit_order(ctx)
+    }
+
+    /// Expire an unfilled limit order (permissionless crank)
+    pub fn expire_limit_order(ctx: Context<ExpireLimitOrder>) -> Result<()> {
+        instructions::expire_limit_order(ctx)
+    }
+
+    /// Assign part of a withdrawal to a liquidity provider
+    pub fn add_withdrawal_fill(
+        ctx: Context<AddWithdrawalFill>,
+        provider_key: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::add_withdrawal_fill(ctx, provider_key, amount)
+    }
+
+    /// Finalize one fill of a partially filled withdrawal
+    pub fn finalize_withdrawal_fill(ctx: Context<FinalizeWithdrawalFill>) -> Result<()> {
+        instructions::finalize_withdrawal_fill(ctx)
+    }
+
+    /// Cancel a pending withdrawal fill (its provider anytime, anyone once timed out)
+    pub fn cancel_withdrawal_fill(ctx: Context<CancelWithdrawalFill>) -> Result<()> {
+        instructions::cancel_withdrawal_fill(ctx)
+    }
 }
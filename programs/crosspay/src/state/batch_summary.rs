@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use super::TravelRuleRecord;
+use crate::constants::MAX_BATCH_SIZE;
+
+#[account]
+pub struct BatchSummary {
+    pub sender: Pubkey,                        // 32
+    pub mint: Pubkey,                          // 32
+    pub total_amount: u64,                     // Gross amount across all legs 8
+    pub total_net_amount: u64,                 // Amount received across all legs 8
+    pub total_fee: u64,                        // Fees charged across all legs 8
+    pub legs: Vec<BatchLeg>,                   // 4 + MAX_BATCH_SIZE * BatchLeg::LEN
+    pub created_at: i64,                       // 8
+    pub nonce: u64,                            // 8
+    pub bump: u8,                              // 1
+    pub travel_rule: Option<TravelRuleRecord>, // 1 + TravelRuleRecord::LEN (covers every leg)
+}
+
+impl BatchSummary {
+    pub const LEN: usize =
+        8 + 32 + 32 + 8 + 8 + 8 + (4 + MAX_BATCH_SIZE * BatchLeg::LEN) + 8 + 8 + 1
+            + 1 + TravelRuleRecord::LEN;
+}
+
+/// Result of a single leg within a batch payout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BatchLeg {
+    pub receiver: Pubkey,  // 32
+    pub amount: u64,       // Gross amount 8
+    pub net_amount: u64,   // Amount after fee 8
+    pub platform_fee: u64, // Fee charged 8
+}
+
+impl BatchLeg {
+    pub const LEN: usize = 32 + 8 + 8 + 8;
+}
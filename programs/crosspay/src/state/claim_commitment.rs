@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// A claimant's sealed commitment to a claimable transfer's secret
+#[account]
+pub struct ClaimCommitment {
+    pub claimable_transfer: Pubkey, // 32
+    pub claimant: Pubkey,           // 32
+    pub commitment: [u8; 32],       // 32 (sha256 of secret || claimant)
+    pub slot: u64,                  // 8
+    pub bump: u8,                   // 1
+}
+
+impl ClaimCommitment {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1;
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use super::TravelRuleRecord;
+
+#[account]
+pub struct ClaimableTransfer {
+    pub sender: Pubkey,                        // 32
+    pub mint: Pubkey,                          // 32
+    pub amount: u64,                           // Gross amount 8
+    pub net_amount: u64,                       // Amount after fee 8
+    pub platform_fee: u64,                     // Fee charged 8
+    pub claim_hash: [u8; 32],                  // 32 (sha256 of the claim secret)
+    pub expires_at: i64,                       // 8
+    pub status: ClaimStatus,                   // 1 + 1
+    pub claimed_by: Option<Pubkey>,            // 1 + 32
+    pub created_at: i64,                       // 8
+    pub completed_at: Option<i64>,             // 1 + 8
+    pub nonce: u64,                            // 8
+    pub bump: u8,                              // 1
+    pub travel_rule: Option<TravelRuleRecord>, // 1 + TravelRuleRecord::LEN
+}
+
+impl ClaimableTransfer {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 32 + 8 + 2 + 33 + 8 + 9 + 8 + 1
+        + 1 + TravelRuleRecord::LEN;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimStatus {
+    Pending,
+    Claimed,
+    Reclaimed,
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct ComplianceOfficer {
+    pub officer: Pubkey,      // 32
+    pub appointed_by: Pubkey, // 32
+    pub created_at: i64,      // 8
+    pub bump: u8,             // 1
+}
+
+impl ComplianceOfficer {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// A compliance hold on a request; kept after resolution as its audit record,
+/// so each request can be held at most once
+#[account]
+pub struct ComplianceHold {
+    pub target: Pubkey,               // 32 (held TransferRequest or WithdrawalRequest)
+    pub officer: Pubkey,              // 32 (officer who placed the hold)
+    pub reason: ComplianceReason,     // 1
+    pub created_at: i64,              // 8
+    pub bump: u8,                     // 1
+    pub status: HoldStatus,           // 1
+    pub resolution: ComplianceReason, // 1 (code recorded when resolved)
+    pub resolved_by: Option<Pubkey>,  // 1 + 32
+    pub resolved_at: Option<i64>,     // 1 + 8
+}
+
+impl ComplianceHold {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 1 + 1 + 1 + (1 + 32) + (1 + 8);
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum HoldStatus {
+    Open,
+    Released,
+    Rejected,
+}
+
+/// Reason codes recorded with every compliance action
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ComplianceReason {
+    #[default]
+    None,
+    Sanctions,
+    Fraud,
+    AccountTakeover,
+    SuspiciousActivity,
+    KycReview,
+    LegalOrder,
+    Other,
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use super::TravelRuleRecord;
+use crate::constants::MAX_MILESTONES;
+
+#[account]
+pub struct Contract {
+    pub client: Pubkey,                        // 32
+    pub freelancer: Pubkey,                    // 32
+    pub arbiter: Pubkey,                       // 32
+    pub mint: Pubkey,                          // 32
+    pub total_amount: u64,                     // Gross amount funded into escrow 8
+    pub released_amount: u64,                  // Gross amount released to freelancer 8
+    pub refunded_amount: u64,                  // Amount returned to client by arbiter 8
+    pub milestones: Vec<Milestone>,            // 4 + MAX_MILESTONES * Milestone::LEN
+    pub status: ContractStatus,                // 1 + 1
+    pub created_at: i64,                       // 8
+    pub completed_at: Option<i64>,             // 1 + 8
+    pub deadline: i64,                         // 8 (client may reclaim unsubmitted milestones after this)
+    pub travel_rule: Option<TravelRuleRecord>, // 1 + TravelRuleRecord::LEN
+    pub nonce: u64,                            // 8
+    pub bump: u8,                              // 1
+}
+
+impl Contract {
+    pub const LEN: usize = 8
+        + 32
+        + 32
+        + 32
+        + 32
+        + 8
+        + 8
+        + 8
+        + (4 + MAX_MILESTONES * Milestone::LEN)
+        + 2
+        + 8
+        + 9
+        + 8
+        + 8
+        + (1 + TravelRuleRecord::LEN)
+        + 1;
+
+    /// A contract is settled once no milestone can move any more funds
+    pub fn is_settled(&self) -> bool {
+        self.milestones.iter().all(|milestone| {
+            matches!(
+                milestone.status,
+                MilestoneStatus::Released | MilestoneStatus::Refunded
+            )
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Milestone {
+    pub amount: u64,                // 8
+    pub platform_fee: u64,          // 8 (priced at creation)
+    pub description_hash: [u8; 32], // 32
+    pub status: MilestoneStatus,    // 1
+}
+
+impl Milestone {
+    pub const LEN: usize = 8 + 8 + 32 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MilestoneStatus {
+    Pending,
+    Submitted,
+    Disputed,
+    Released,
+    Refunded,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ContractStatus {
+    Active,
+    Completed,
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+
+#[account]
+pub struct Corridor {
+    pub sender_country: String,   // 4 + max 3 = 7
+    pub receiver_country: String, // 4 + max 3 = 7
+    pub enabled: bool,            // 1
+    pub fee_bps: Option<u16>,     // 1 + 2 (overrides tiered platform fees)
+    pub min_amount: u64,          // 8
+    pub max_amount: u64,          // 8 (0 means no maximum)
+    pub daily_cap: u64,           // 8 (0 means no cap)
+    pub current_day: i64,         // 8 (unix day of daily_volume)
+    pub daily_volume: u64,        // 8
+    pub bump: u8,                 // 1
+}
+
+impl Corridor {
+    pub const LEN: usize = 8 + 7 + 7 + 1 + 3 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /// Read the corridor stored at `info`, if one has been configured
+    /// Unconfigured corridors are unrestricted and use the default fee
+    pub fn load(info: &AccountInfo) -> Result<Option<Corridor>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, CrossPayError::InvalidCorridor);
+
+        let data = info.try_borrow_data()?;
+        Ok(Some(Corridor::try_deserialize(&mut &data[..])?))
+    }
+
+    /// PDA of the corridor between two countries
+    pub fn address(sender_country: &str, receiver_country: &str) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"corridor",
+                sender_country.as_bytes(),
+                receiver_country.as_bytes(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Check a transfer of `amount` against the corridor at `info` and count it
+    /// towards today's volume; a no-op for unconfigured corridors
+    pub fn check_and_record(info: &AccountInfo, amount: u64, now: i64) -> Result<()> {
+        if let Some(mut corridor) = Corridor::load(info)? {
+            corridor.check_transfer(amount, now)?;
+            corridor.record_transfer(amount, now)?;
+            corridor.store(info)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the corridor back to `info` after updating its daily volume
+    pub fn store(&self, info: &AccountInfo) -> Result<()> {
+        let mut data = info.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+
+    /// Fee rate on this corridor, falling back to `default_bps` without an override
+    pub fn fee_bps_or(&self, default_bps: u16) -> u16 {
+        self.fee_bps.unwrap_or(default_bps)
+    }
+
+    /// Volume already confirmed on this corridor during the day containing `now`
+    pub fn volume_on(&self, now: i64) -> u64 {
+        if self.current_day == now / SECONDS_PER_DAY {
+            self.daily_volume
+        } else {
+            0
+        }
+    }
+
+    /// Check that a transfer of `amount` is allowed on this corridor right now
+    pub fn check_transfer(&self, amount: u64, now: i64) -> Result<()> {
+        require!(self.enabled, CrossPayError::CorridorDisabled);
+        require!(
+            amount >= self.min_amount && (self.max_amount == 0 || amount <= self.max_amount),
+            CrossPayError::AmountOutsideCorridorLimits
+        );
+
+        if self.daily_cap > 0 {
+            let volume = self
+                .volume_on(now)
+                .checked_add(amount)
+                .ok_or(CrossPayError::ArithmeticOverflow)?;
+            require!(
+                volume <= self.daily_cap,
+                CrossPayError::CorridorDailyCapExceeded
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Count a confirmed transfer of `amount` against today's volume
+    pub fn record_transfer(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.daily_volume = self
+            .volume_on(now)
+            .checked_add(amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        self.current_day = now / SECONDS_PER_DAY;
+
+        Ok(())
+    }
+}
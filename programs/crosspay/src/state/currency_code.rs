@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::is_valid_currency_code;
+
+/// ISO 4217 alphabetic currency code, e.g. "NGN" or "KES"
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CurrencyCode {
+    pub code: [u8; 3], // 3
+}
+
+impl CurrencyCode {
+    pub const LEN: usize = 3;
+
+    /// Whether this is a known circulating ISO 4217 currency
+    pub fn is_valid(&self) -> bool {
+        std::str::from_utf8(&self.code).is_ok_and(is_valid_currency_code)
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use super::ComplianceReason;
+
+/// Marks an address as blocked from sending, receiving or providing liquidity
+/// Instructions check for the absence of this PDA, so removal closes it.
+#[account]
+pub struct DeniedAddress {
+    pub address: Pubkey,          // 32
+    pub reason: ComplianceReason, // 1
+    pub added_by: Pubkey,         // 32
+    pub created_at: i64,          // 8
+    pub bump: u8,                 // 1
+}
+
+impl DeniedAddress {
+    pub const LEN: usize = 8 + 32 + 1 + 32 + 8 + 1;
+
+    /// Whether `info` is the deny-list PDA of `address` with no entry in it
+    pub fn is_clear(info: &AccountInfo, address: &Pubkey) -> bool {
+        let (expected, _) =
+            Pubkey::find_program_address(&[b"denied_address", address.as_ref()], &crate::ID);
+        info.key() == expected && info.data_is_empty()
+    }
+}
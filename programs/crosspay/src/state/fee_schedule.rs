@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+
+use super::{Corridor, MintFeeConfig};
+use crate::constants::*;
+use crate::errors::CrossPayError;
+
+/// Volume-based platform fee tiers, applied from the sender's windowed volume
+#[account]
+pub struct FeeSchedule {
+    pub tiers: Vec<FeeTier>, // 4 + MAX_FEE_TIERS * FeeTier::LEN
+    pub bump: u8,            // 1
+}
+
+impl FeeSchedule {
+    pub const LEN: usize = 8 + 4 + MAX_FEE_TIERS * FeeTier::LEN + 1;
+
+    /// Read the fee schedule stored at `info`, if one has been configured
+    pub fn load(info: &AccountInfo) -> Result<Option<FeeSchedule>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, CrossPayError::InvalidFeeTiers);
+
+        let data = info.try_borrow_data()?;
+        Ok(Some(FeeSchedule::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Check tiers start at zero volume with strictly increasing thresholds
+    pub fn validate_tiers(tiers: &[FeeTier]) -> Result<()> {
+        require!(
+            !tiers.is_empty() && tiers.len() <= MAX_FEE_TIERS && tiers[0].min_volume == 0,
+            CrossPayError::InvalidFeeTiers
+        );
+        require!(
+            tiers
+                .iter()
+                .all(|tier| tier.fee_bps as u64 <= BASIS_POINTS_DIVISOR),
+            CrossPayError::InvalidFeeTiers
+        );
+        require!(
+            tiers
+                .windows(2)
+                .all(|pair| pair[0].min_volume < pair[1].min_volume),
+            CrossPayError::InvalidFeeTiers
+        );
+
+        Ok(())
+    }
+
+    /// Fee rate of the highest tier whose threshold `volume` has reached
+    pub fn fee_bps_for(&self, volume: u64) -> u16 {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| volume >= tier.min_volume)
+            .map_or(PLATFORM_FEE_BPS, |tier| tier.fee_bps)
+    }
+}
+
+/// Prices the platform fee the same way on every fee-charging path
+///
+/// The sender's volume tier sets the rate, a corridor may override it, and the
+/// mint's fee config bounds and rounds the resulting fee.
+pub struct FeeResolver {
+    tier_bps: u16,
+    mint_fee_config: Option<MintFeeConfig>,
+}
+
+impl FeeResolver {
+    /// Load the fee schedule and mint fee config for a sender with `sender_volume`
+    pub fn load(
+        fee_schedule: &AccountInfo,
+        mint_fee_config: &AccountInfo,
+        sender_volume: u64,
+    ) -> Result<Self> {
+        let tier_bps = FeeSchedule::load(fee_schedule)?
+            .map_or(PLATFORM_FEE_BPS, |schedule| schedule.fee_bps_for(sender_volume));
+
+        Ok(Self {
+            tier_bps,
+            mint_fee_config: MintFeeConfig::load(mint_fee_config)?,
+        })
+    }
+
+    /// Fee rate and platform fee for `amount` sent through `corridor`
+    pub fn resolve(&self, corridor: Option<&Corridor>, amount: u64) -> Result<(u16, u64)> {
+        let fee_bps = corridor.map_or(self.tier_bps, |corridor| corridor.fee_bps_or(self.tier_bps));
+        let platform_fee =
+            MintFeeConfig::platform_fee(self.mint_fee_config.as_ref(), amount, fee_bps)?;
+
+        Ok((fee_bps, platform_fee))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct FeeTier {
+    pub min_volume: u64, // 8 (windowed volume needed to reach this tier)
+    pub fee_bps: u16,    // 2
+}
+
+impl FeeTier {
+    pub const LEN: usize = 8 + 2;
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Invoice {
+    pub issuer: Pubkey,                // 32 (receiver of funds)
+    pub payer: Pubkey,                 // 32
+    pub mint: Pubkey,                  // 32
+    pub amount: u64,                   // Gross amount invoiced 8
+    pub amount_paid: u64,              // Gross amount paid so far 8
+    pub due_date: i64,                 // 8
+    pub reference_hash: [u8; 32],      // 32
+    pub status: InvoiceStatus,         // 1 + 1
+    pub last_transfer: Option<Pubkey>, // 1 + 32 (latest TransferRequest)
+    pub payment_count: u32,            // 4
+    pub created_at: i64,               // 8
+    pub paid_at: Option<i64>,          // 1 + 8
+    pub nonce: u64,                    // 8
+    pub bump: u8,                      // 1
+}
+
+impl Invoice {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 32 + 2 + 33 + 4 + 8 + 9 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum InvoiceStatus {
+    Open,
+    PartiallyPaid,
+    Overdue,
+    Paid,
+    PaidLate,
+}
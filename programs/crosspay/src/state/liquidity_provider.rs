@@ -1,19 +1,83 @@
 use anchor_lang::prelude::*;
 
+use super::{ComplianceReason, CurrencyCode, PayoutMethod, WithdrawalRequest};
+use crate::errors::CrossPayError;
+
 #[account]
 pub struct LiquidityProvider {
-    pub authority: Pubkey,           // 32
-    pub location: String,            // 4 + max 50 = 54
-    pub exchange_rate: u64,          // 8 (scaled by 10^6 for decimals)
-    pub available_liquidity: u64,    // 8
-    pub total_volume: u64,           // 8
-    pub completed_transactions: u64, // 8
-    pub trust_score: u16,            // 2 (out of 10000 for 2 decimals)
-    pub is_active: bool,             // 1
-    pub created_at: i64,             // 8
-    pub bump: u8,                    // 1
+    pub authority: Pubkey,               // 32
+    pub location: String,                // 4 + max 50 = 54
+    pub exchange_rate: u64,              // 8 (scaled by 10^6 for decimals)
+    pub available_liquidity: u64,        // 8
+    pub total_volume: u64,               // 8
+    pub completed_transactions: u64,     // 8
+    pub trust_score: u16,                // 2 (out of 10000 for 2 decimals)
+    pub is_active: bool,                 // 1
+    pub created_at: i64,                 // 8
+    pub bump: u8,                        // 1
+    pub is_frozen: bool,                 // 1
+    pub freeze_reason: ComplianceReason, // 1
+    pub currency: CurrencyCode,          // 3 (ISO 4217 payout currency)
+    pub fee_bps: u16,                    // 2 (charged on the withdrawal amount)
+    pub fixed_fee: u64,                  // 8 (flat fee per withdrawal, token units)
+    pub payout_methods: u8,              // 1 (bitmask of PayoutMethod::mask)
+    pub reserved_liquidity: u64,         // 8 (held by pending withdrawal fills)
 }
 
 impl LiquidityProvider {
-    pub const LEN: usize = 8 + 32 + 54 + 8 + 8 + 8 + 8 + 2 + 1 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 54 + 8 + 8 + 8 + 8 + 2 + 1 + 8 + 1 + 1 + 1 + CurrencyCode::LEN + 2 + 8 + 1 + 8;
+
+    /// Whether the provider may currently fill `withdrawal`
+    pub fn can_fill(&self, withdrawal: &WithdrawalRequest) -> bool {
+        self.is_active
+            && !self.is_frozen
+            && self.currency == withdrawal.currency
+            && self.free_liquidity() >= withdrawal.amount
+            && self.trust_score >= withdrawal.min_trust_score
+            && self.supports(withdrawal.payout_method)
+    }
+
+    /// Liquidity not already promised to pending withdrawal fills
+    pub fn free_liquidity(&self) -> u64 {
+        self.available_liquidity.saturating_sub(self.reserved_liquidity)
+    }
+
+    /// Hold `amount` of free liquidity for a pending fill
+    pub fn reserve(&mut self, amount: u64) -> Result<()> {
+        require!(
+            self.free_liquidity() >= amount,
+            CrossPayError::InsufficientLiquidity
+        );
+        self.reserved_liquidity = self
+            .reserved_liquidity
+            .checked_add(amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// Release a pending fill's reservation once it completes or is cancelled
+    pub fn release_reserved(&mut self, amount: u64) {
+        self.reserved_liquidity = self.reserved_liquidity.saturating_sub(amount);
+    }
+
+    /// Record a completed withdrawal (or fill) of `amount` tokens
+    pub fn record_completed(&mut self, amount: u64) -> Result<()> {
+        self.total_volume = self
+            .total_volume
+            .checked_add(amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        self.completed_transactions = self
+            .completed_transactions
+            .checked_add(1)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        self.available_liquidity = self.available_liquidity.saturating_sub(amount);
+
+        Ok(())
+    }
+
+    /// Whether the provider pays out through `method`
+    pub fn supports(&self, method: PayoutMethod) -> bool {
+        self.payout_methods & method.mask() != 0
+    }
 }
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::CrossPayError;
+
+/// Fee floor, ceiling and rounding for transfers in one mint
+#[account]
+pub struct MintFeeConfig {
+    pub mint: Pubkey,          // 32
+    pub min_fee: u64,          // 8 (in mint base units)
+    pub max_fee: u64,          // 8 (0 means uncapped)
+    pub rounding: FeeRounding, // 1
+    pub bump: u8,              // 1
+}
+
+impl MintFeeConfig {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1 + 1;
+
+    /// Read the fee config stored at `info`, if one has been configured
+    pub fn load(info: &AccountInfo) -> Result<Option<MintFeeConfig>> {
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, CrossPayError::InvalidMintFeeConfig);
+
+        let data = info.try_borrow_data()?;
+        Ok(Some(MintFeeConfig::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Platform fee for `amount` at `fee_bps` under an optional mint config
+    /// Without a config the default rounding applies and there are no bounds.
+    pub fn platform_fee(config: Option<&Self>, amount: u64, fee_bps: u16) -> Result<u64> {
+        match config {
+            Some(config) => calculate_bounded_fee(
+                amount,
+                fee_bps,
+                config.rounding,
+                config.min_fee,
+                config.max_fee,
+            ),
+            None => calculate_fee_at_bps(amount, fee_bps, DEFAULT_FEE_ROUNDING),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FeeRounding {
+    Down,
+    Up,
+    HalfUp,
+}
@@ -1,9 +1,47 @@
+pub mod batch_summary;
+pub mod claim_commitment;
+pub mod claimable_transfer;
+pub mod compliance;
+pub mod contract;
+pub mod corridor;
+pub mod currency_code;
+pub mod denied_address;
+pub mod fee_schedule;
+pub mod invoice;
 pub mod liquidity_provider;
+pub mod mint_fee_config;
+pub mod payment_schedule;
+pub mod payment_stream;
+pub mod platform_config;
+pub mod quote;
+pub mod reference_rate;
+pub mod referral_rewards;
+pub mod transfer_refund;
 pub mod transfer_request;
 pub mod user_profile;
+pub mod withdrawal_fill;
 pub mod withdrawal_request;
 
+pub use batch_summary::*;
+pub use claim_commitment::*;
+pub use claimable_transfer::*;
+pub use compliance::*;
+pub use contract::*;
+pub use corridor::*;
+pub use currency_code::*;
+pub use denied_address::*;
+pub use fee_schedule::*;
+pub use invoice::*;
 pub use liquidity_provider::*;
+pub use mint_fee_config::*;
+pub use payment_schedule::*;
+pub use payment_stream::*;
+pub use platform_config::*;
+pub use quote::*;
+pub use reference_rate::*;
+pub use referral_rewards::*;
+pub use transfer_refund::*;
 pub use transfer_request::*;
 pub use user_profile::*;
+pub use withdrawal_fill::*;
 pub use withdrawal_request::*;
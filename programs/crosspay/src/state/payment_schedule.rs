@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use super::TravelRuleRecord;
+
+#[account]
+pub struct PaymentSchedule {
+    pub sender: Pubkey,                        // 32
+    pub receiver: Pubkey,                      // 32
+    pub mint: Pubkey,                          // 32
+    pub amount: u64,                           // Gross amount per payment 8
+    pub net_amount: u64,                       // Amount after fee per payment 8
+    pub platform_fee: u64,                     // Fee charged per payment 8
+    pub referral_fee: u64,                     // Share of the fee paid to the referrer 8
+    pub interval: i64,                         // Seconds between payments 8
+    pub next_due: i64,                         // 8
+    pub remaining_payments: u32,               // 4
+    pub payments_made: u32,                    // 4
+    pub created_at: i64,                       // 8
+    pub nonce: u64,                            // 8
+    pub bump: u8,                              // 1
+    pub travel_rule: Option<TravelRuleRecord>, // 1 + TravelRuleRecord::LEN
+}
+
+impl PaymentSchedule {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 4 + 8 + 8 + 1
+        + 1 + TravelRuleRecord::LEN;
+
+    /// Allowance each payment draws from the sender: the gross amount, as the
+    /// receiver, the referrer and the treasury are all paid by the delegate
+    pub fn allowance_per_payment(&self) -> u64 {
+        self.amount
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use super::TravelRuleRecord;
+
+#[account]
+pub struct PaymentStream {
+    pub sender: Pubkey,                        // 32
+    pub receiver: Pubkey,                      // 32
+    pub mint: Pubkey,                          // 32
+    pub deposited_amount: u64,                 // Gross amount funded into escrow 8
+    pub withdrawn_amount: u64,                 // Gross amount released to receiver 8
+    pub refunded_amount: u64,                  // Unvested amount returned on cancel 8
+    pub platform_fee: u64,                     // Fee on the full deposit, charged pro rata 8
+    pub start_time: i64,                       // 8
+    pub end_time: i64,                         // 8
+    pub status: StreamStatus,                  // 1 + 1
+    pub created_at: i64,                       // 8
+    pub nonce: u64,                            // 8
+    pub bump: u8,                              // 1
+    pub travel_rule: Option<TravelRuleRecord>, // 1 + TravelRuleRecord::LEN
+}
+
+impl PaymentStream {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + 8 + 8 + 1
+        + 1 + TravelRuleRecord::LEN;
+
+    /// Share of the platform fee due once `released` of the deposit has been withdrawn
+    pub fn fee_due(&self, released: u64) -> u64 {
+        if self.deposited_amount == 0 {
+            return 0;
+        }
+        // released <= deposited_amount, so the result always fits back into a u64
+        ((self.platform_fee as u128 * released as u128) / self.deposited_amount as u128) as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum StreamStatus {
+    Active,
+    Cancelled,
+    Completed,
+}
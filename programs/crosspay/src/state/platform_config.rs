@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use super::TravelRuleRecord;
+use crate::errors::CrossPayError;
+
+#[account]
+pub struct PlatformConfig {
+    pub admin: Pubkey,                 // 32
+    pub large_transfer_threshold: u64, // 8 (0 disables the hold)
+    pub hold_period: i64,              // 8 (seconds)
+    pub bump: u8,                      // 1
+    pub travel_rule_threshold: u64,    // 8 (0 disables the requirement)
+    pub referral_share_bps: u16,       // 2 (share of platform_fee paid to referrers)
+    pub offramp_fee_bps: u16,          // 2 (platform fee on withdrawals)
+}
+
+impl PlatformConfig {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1 + 8 + 2 + 2;
+
+    /// Whether a transfer of `amount` must sit in the holding escrow
+    pub fn requires_hold(&self, amount: u64) -> bool {
+        self.large_transfer_threshold > 0 && amount >= self.large_transfer_threshold
+    }
+
+    /// Reject `amount` on a path that pays out without the holding escrow
+    /// Only confirm_transfer can hold funds for the cool-off, so batches, splits,
+    /// invoices, schedules, streams, contracts and claimables are capped below the
+    /// hold threshold and larger payments must be sent as a regular transfer.
+    pub fn check_unheld(&self, amount: u64) -> Result<()> {
+        require!(!self.requires_hold(amount), CrossPayError::HoldRequired);
+
+        Ok(())
+    }
+
+    /// Whether a transfer of `amount` must carry travel-rule data
+    pub fn requires_travel_rule(&self, amount: u64) -> bool {
+        self.travel_rule_threshold > 0 && amount >= self.travel_rule_threshold
+    }
+
+    /// Reject moving `amount` without travel-rule data when it is required
+    pub fn check_travel_rule(
+        &self,
+        amount: u64,
+        travel_rule: Option<&TravelRuleRecord>,
+    ) -> Result<()> {
+        require!(
+            !self.requires_travel_rule(amount) || travel_rule.is_some(),
+            CrossPayError::TravelRuleDataRequired
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+/// A liquidity provider's offer to fill a withdrawal during its quote window
+#[account]
+pub struct Quote {
+    pub withdrawal_request: Pubkey, // 32
+    pub provider: Pubkey,           // 32 (liquidity provider authority)
+    pub exchange_rate: u64,         // 8 (scaled by 10^6 for decimals)
+    pub fee_bps: u16,               // 2
+    pub fixed_fee: u64,             // 8
+    pub created_at: i64,            // 8
+    pub bump: u8,                   // 1
+    pub ranked: bool,               // 1 (counted by select_best_quote)
+}
+
+impl Quote {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 2 + 8 + 8 + 1 + 1;
+}
+
+/// Terms of the best eligible quote ranked so far after the window closed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BestQuote {
+    pub provider: Pubkey,   // 32
+    pub exchange_rate: u64, // 8
+    pub fee_bps: u16,       // 2
+    pub fixed_fee: u64,     // 8
+    pub payout: u128,       // 16 (unscaled local-currency payout)
+}
+
+impl BestQuote {
+    pub const LEN: usize = 32 + 8 + 2 + 8 + 16;
+}
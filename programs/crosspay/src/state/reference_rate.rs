@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use super::CurrencyCode;
+use crate::constants::{rate_within_band, REFERENCE_BASE_CURRENCY};
+use crate::errors::CrossPayError;
+
+/// Oracle-fed reference exchange rate for one currency pair
+#[account]
+pub struct ReferenceRate {
+    pub base: CurrencyCode,     // 3
+    pub quote: CurrencyCode,    // 3
+    pub oracle: Pubkey,         // 32 (key allowed to post rates)
+    pub rate: u64,              // 8 (scaled by 10^6, 0 until first posted)
+    pub updated_at: i64,        // 8
+    pub max_deviation_bps: u16, // 2 (allowed distance of provider rates)
+    pub max_age: i64,           // 8 (seconds before the rate is stale)
+    pub bump: u8,               // 1
+}
+
+impl ReferenceRate {
+    pub const LEN: usize = 8 + CurrencyCode::LEN + CurrencyCode::LEN + 32 + 8 + 8 + 2 + 8 + 1;
+
+    /// Address of the reference rate quoting `currency` against the base currency
+    pub fn address(currency: &CurrencyCode) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"reference_rate",
+                REFERENCE_BASE_CURRENCY.as_ref(),
+                currency.code.as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Read the reference rate for `currency`, if one has been configured
+    /// `info` must be the pair's PDA; pairs without a reference rate are not guarded.
+    pub fn load_pair(info: &AccountInfo, currency: &CurrencyCode) -> Result<Option<ReferenceRate>> {
+        require_keys_eq!(
+            info.key(),
+            ReferenceRate::address(currency),
+            CrossPayError::InvalidReferenceRate
+        );
+        if info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*info.owner, crate::ID, CrossPayError::InvalidReferenceRate);
+
+        let data = info.try_borrow_data()?;
+        Ok(Some(ReferenceRate::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Check a provider's `exchange_rate` against this reference at `now`
+    pub fn check_rate(&self, exchange_rate: u64, now: i64) -> Result<()> {
+        require!(
+            self.rate > 0 && now.saturating_sub(self.updated_at) <= self.max_age,
+            CrossPayError::StaleReferenceRate
+        );
+        require!(
+            rate_within_band(exchange_rate, self.rate, self.max_deviation_bps),
+            CrossPayError::RateOutsideReferenceBand
+        );
+
+        Ok(())
+    }
+}
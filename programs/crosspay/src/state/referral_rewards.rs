@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use super::FeeRounding;
+use crate::constants::calculate_fee_at_bps;
+use crate::errors::CrossPayError;
+
+/// Referral rewards earned by one referrer in one mint
+#[account]
+pub struct ReferralRewards {
+    pub referrer: Pubkey,  // 32
+    pub mint: Pubkey,      // 32
+    pub claimable: u64,    // 8
+    pub total_earned: u64, // 8
+    pub bump: u8,          // 1
+}
+
+impl ReferralRewards {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+
+    /// PDA of a referrer's rewards balance in a mint
+    pub fn address(referrer: &Pubkey, mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"referral_rewards", referrer.as_ref(), mint.as_ref()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Referral share of `platform_fee` owed to a receiver's `referrer`
+    /// Zero without a referrer, and when the referrer has not opened a balance
+    /// in `mint` at `info` - a missing balance must never block the payment.
+    pub fn share_of(
+        info: Option<&AccountInfo>,
+        referrer: Option<Pubkey>,
+        mint: &Pubkey,
+        platform_fee: u64,
+        share_bps: u16,
+    ) -> Result<u64> {
+        let Some(referrer) = referrer else {
+            return Ok(0);
+        };
+        let info = info.ok_or(CrossPayError::ReferralAccountsRequired)?;
+        require_keys_eq!(
+            info.key(),
+            ReferralRewards::address(&referrer, mint),
+            CrossPayError::ReferralAccountsRequired
+        );
+        if info.data_is_empty() {
+            return Ok(0);
+        }
+
+        calculate_fee_at_bps(platform_fee, share_bps, FeeRounding::Down)
+    }
+
+    /// Add `reward` to the balance stored at `info`, already checked by `share_of`
+    pub fn credit(info: &AccountInfo, reward: u64) -> Result<()> {
+        require_keys_eq!(*info.owner, crate::ID, CrossPayError::ReferralAccountsRequired);
+
+        let mut rewards = {
+            let data = info.try_borrow_data()?;
+            ReferralRewards::try_deserialize(&mut &data[..])?
+        };
+        rewards.claimable = rewards
+            .claimable
+            .checked_add(reward)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        rewards.total_earned = rewards
+            .total_earned
+            .checked_add(reward)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        let mut data = info.try_borrow_mut_data()?;
+        rewards.try_serialize(&mut &mut data[..])
+    }
+
+    /// Take back `reward` from the unclaimed balance stored at `info`
+    pub fn debit(info: &AccountInfo, reward: u64) -> Result<()> {
+        require_keys_eq!(*info.owner, crate::ID, CrossPayError::ReferralAccountsRequired);
+
+        let mut rewards = {
+            let data = info.try_borrow_data()?;
+            ReferralRewards::try_deserialize(&mut &data[..])?
+        };
+        rewards.claimable = rewards
+            .claimable
+            .checked_sub(reward)
+            .ok_or(CrossPayError::ReferralRewardClaimed)?;
+        rewards.total_earned = rewards
+            .total_earned
+            .checked_sub(reward)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        let mut data = info.try_borrow_mut_data()?;
+        rewards.try_serialize(&mut &mut data[..])
+    }
+}
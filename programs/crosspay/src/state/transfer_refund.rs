@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct TransferRefund {
+    pub transfer_request: Pubkey, // 32 (original TransferRequest)
+    pub sender: Pubkey,           // 32 (original sender, receives the refund)
+    pub receiver: Pubkey,         // 32 (original receiver, pays the refund)
+    pub mint: Pubkey,             // 32
+    pub amount: u64,              // Tokens returned to sender 8
+    pub fee_refunded: u64,        // Referral share of the fee returned to sender 8
+    pub index: u16,               // 2 (position among the transfer's refunds)
+    pub created_at: i64,          // 8
+    pub bump: u8,                 // 1
+}
+
+impl TransferRefund {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 2 + 8 + 1;
+}
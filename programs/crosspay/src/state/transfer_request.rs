@@ -1,22 +1,109 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{MAX_SPLIT_RECEIVERS, MAX_TRAVEL_RULE_URI_LEN};
+use crate::errors::CrossPayError;
+
 #[account]
 pub struct TransferRequest {
-    pub sender: Pubkey,            // 32
-    pub receiver: Pubkey,          // 32
-    pub amount: u64,               // Gross amount 8
-    pub net_amount: u64,           // Amount after fee 8
-    pub platform_fee: u64,         // Fee charged 8
-    pub mint: Pubkey,              // 32 (stablecoin mint address)
-    pub status: TransferStatus,    // 1 + 1
-    pub created_at: i64,           // 8
-    pub completed_at: Option<i64>, // 1 + 8
-    pub nonce: u64,                // 8
-    pub bump: u8,                  // 1
+    pub sender: Pubkey,                        // 32
+    pub receiver: Pubkey,                      // 32
+    pub amount: u64,                           // Gross amount 8
+    pub net_amount: u64,                       // Amount after fee 8
+    pub platform_fee: u64,                     // Fee charged 8
+    pub mint: Pubkey,                          // 32 (stablecoin mint address)
+    pub status: TransferStatus,                // 1 + 1
+    pub created_at: i64,                       // 8
+    pub completed_at: Option<i64>,             // 1 + 8
+    pub nonce: u64,                            // 8
+    pub bump: u8,                              // 1
+    pub splits: Vec<SplitShare>,               // SPLITS_LEN (empty for single-receiver transfers)
+    pub release_at: Option<i64>,               // 1 + 8 (set while held in escrow)
+    pub travel_rule: Option<TravelRuleRecord>, // TRAVEL_RULE_LEN
+    pub fee_bps: u16,                          // 2 (platform fee rate locked in at initiation)
+    pub fee_bearer: FeeBearer,                 // 1
+    pub referral_reward: u64,                  // Share of the fee paid to the receiver's referrer 8
+    pub refunded_amount: u64,                  // Net amount returned to the sender so far 8
+    pub refund_count: u16,                     // 2 (seeds the next TransferRefund)
 }
 
 impl TransferRequest {
-    pub const LEN: usize = 8 + 32 + 32 + 8 + 32 + 2 + 8 + 8 + 8 + 9 + 8 + 1;
+    pub const SPLITS_LEN: usize = 4 + MAX_SPLIT_RECEIVERS * SplitShare::LEN;
+    pub const TRAVEL_RULE_LEN: usize = 1 + TravelRuleRecord::LEN;
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 32 + 2 + 8 + 8 + 8 + 9 + 8 + 1
+        + Self::SPLITS_LEN + 9 + Self::TRAVEL_RULE_LEN + 2 + 1 + 8 + 8 + 2;
+
+    /// Total taken from the sender: the gross amount, plus the fee on top
+    /// when the sender bears it
+    pub fn sender_debit(&self) -> Option<u64> {
+        match self.fee_bearer {
+            FeeBearer::Receiver => Some(self.amount),
+            FeeBearer::Sender => self.amount.checked_add(self.platform_fee),
+        }
+    }
+}
+
+/// Commitment to originator and beneficiary data kept off-chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct TravelRuleRecord {
+    pub data_hash: [u8; 32], // 32 (hash of the plaintext travel-rule payload)
+    pub blob_uri: String,    // 4 + max 128 (pointer to the encrypted payload)
+}
+
+impl TravelRuleRecord {
+    pub const LEN: usize = 32 + 4 + MAX_TRAVEL_RULE_URI_LEN;
+
+    /// Check the record commits to a payload and points at its encrypted copy
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.data_hash != [0; 32]
+                && !self.blob_uri.is_empty()
+                && self.blob_uri.len() <= MAX_TRAVEL_RULE_URI_LEN,
+            CrossPayError::InvalidTravelRuleData
+        );
+
+        Ok(())
+    }
+}
+
+/// One receiver's share of a split transfer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct SplitShare {
+    pub receiver: Pubkey, // 32
+    pub share_bps: u16,   // 2 (out of 10000)
+}
+
+impl SplitShare {
+    pub const LEN: usize = 32 + 2;
+}
+
+/// Which side of a transfer pays the platform fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeeBearer {
+    /// Fee is deducted from `amount`, so the receiver gets `amount - fee`
+    #[default]
+    Receiver,
+    /// Fee is charged on top, so the receiver gets exactly `amount`
+    Sender,
+}
+
+impl FeeBearer {
+    /// Net amount received and total taken from the sender for `amount`
+    pub fn price(self, amount: u64, platform_fee: u64) -> Result<(u64, u64)> {
+        match self {
+            FeeBearer::Receiver => {
+                let net_amount = amount
+                    .checked_sub(platform_fee)
+                    .ok_or(CrossPayError::ArithmeticOverflow)?;
+                Ok((net_amount, amount))
+            }
+            FeeBearer::Sender => {
+                let sender_debit = amount
+                    .checked_add(platform_fee)
+                    .ok_or(CrossPayError::ArithmeticOverflow)?;
+                Ok((amount, sender_debit))
+            }
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -26,4 +113,8 @@ pub enum TransferStatus {
     Completed,
     Failed,
     Cancelled,
+    Refunded,
+    Held,
+    Disputed,
+    UnderReview,
 }
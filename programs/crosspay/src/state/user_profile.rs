@@ -1,32 +1,102 @@
 use anchor_lang::prelude::*;
 
+use super::ComplianceReason;
+use crate::constants::ROLLING_VOLUME_WINDOW;
+use crate::errors::CrossPayError;
+
 #[account]
 #[derive(Default)]
 pub struct UserProfile {
-    pub authority: Pubkey,    // 32
-    pub role: UserRole,       // 1 + 1 (enum discriminator)
-    pub kyc_verified: bool,   // 1
-    pub kyc_hash: [u8; 32],   // 32
-    pub country_code: String, // 4 + max 3 = 7
-    pub created_at: i64,      // 8
-    pub total_sent: u64,      // 8
-    pub total_received: u64,  // 8
-    pub bump: u8,             // 1
+    pub authority: Pubkey,               // 32
+    pub role: UserRole,                  // 1 + 1 (enum discriminator)
+    pub kyc_verified: bool,              // 1
+    pub kyc_hash: [u8; 32],              // 32
+    pub country_code: String,            // 4 + max 3 = 7
+    pub created_at: i64,                 // 8
+    pub total_sent: u64,                 // 8
+    pub total_received: u64,             // 8
+    pub bump: u8,                        // 1
+    pub is_frozen: bool,                 // 1
+    pub freeze_reason: ComplianceReason, // 1
+    pub volume_window_start: i64,        // 8
+    pub window_volume: u64,              // 8 (gross amount sent since volume_window_start)
+    pub referrer: Option<Pubkey>,        // 1 + 32
+    pub transfer_nonce: u64,             // 8 (seeds the next TransferRequest / BatchSummary)
+    pub withdrawal_nonce: u64,           // 8 (seeds the next WithdrawalRequest)
 }
 
 impl UserProfile {
-    pub const LEN: usize = 8 + 32 + 2 + 1 + 32 + 7 + 8 + 8 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 2 + 1 + 32 + 7 + 8 + 8 + 8 + 1 + 1 + 1 + 8 + 8 + 33 + 8 + 8;
+
+    /// Volume sent in the current window, or 0 once the window has lapsed
+    pub fn volume_at(&self, now: i64) -> u64 {
+        if now
+            < self
+                .volume_window_start
+                .saturating_add(ROLLING_VOLUME_WINDOW)
+        {
+            self.window_volume
+        } else {
+            0
+        }
+    }
+
+    /// Count `amount` towards both lifetime and windowed sent volume
+    pub fn record_sent(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.total_sent = self
+            .total_sent
+            .checked_add(amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        let window_volume = self.volume_at(now);
+        if window_volume == 0 {
+            self.volume_window_start = now;
+        }
+        self.window_volume = window_volume
+            .checked_add(amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// Take the nonce for the next TransferRequest or BatchSummary
+    /// Unlike `total_sent`, this only ever moves forward, so seeds never repeat.
+    pub fn next_transfer_nonce(&mut self) -> Result<u64> {
+        let nonce = self.transfer_nonce;
+        self.transfer_nonce = nonce
+            .checked_add(1)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        Ok(nonce)
+    }
+
+    /// Take the nonce for the next WithdrawalRequest
+    pub fn next_withdrawal_nonce(&mut self) -> Result<u64> {
+        let nonce = self.withdrawal_nonce;
+        self.withdrawal_nonce = nonce
+            .checked_add(1)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+
+        Ok(nonce)
+    }
+
+    /// Reverse a previously recorded send (refunds and upheld fraud claims)
+    /// Only affects volume stats; transfer seeds use `transfer_nonce`.
+    pub fn reverse_sent(&mut self, amount: u64) -> Result<()> {
+        self.total_sent = self
+            .total_sent
+            .checked_sub(amount)
+            .ok_or(CrossPayError::ArithmeticOverflow)?;
+        self.window_volume = self.window_volume.saturating_sub(amount);
+
+        Ok(())
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum UserRole {
     Sender,
+    #[default]
     Receiver,
     Both,
 }
-
-impl Default for UserRole {
-    fn default() -> Self {
-        UserRole::Receiver
-    }
-}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+/// One provider's portion of a withdrawal filled by several providers
+#[account]
+pub struct WithdrawalFill {
+    pub withdrawal_request: Pubkey, // 32
+    pub index: u8,                  // 1
+    pub provider: Pubkey,           // 32 (liquidity provider authority)
+    pub amount: u64,                // 8
+    pub provider_fee: u64,          // 8
+    pub platform_fee: u64,          // 8
+    pub net_amount: u64,            // 8
+    pub exchange_rate: u64,         // 8
+    pub status: FillStatus,         // 1
+    pub created_at: i64,            // 8
+    pub completed_at: Option<i64>,  // 1 + 8
+    pub bump: u8,                   // 1
+    pub expires_at: i64,            // 8 (cancellable by anyone after this)
+}
+
+impl WithdrawalFill {
+    pub const LEN: usize = 8 + 32 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 9 + 1 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FillStatus {
+    Pending,
+    Completed,
+    Cancelled,
+}
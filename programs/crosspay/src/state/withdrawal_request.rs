@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use super::{BestQuote, CurrencyCode};
+use crate::constants::{calculate_fiat_payout, calculate_withdrawal_fees};
+
 #[account]
 pub struct WithdrawalRequest {
     pub freelancer: Pubkey,                // 32
@@ -12,10 +15,127 @@ pub struct WithdrawalRequest {
     pub completed_at: Option<i64>,         // 1 + 8
     pub nonce: u64,                        // 8
     pub bump: u8,                          // 1
+    pub currency: CurrencyCode,            // 3 (ISO 4217 payout currency)
+    pub provider_fee: u64,                 // 8 (kept by the provider)
+    pub platform_fee: u64,                 // 8 (off-ramp fee to the platform)
+    pub net_amount: u64,                   // 8 (converted to fiat for the freelancer)
+    pub exchange_rate: u64,                // 8 (rate locked at provider selection)
+    pub quote_deadline: i64,               // 8 (0 when the freelancer picks directly)
+    pub min_trust_score: u16,              // 2
+    pub quote_count: u32,                  // 4 (quotes currently open)
+    pub filled_amount: u64,                // 8 (portion allocated to fills)
+    pub fill_count: u8,                    // 1
+    pub completed_fills: u8,               // 1
+    pub lock_expires_at: i64,              // 8 (0 unless a provider accepted it)
+    pub min_rate: u64,                     // 8 (limit order target rate, 0 for none)
+    pub order_expires_at: i64,             // 8 (0 when the limit order never expires)
+    pub quotes_ranked: u32,                // 4 (quotes ranked since the window closed)
+    pub best_quote: Option<BestQuote>,     // 1 + BestQuote::LEN
+    pub cancelled_fills: u8,               // 1
+    pub last_locker: Option<Pubkey>,       // 1 + 32 (provider that accepted it last)
+    pub provider_accepts: bool,            // 1 (providers may accept it directly)
 }
 
 impl WithdrawalRequest {
-    pub const LEN: usize = 8 + 32 + 8 + 32 + 2 + 33 + 2 + 8 + 9 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 2 + 33 + 2 + 8 + 9 + 8 + 1 + CurrencyCode::LEN + 8 + 8 + 8 + 8 + 8 + 2 + 4 + 8 + 1 + 1 + 8 + 8 + 8 + 4 + 1 + BestQuote::LEN + 1 + 33 + 1;
+
+    /// Whether providers compete for this withdrawal through quotes
+    pub fn is_rfq(&self) -> bool {
+        self.quote_deadline != 0
+    }
+
+    /// Local-currency payout the freelancer would receive at the given rate and fees
+    pub fn payout_at(
+        &self,
+        exchange_rate: u64,
+        provider_fee_bps: u16,
+        provider_fixed_fee: u64,
+        offramp_fee_bps: u16,
+    ) -> Result<u128> {
+        let (provider_fee, platform_fee) = calculate_withdrawal_fees(
+            self.amount,
+            provider_fee_bps,
+            provider_fixed_fee,
+            offramp_fee_bps,
+        )?;
+
+        Ok(calculate_fiat_payout(
+            self.amount - provider_fee - platform_fee,
+            exchange_rate,
+        ))
+    }
+
+    /// Lock in the provider's rate and fees plus the platform's off-ramp fee
+    pub fn select_provider(
+        &mut self,
+        provider: Pubkey,
+        exchange_rate: u64,
+        provider_fee_bps: u16,
+        provider_fixed_fee: u64,
+        offramp_fee_bps: u16,
+    ) -> Result<()> {
+        let (provider_fee, platform_fee) = calculate_withdrawal_fees(
+            self.amount,
+            provider_fee_bps,
+            provider_fixed_fee,
+            offramp_fee_bps,
+        )?;
+
+        self.selected_provider = Some(provider);
+        self.status = WithdrawalStatus::ProviderSelected;
+        self.exchange_rate = exchange_rate;
+        self.provider_fee = provider_fee;
+        self.platform_fee = platform_fee;
+        self.net_amount = self.amount - provider_fee - platform_fee;
+        self.lock_expires_at = 0;
+
+        Ok(())
+    }
+
+    /// Whether the payout at the given rate and fees meets the freelancer's limit, if any
+    /// `min_rate` is an all-in rate: the fiat received must be at least
+    /// `amount * min_rate` once provider and off-ramp fees are taken out.
+    pub fn accepts_rate(
+        &self,
+        exchange_rate: u64,
+        provider_fee_bps: u16,
+        provider_fixed_fee: u64,
+        offramp_fee_bps: u16,
+    ) -> Result<bool> {
+        if self.min_rate == 0 {
+            return Ok(true);
+        }
+        let payout = self.payout_at(
+            exchange_rate,
+            provider_fee_bps,
+            provider_fixed_fee,
+            offramp_fee_bps,
+        )?;
+        Ok(payout >= calculate_fiat_payout(self.amount, self.min_rate))
+    }
+
+    /// Whether an unfilled limit order has passed its expiry
+    pub fn order_expired(&self, now: i64) -> bool {
+        self.order_expires_at != 0 && now >= self.order_expires_at
+    }
+
+    /// Whether a provider's acceptance lock has lapsed, reopening the withdrawal
+    pub fn lock_expired(&self, now: i64) -> bool {
+        self.status == WithdrawalStatus::ProviderSelected
+            && self.lock_expires_at != 0
+            && now >= self.lock_expires_at
+    }
+
+    /// Return the withdrawal to the open pool, dropping the selected provider
+    pub fn reopen(&mut self) {
+        self.selected_provider = None;
+        self.status = WithdrawalStatus::Pending;
+        self.exchange_rate = 0;
+        self.provider_fee = 0;
+        self.platform_fee = 0;
+        self.net_amount = self.amount;
+        self.lock_expires_at = 0;
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -25,6 +145,18 @@ pub enum PayoutMethod {
     Cash,
 }
 
+impl PayoutMethod {
+    /// Bit for this method in `LiquidityProvider::payout_methods`
+    pub fn mask(self) -> u8 {
+        1 << self as u8
+    }
+
+    /// Combine methods into a `LiquidityProvider::payout_methods` bitmask
+    pub fn mask_of(methods: &[PayoutMethod]) -> u8 {
+        methods.iter().fold(0, |mask, method| mask | method.mask())
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum WithdrawalStatus {
     Pending,
@@ -32,4 +164,7 @@ pub enum WithdrawalStatus {
     AwaitingConfirmation,
     Completed,
     Failed,
+    UnderReview,
+    PartiallyFilled,
+    Expired,
 }
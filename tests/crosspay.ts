@@ -8,9 +8,11 @@ import {
   createAccount,
   mintTo,
   getAccount,
+  revoke,
   TOKEN_2022_PROGRAM_ID,
 } from "@solana/spl-token";
 import { assert } from "chai";
+import { createHash, randomBytes } from "crypto";
 
 describe("crosspay", () => {
   const provider = anchor.AnchorProvider.env();
@@ -27,6 +29,15 @@ describe("crosspay", () => {
   let senderTokenAccount: PublicKey;
   let receiverTokenAccount: PublicKey;
   let lpTokenAccount: PublicKey;
+  let platformFeeAccount: PublicKey;
+
+  const PLATFORM_FEE_RECIPIENT = new PublicKey("3NPHMMM5dNde1ZV8VPkFQrd2TEPH6n9WTyPt3AyvfxdD");
+
+  // No USD/NGN reference rate is configured, so provider rates are unguarded
+  const [ngnReferenceRatePda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("reference_rate"), Buffer.from("USD"), Buffer.from("NGN")],
+    anchor.workspace.Crosspay.programId
+  );
 
   before(async () => {
     // Create test accounts
@@ -125,6 +136,13 @@ describe("crosspay", () => {
       liquidityProvider.publicKey
     );
 
+    platformFeeAccount = await createAccount(
+      provider.connection,
+      liquidityProvider,
+      usdcMint,
+      PLATFORM_FEE_RECIPIENT
+    );
+
     // Mint tokens to sender (1000 USDC)
     await mintTo(
       provider.connection,
@@ -194,6 +212,257 @@ describe("crosspay", () => {
     console.log("\n💰 All SOL returned to local wallet!");
   });
 
+  // Helpers for the flows below, which use fresh actors so the balances
+  // asserted in the summary are unaffected
+  const usdc = (amount: number) => new anchor.BN(Math.round(amount * 10 ** 6));
+  const NGN = { code: Array.from(Buffer.from("NGN")) };
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const profilePda = (user: PublicKey) => pda(Buffer.from("user_profile"), user.toBuffer());
+  const providerPda = (user: PublicKey) =>
+    pda(Buffer.from("liquidity_provider"), user.toBuffer());
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+    } catch (err) {
+      assert.include(err.toString(), code);
+      return;
+    }
+    assert.fail(`Expected the transaction to fail with ${code}`);
+  };
+
+  const chainTime = async () =>
+    provider.connection.getBlockTime(await provider.connection.getSlot());
+
+  const fundedKeypair = async () => {
+    const keypair = Keypair.generate();
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: keypair.publicKey,
+          lamports: 0.2 * anchor.web3.LAMPORTS_PER_SOL,
+        })
+      ),
+      [provider.wallet.payer]
+    );
+    return keypair;
+  };
+
+  const tokenAccountWith = async (owner: Keypair, amount: number) => {
+    const tokenAccount = await createAccount(provider.connection, owner, usdcMint, owner.publicKey);
+    if (amount > 0) {
+      await mintTo(provider.connection, mintAuthority, usdcMint, tokenAccount, mintAuthority, usdc(amount).toNumber());
+    }
+    return tokenAccount;
+  };
+
+  const balanceOf = async (tokenAccount: PublicKey) =>
+    Number((await getAccount(provider.connection, tokenAccount, undefined, TOKEN_PROGRAM_ID)).amount);
+
+  const newUser = async (
+    country: string,
+    balance: number,
+    options: { kyc?: boolean; referrer?: PublicKey } = {}
+  ) => {
+    const keypair = await fundedKeypair();
+    const profile = profilePda(keypair.publicKey);
+
+    await program.methods
+      .initializeUser({ both: {} }, country, options.referrer ?? null)
+      .accountsPartial({
+        userProfile: profile,
+        referrerProfile: options.referrer ? profilePda(options.referrer) : null,
+        authority: keypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([keypair])
+      .rpc();
+
+    if (options.kyc) {
+      await program.methods
+        .updateKycStatus(true, Array(32).fill(1))
+        .accountsPartial({ userProfile: profile, authority: keypair.publicKey })
+        .signers([keypair])
+        .rpc();
+    }
+
+    const tokenAccount = await tokenAccountWith(keypair, balance);
+    return { keypair, profile, tokenAccount };
+  };
+
+  const newProvider = async (
+    location: string,
+    exchangeRate: number,
+    feeBps: number,
+    fixedFee: number = 0
+  ) => {
+    const keypair = await fundedKeypair();
+    const account = providerPda(keypair.publicKey);
+
+    await program.methods
+      .registerLiquidityProvider(
+        location,
+        usdc(exchangeRate),
+        NGN,
+        feeBps,
+        usdc(fixedFee),
+        [{ mobileMoney: {} }, { bankTransfer: {} }]
+      )
+      .accountsPartial({
+        liquidityProvider: account,
+        referenceRate: ngnReferenceRatePda,
+        authority: keypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([keypair])
+      .rpc();
+
+    await program.methods
+      .updateProviderAvailability(usdc(1000), true)
+      .accountsPartial({ liquidityProvider: account, authority: keypair.publicKey })
+      .signers([keypair])
+      .rpc();
+
+    const tokenAccount = await tokenAccountWith(keypair, 0);
+    return { keypair, account, tokenAccount };
+  };
+
+  // A fresh freelancer has received nothing, so its first withdrawal uses nonce 0
+  const requestWithdrawal = async (
+    freelancer: Awaited<ReturnType<typeof newUser>>,
+    amount: number,
+    quoteWindow: number = 0
+  ) => {
+    const profile = await program.account.userProfile.fetch(freelancer.profile);
+    const withdrawalRequest = pda(
+      Buffer.from("withdrawal_request"),
+      freelancer.keypair.publicKey.toBuffer(),
+      profile.withdrawalNonce.toArrayLike(Buffer, "le", 8)
+    );
+
+    await program.methods
+      .requestWithdrawal(usdc(amount), { mobileMoney: {} }, NGN, new anchor.BN(quoteWindow), 0)
+      .accountsPartial({
+        freelancerProfile: freelancer.profile,
+        withdrawalRequest,
+        freelancerTokenAccount: freelancer.tokenAccount,
+        freelancer: freelancer.keypair.publicKey,
+        authority: freelancer.keypair.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([freelancer.keypair])
+      .rpc();
+
+    return withdrawalRequest;
+  };
+
+  const initiateTransfer = async (
+    payer: Awaited<ReturnType<typeof newUser>>,
+    payee: Awaited<ReturnType<typeof newUser>>,
+    amount: number,
+    feeBearer: object = { receiver: {} }
+  ) => {
+    const profile = await program.account.userProfile.fetch(payer.profile);
+    const transferRequest = pda(
+      Buffer.from("transfer_request"),
+      payer.keypair.publicKey.toBuffer(),
+      payee.keypair.publicKey.toBuffer(),
+      profile.transferNonce.toArrayLike(Buffer, "le", 8)
+    );
+
+    await program.methods
+      .initiateTransfer(usdc(amount), payee.keypair.publicKey, feeBearer as any)
+      .accountsPartial({
+        senderProfile: payer.profile,
+        transferRequest,
+        receiverProfile: payee.profile,
+        senderTokenAccount: payer.tokenAccount,
+        mint: usdcMint,
+        receiver: payee.keypair.publicKey,
+        sender: payer.keypair.publicKey,
+        authority: payer.keypair.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([payer.keypair])
+      .rpc();
+
+    return transferRequest;
+  };
+
+  // Confirms a transfer to a receiver without a referrer, below the hold threshold
+  const confirmTransfer = (
+    payer: Awaited<ReturnType<typeof newUser>>,
+    payee: Awaited<ReturnType<typeof newUser>>,
+    transferRequest: PublicKey
+  ) =>
+    program.methods
+      .confirmTransfer()
+      .accountsPartial({
+        transferRequest,
+        senderProfile: payer.profile,
+        receiverProfile: payee.profile,
+        senderTokenAccount: payer.tokenAccount,
+        receiverTokenAccount: payee.tokenAccount,
+        holdVault: null,
+        referralRewards: null,
+        referralVault: null,
+        sender: payer.keypair.publicKey,
+        authority: payer.keypair.publicKey,
+        platformFeeAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([payer.keypair])
+      .rpc();
+
+  const newComplianceOfficer = async () => {
+    const officer = await fundedKeypair();
+    await program.methods
+      .addComplianceOfficer(officer.publicKey)
+      .accountsPartial({
+        complianceOfficer: pda(Buffer.from("compliance_officer"), officer.publicKey.toBuffer()),
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return officer;
+  };
+
+  describe("Platform Configuration", () => {
+    it("Initializes the platform config", async () => {
+      const [platformConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_config")],
+        program.programId
+      );
+      const [programDataPda] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      );
+
+      // Threshold 0 disables the large-transfer hold for the flows below
+      await program.methods
+        .initializePlatformConfig(new anchor.BN(0), new anchor.BN(0))
+        .accountsPartial({
+          platformConfig: platformConfigPda,
+          program: program.programId,
+          programData: programDataPda,
+          authority: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const platformConfig = await program.account.platformConfig.fetch(platformConfigPda);
+      assert.equal(platformConfig.admin.toString(), provider.wallet.publicKey.toString());
+      assert.equal(platformConfig.largeTransferThreshold.toNumber(), 0);
+    });
+  });
+
   describe("User Management", () => {
     it("Initializes sender user profile", async () => {
       const [userProfilePda] = PublicKey.findProgramAddressSync(
@@ -202,9 +471,10 @@ describe("crosspay", () => {
       );
 
       await program.methods
-        .initializeUser({ sender: {} }, "USA")
+        .initializeUser({ sender: {} }, "USA", null)
         .accountsPartial({
           userProfile: userProfilePda,
+          referrerProfile: null,
           authority: sender.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -224,9 +494,10 @@ describe("crosspay", () => {
       );
 
       await program.methods
-        .initializeUser({ receiver: {} }, "NGA")
+        .initializeUser({ receiver: {} }, "NGA", null)
         .accountsPartial({
           userProfile: userProfilePda,
+          referrerProfile: null,
           authority: receiver.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -269,12 +540,22 @@ describe("crosspay", () => {
 
       const senderProfile = await program.account.userProfile.fetch(senderProfilePda);
 
+      const [receiverProfilePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), receiver.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const [corridorPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("corridor"), Buffer.from("USA"), Buffer.from("NGA")],
+        program.programId
+      );
+
       const [transferRequestPda] = PublicKey.findProgramAddressSync(
         [
           Buffer.from("transfer_request"),
           sender.publicKey.toBuffer(),
           receiver.publicKey.toBuffer(),
-          senderProfile.totalSent.toArrayLike(Buffer, "le", 8),
+          senderProfile.transferNonce.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -282,10 +563,12 @@ describe("crosspay", () => {
       const amount = new anchor.BN(100 * 10 ** 6); // 100 USDC
 
       await program.methods
-        .initiateTransfer(amount, receiver.publicKey)
+        .initiateTransfer(amount, receiver.publicKey, { receiver: {} })
         .accountsPartial({
           senderProfile: senderProfilePda,
           transferRequest: transferRequestPda,
+          receiverProfile: receiverProfilePda,
+          corridor: corridorPda,
           senderTokenAccount: senderTokenAccount,
           mint: usdcMint,
           receiver: receiver.publicKey,
@@ -324,6 +607,11 @@ describe("crosspay", () => {
         program.programId
       );
 
+      const [corridorPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("corridor"), Buffer.from("USA"), Buffer.from("NGA")],
+        program.programId
+      );
+
       const balanceBefore = await getAccount(
         provider.connection,
         receiverTokenAccount,
@@ -337,10 +625,15 @@ describe("crosspay", () => {
           transferRequest: transferRequestPda,
           senderProfile: senderProfilePda,
           receiverProfile: receiverProfilePda,
+          corridor: corridorPda,
           senderTokenAccount: senderTokenAccount,
           receiverTokenAccount: receiverTokenAccount,
+          holdVault: null,
+          referralRewards: null,
+          referralVault: null,
           sender: sender.publicKey,
           authority: sender.publicKey,
+          platformFeeAccount,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([sender])
@@ -373,9 +666,17 @@ describe("crosspay", () => {
       );
 
       await program.methods
-        .registerLiquidityProvider("Lagos, Nigeria", new anchor.BN(1500 * 10 ** 6)) // 1 USDC = 1500 NGN
+        .registerLiquidityProvider(
+          "Lagos, Nigeria",
+          new anchor.BN(1500 * 10 ** 6), // 1 USDC = 1500 NGN
+          { code: Array.from(Buffer.from("NGN")) },
+          100, // 1% provider fee
+          new anchor.BN(0),
+          [{ mobileMoney: {} }, { bankTransfer: {} }]
+        )
         .accountsPartial({
           liquidityProvider: lpPda,
+          referenceRate: ngnReferenceRatePda,
           authority: liquidityProvider.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -424,7 +725,7 @@ describe("crosspay", () => {
         [
           Buffer.from("withdrawal_request"),
           receiver.publicKey.toBuffer(),
-          receiverProfile.totalReceived.toArrayLike(Buffer, "le", 8),
+          receiverProfile.withdrawalNonce.toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -432,7 +733,13 @@ describe("crosspay", () => {
       const amount = new anchor.BN(50 * 10 ** 6); // 50 USDC
 
       await program.methods
-        .requestWithdrawal(amount, { mobileMoney: {} })
+        .requestWithdrawal(
+          amount,
+          { mobileMoney: {} },
+          { code: Array.from(Buffer.from("NGN")) },
+          new anchor.BN(0), // no quote window - pick the provider directly
+          0
+        )
         .accountsPartial({
           freelancerProfile: receiverProfilePda,
           withdrawalRequest: withdrawalRequestPda,
@@ -462,7 +769,8 @@ describe("crosspay", () => {
         [
           Buffer.from("withdrawal_request"),
           receiver.publicKey.toBuffer(),
-          receiverProfile.totalReceived.toArrayLike(Buffer, "le", 8),
+          // The request took the nonce before the current one
+          receiverProfile.withdrawalNonce.subn(1).toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -473,10 +781,11 @@ describe("crosspay", () => {
       );
 
       await program.methods
-        .selectProvider(liquidityProvider.publicKey)
+        .selectProvider(liquidityProvider.publicKey, new anchor.BN(0))
         .accountsPartial({
           withdrawalRequest: withdrawalRequestPda,
           liquidityProvider: lpPda,
+          referenceRate: ngnReferenceRatePda,
           freelancer: receiver.publicKey,
         })
         .signers([receiver])
@@ -488,6 +797,8 @@ describe("crosspay", () => {
         liquidityProvider.publicKey.toString()
       );
       assert.deepEqual(withdrawalRequest.status, { providerSelected: {} });
+      assert.equal(withdrawalRequest.providerFee.toNumber(), 0.5 * 10 ** 6);
+      assert.equal(withdrawalRequest.netAmount.toNumber(), 49.5 * 10 ** 6);
     });
 
     it("Finalizes the withdrawal - 50 USDC to LP", async () => {
@@ -502,7 +813,8 @@ describe("crosspay", () => {
         [
           Buffer.from("withdrawal_request"),
           receiver.publicKey.toBuffer(),
-          receiverProfile.totalReceived.toArrayLike(Buffer, "le", 8),
+          // The request took the nonce before the current one
+          receiverProfile.withdrawalNonce.subn(1).toArrayLike(Buffer, "le", 8),
         ],
         program.programId
       );
@@ -526,6 +838,7 @@ describe("crosspay", () => {
           liquidityProvider: lpPda,
           freelancerTokenAccount: receiverTokenAccount,
           providerTokenAccount: lpTokenAccount,
+          platformFeeAccount,
           freelancer: receiver.publicKey,
           providerAuthority: liquidityProvider.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -552,6 +865,2115 @@ describe("crosspay", () => {
     });
   });
 
+  describe("Fee Bearer", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let payee: Awaited<ReturnType<typeof newUser>>;
+
+    const transferPda = async () => {
+      const profile = await program.account.userProfile.fetch(payer.profile);
+      return pda(
+        Buffer.from("transfer_request"),
+        payer.keypair.publicKey.toBuffer(),
+        payee.keypair.publicKey.toBuffer(),
+        profile.transferNonce.toArrayLike(Buffer, "le", 8)
+      );
+    };
+
+    const initiate = async (amount: number, feeBearer: object) => {
+      const transferRequest = await transferPda();
+      await program.methods
+        .initiateTransfer(usdc(amount), payee.keypair.publicKey, feeBearer as any)
+        .accountsPartial({
+          senderProfile: payer.profile,
+          transferRequest,
+          receiverProfile: payee.profile,
+          senderTokenAccount: payer.tokenAccount,
+          mint: usdcMint,
+          receiver: payee.keypair.publicKey,
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+      return transferRequest;
+    };
+
+    before(async () => {
+      payer = await newUser("USA", 20, { kyc: true });
+      payee = await newUser("NGA", 0);
+    });
+
+    it("Rejects a sender-pays transfer the sender can't cover with the fee on top", async () => {
+      // 20 USDC plus the 0.5% fee is more than the sender holds
+      await expectError(initiate(20, { sender: {} }), "InsufficientBalance");
+    });
+
+    it("Delivers the full amount when the sender bears the fee", async () => {
+      const transferRequest = await initiate(10, { sender: {} });
+
+      const request = await program.account.transferRequest.fetch(transferRequest);
+      assert.equal(request.netAmount.toNumber(), usdc(10).toNumber());
+      assert.equal(request.platformFee.toNumber(), usdc(0.05).toNumber());
+      assert.deepEqual(request.feeBearer, { sender: {} });
+
+      const balanceBefore = await balanceOf(payee.tokenAccount);
+      await program.methods
+        .confirmTransfer()
+        .accountsPartial({
+          transferRequest,
+          senderProfile: payer.profile,
+          receiverProfile: payee.profile,
+          senderTokenAccount: payer.tokenAccount,
+          receiverTokenAccount: payee.tokenAccount,
+          holdVault: null,
+          referralRewards: null,
+          referralVault: null,
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          platformFeeAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+
+      assert.equal((await balanceOf(payee.tokenAccount)) - balanceBefore, usdc(10).toNumber());
+      const senderProfile = await program.account.userProfile.fetch(payer.profile);
+      assert.equal(senderProfile.totalSent.toNumber(), usdc(10.05).toNumber());
+    });
+  });
+
+  describe("Referral Rewards", () => {
+    let referrer: Awaited<ReturnType<typeof newUser>>;
+    let referee: Awaited<ReturnType<typeof newUser>>;
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let transferRequest: PublicKey;
+
+    const referralVault = () => pda(Buffer.from("referral_vault"), usdcMint.toBuffer());
+    const referralRewards = () =>
+      pda(Buffer.from("referral_rewards"), referrer.keypair.publicKey.toBuffer(), usdcMint.toBuffer());
+
+    const confirm = (rewards: PublicKey | null, vault: PublicKey | null) =>
+      program.methods
+        .confirmTransfer()
+        .accountsPartial({
+          transferRequest,
+          senderProfile: payer.profile,
+          receiverProfile: referee.profile,
+          senderTokenAccount: payer.tokenAccount,
+          receiverTokenAccount: referee.tokenAccount,
+          holdVault: null,
+          referralRewards: rewards,
+          referralVault: vault,
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          platformFeeAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+
+    const claim = (claimant: Keypair, tokenAccount: PublicKey) =>
+      program.methods
+        .claimReferralRewards()
+        .accountsPartial({
+          referralRewards: referralRewards(),
+          referralVault: referralVault(),
+          referrerTokenAccount: tokenAccount,
+          referrer: claimant.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([claimant])
+        .rpc();
+
+    before(async () => {
+      referrer = await newUser("NGA", 0);
+      payer = await newUser("USA", 200, { kyc: true });
+
+      // Referrers earn 20% of the platform fee
+      await program.methods
+        .updateReferralShare(2000)
+        .accountsPartial({ admin: provider.wallet.publicKey })
+        .rpc();
+      await program.methods
+        .initializeReferralVault()
+        .accountsPartial({
+          referralVault: referralVault(),
+          mint: usdcMint,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    });
+
+    it("Rejects a referrer without a registered profile", async () => {
+      const keypair = await fundedKeypair();
+
+      await expectError(
+        program.methods
+          .initializeUser({ receiver: {} }, "NGA", Keypair.generate().publicKey)
+          .accountsPartial({
+            userProfile: profilePda(keypair.publicKey),
+            referrerProfile: null,
+            authority: keypair.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([keypair])
+          .rpc(),
+        "InvalidReferrer"
+      );
+    });
+
+    it("Registers a referred user", async () => {
+      referee = await newUser("NGA", 0, { referrer: referrer.keypair.publicKey });
+
+      const profile = await program.account.userProfile.fetch(referee.profile);
+      assert.equal(profile.referrer.toString(), referrer.keypair.publicKey.toString());
+    });
+
+    it("Requires the referrer's rewards account when paying a referred receiver", async () => {
+      const payerProfile = await program.account.userProfile.fetch(payer.profile);
+      transferRequest = pda(
+        Buffer.from("transfer_request"),
+        payer.keypair.publicKey.toBuffer(),
+        referee.keypair.publicKey.toBuffer(),
+        payerProfile.transferNonce.toArrayLike(Buffer, "le", 8)
+      );
+
+      await program.methods
+        .initiateTransfer(usdc(100), referee.keypair.publicKey, { receiver: {} })
+        .accountsPartial({
+          senderProfile: payer.profile,
+          transferRequest,
+          receiverProfile: referee.profile,
+          senderTokenAccount: payer.tokenAccount,
+          mint: usdcMint,
+          receiver: referee.keypair.publicKey,
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+
+      await expectError(confirm(null, null), "ReferralAccountsRequired");
+    });
+
+    it("Credits the referrer a share of the fee", async () => {
+      await program.methods
+        .initializeReferralRewards(referrer.keypair.publicKey)
+        .accountsPartial({
+          referralRewards: referralRewards(),
+          mint: usdcMint,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      await confirm(referralRewards(), referralVault());
+
+      // 20% of the 0.5 USDC fee on 100 USDC
+      const rewards = await program.account.referralRewards.fetch(referralRewards());
+      assert.equal(rewards.claimable.toNumber(), usdc(0.1).toNumber());
+      assert.equal(rewards.totalEarned.toNumber(), usdc(0.1).toNumber());
+      assert.equal(await balanceOf(referralVault()), usdc(0.1).toNumber());
+      assert.equal(await balanceOf(referee.tokenAccount), usdc(99.5).toNumber());
+    });
+
+    it("Only lets the referrer claim", async () => {
+      await expectError(claim(referee.keypair, referee.tokenAccount), "Unauthorized");
+    });
+
+    it("Pays out the claimable balance once", async () => {
+      await claim(referrer.keypair, referrer.tokenAccount);
+
+      assert.equal(await balanceOf(referrer.tokenAccount), usdc(0.1).toNumber());
+      const rewards = await program.account.referralRewards.fetch(referralRewards());
+      assert.equal(rewards.claimable.toNumber(), 0);
+
+      await expectError(claim(referrer.keypair, referrer.tokenAccount), "NothingToWithdraw");
+    });
+  });
+
+  describe("Request for Quote", () => {
+    let lowBidder: Awaited<ReturnType<typeof newProvider>>;
+    let highBidder: Awaited<ReturnType<typeof newProvider>>;
+    let lateBidder: Awaited<ReturnType<typeof newProvider>>;
+
+    const quotePda = (withdrawalRequest: PublicKey, bidder: Awaited<ReturnType<typeof newProvider>>) =>
+      pda(Buffer.from("quote"), withdrawalRequest.toBuffer(), bidder.keypair.publicKey.toBuffer());
+
+    const submitQuote = (
+      withdrawalRequest: PublicKey,
+      bidder: Awaited<ReturnType<typeof newProvider>>,
+      exchangeRate: number,
+      feeBps: number
+    ) =>
+      program.methods
+        .submitQuote(usdc(exchangeRate), feeBps, new anchor.BN(0))
+        .accountsPartial({
+          withdrawalRequest,
+          quote: quotePda(withdrawalRequest, bidder),
+          liquidityProvider: bidder.account,
+          referenceRate: ngnReferenceRatePda,
+          authority: bidder.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bidder.keypair])
+        .rpc();
+
+    const crank = (withdrawalRequest: PublicKey, bidders: Awaited<ReturnType<typeof newProvider>>[]) =>
+      program.methods
+        .selectBestQuote()
+        .accountsPartial({ withdrawalRequest, referenceRate: ngnReferenceRatePda })
+        .remainingAccounts(
+          bidders.reduce(
+            (accounts, bidder) =>
+              accounts.concat([
+                { pubkey: quotePda(withdrawalRequest, bidder), isWritable: true, isSigner: false },
+                { pubkey: bidder.account, isWritable: false, isSigner: false },
+              ]),
+            [] as anchor.web3.AccountMeta[]
+          )
+        )
+        .rpc();
+
+    before(async () => {
+      lowBidder = await newProvider("Lagos, Nigeria", 1500, 100);
+      highBidder = await newProvider("Abuja, Nigeria", 1520, 50);
+      lateBidder = await newProvider("Ibadan, Nigeria", 1510, 100);
+    });
+
+    describe("Crank selection", () => {
+      let withdrawalRequest: PublicKey;
+
+      before(async () => {
+        const freelancer = await newUser("NGA", 100);
+        withdrawalRequest = await requestWithdrawal(freelancer, 50, 6);
+      });
+
+      it("Rejects quotes with fees above the cap", async () => {
+        await expectError(
+          program.methods
+            .submitQuote(usdc(1500), 1001, new anchor.BN(0))
+            .accountsPartial({
+              withdrawalRequest,
+              quote: quotePda(withdrawalRequest, lowBidder),
+              liquidityProvider: lowBidder.account,
+              referenceRate: ngnReferenceRatePda,
+              authority: lowBidder.keypair.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([lowBidder.keypair])
+            .rpc(),
+          "InvalidWithdrawalFee"
+        );
+      });
+
+      it("Collects quotes during the window", async () => {
+        await submitQuote(withdrawalRequest, lowBidder, 1500, 100);
+        await submitQuote(withdrawalRequest, highBidder, 1520, 50);
+
+        const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+        assert.equal(request.quoteCount, 2);
+      });
+
+      it("Won't rank quotes while the window is open", async () => {
+        await expectError(crank(withdrawalRequest, [lowBidder]), "QuoteWindowOpen");
+      });
+
+      it("Closes the window to new quotes", async () => {
+        await sleep(7000);
+        await expectError(submitQuote(withdrawalRequest, lateBidder, 1510, 100), "QuoteWindowClosed");
+      });
+
+      it("Ranks each quote once across pages, then selects the best payout", async () => {
+        await crank(withdrawalRequest, [lowBidder]);
+        let request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+        assert.deepEqual(request.status, { pending: {} });
+        assert.equal(request.quotesRanked, 1);
+
+        await expectError(crank(withdrawalRequest, [lowBidder]), "InvalidQuote");
+
+        await crank(withdrawalRequest, [highBidder]);
+        request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+        assert.deepEqual(request.status, { providerSelected: {} });
+        assert.equal(request.selectedProvider.toString(), highBidder.keypair.publicKey.toString());
+        assert.equal(request.exchangeRate.toNumber(), usdc(1520).toNumber());
+        assert.equal(request.netAmount.toNumber(), usdc(49.75).toNumber());
+      });
+    });
+
+    describe("Freelancer acceptance", () => {
+      let freelancer: Awaited<ReturnType<typeof newUser>>;
+      let withdrawalRequest: PublicKey;
+
+      const acceptQuote = (bidder: Awaited<ReturnType<typeof newProvider>>) =>
+        program.methods
+          .acceptQuote()
+          .accountsPartial({
+            withdrawalRequest,
+            quote: quotePda(withdrawalRequest, bidder),
+            liquidityProvider: bidder.account,
+            referenceRate: ngnReferenceRatePda,
+            freelancer: freelancer.keypair.publicKey,
+          })
+          .signers([freelancer.keypair])
+          .rpc();
+
+      before(async () => {
+        freelancer = await newUser("NGA", 100);
+        withdrawalRequest = await requestWithdrawal(freelancer, 50, 60);
+        await submitQuote(withdrawalRequest, lowBidder, 1500, 100);
+        await submitQuote(withdrawalRequest, lateBidder, 1510, 100);
+      });
+
+      it("Rejects a quote whose provider no longer qualifies", async () => {
+        await program.methods
+          .updateProviderAvailability(usdc(1000), false)
+          .accountsPartial({ liquidityProvider: lateBidder.account, authority: lateBidder.keypair.publicKey })
+          .signers([lateBidder.keypair])
+          .rpc();
+
+        await expectError(acceptQuote(lateBidder), "NoEligibleProvider");
+      });
+
+      it("Locks in an accepted quote's rate and fees", async () => {
+        await acceptQuote(lowBidder);
+
+        const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+        assert.deepEqual(request.status, { providerSelected: {} });
+        assert.equal(request.selectedProvider.toString(), lowBidder.keypair.publicKey.toString());
+        assert.equal(request.providerFee.toNumber(), usdc(0.5).toNumber());
+        assert.equal(request.netAmount.toNumber(), usdc(49.5).toNumber());
+      });
+    });
+  });
+
+  describe("Provider Routing", () => {
+    let lagos: Awaited<ReturnType<typeof newProvider>>;
+    let kano: Awaited<ReturnType<typeof newProvider>>;
+    let lagosNoFee: Awaited<ReturnType<typeof newProvider>>;
+
+    const route = (withdrawalRequest: PublicKey, freelancer: Keypair, location: string | null, minNet: number) =>
+      program.methods
+        .routeWithdrawal(location, usdc(minNet))
+        .accountsPartial({
+          withdrawalRequest,
+          referenceRate: ngnReferenceRatePda,
+          freelancer: freelancer.publicKey,
+        })
+        .remainingAccounts(
+          [lagos, kano, lagosNoFee].map((candidate) => ({
+            pubkey: candidate.account,
+            isWritable: false,
+            isSigner: false,
+          }))
+        )
+        .signers([freelancer])
+        .rpc();
+
+    before(async () => {
+      // Payouts on 50 USDC: 74,250 / 74,970 / 74,500 NGN
+      lagos = await newProvider("Lagos, Nigeria", 1500, 100);
+      kano = await newProvider("Kano, Nigeria", 1530, 200);
+      lagosNoFee = await newProvider("Lagos, Nigeria", 1490, 0);
+    });
+
+    it("Routes to the candidate paying the most local currency", async () => {
+      const freelancer = await newUser("NGA", 100);
+      const withdrawalRequest = await requestWithdrawal(freelancer, 50);
+
+      // The best payout still takes a 2% fee
+      await expectError(route(withdrawalRequest, freelancer.keypair, null, 49.5), "NetAmountTooLow");
+
+      await route(withdrawalRequest, freelancer.keypair, null, 49);
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.equal(request.selectedProvider.toString(), kano.keypair.publicKey.toString());
+      assert.equal(request.netAmount.toNumber(), usdc(49).toNumber());
+    });
+
+    it("Only considers candidates in the requested location", async () => {
+      const freelancer = await newUser("NGA", 100);
+      const withdrawalRequest = await requestWithdrawal(freelancer, 50);
+
+      await expectError(route(withdrawalRequest, freelancer.keypair, "Accra, Ghana", 0), "NoEligibleProvider");
+
+      await route(withdrawalRequest, freelancer.keypair, "Lagos, Nigeria", 0);
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.equal(request.selectedProvider.toString(), lagosNoFee.keypair.publicKey.toString());
+      assert.equal(request.netAmount.toNumber(), usdc(50).toNumber());
+    });
+  });
+
+  describe("Partial Fills", () => {
+    let freelancer: Awaited<ReturnType<typeof newUser>>;
+    let first: Awaited<ReturnType<typeof newProvider>>;
+    let second: Awaited<ReturnType<typeof newProvider>>;
+    let withdrawalRequest: PublicKey;
+
+    const fillPda = (index: number) =>
+      pda(Buffer.from("withdrawal_fill"), withdrawalRequest.toBuffer(), Buffer.from([index]));
+
+    const addFill = async (filler: Awaited<ReturnType<typeof newProvider>>, amount: number) => {
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      return program.methods
+        .addWithdrawalFill(filler.keypair.publicKey, usdc(amount))
+        .accountsPartial({
+          withdrawalRequest,
+          withdrawalFill: fillPda(request.fillCount),
+          liquidityProvider: filler.account,
+          referenceRate: ngnReferenceRatePda,
+          freelancer: freelancer.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([freelancer.keypair])
+        .rpc();
+    };
+
+    const cancelFill = (index: number, filler: Awaited<ReturnType<typeof newProvider>>, signer: Keypair) =>
+      program.methods
+        .cancelWithdrawalFill()
+        .accountsPartial({
+          withdrawalRequest,
+          withdrawalFill: fillPda(index),
+          liquidityProvider: filler.account,
+          authority: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+
+    const finalizeFill = (index: number, filler: Awaited<ReturnType<typeof newProvider>>) =>
+      program.methods
+        .finalizeWithdrawalFill()
+        .accountsPartial({
+          withdrawalRequest,
+          withdrawalFill: fillPda(index),
+          liquidityProvider: filler.account,
+          freelancerTokenAccount: freelancer.tokenAccount,
+          providerTokenAccount: filler.tokenAccount,
+          platformFeeAccount,
+          freelancer: freelancer.keypair.publicKey,
+          providerAuthority: filler.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([freelancer.keypair, filler.keypair])
+        .rpc();
+
+    before(async () => {
+      freelancer = await newUser("NGA", 100);
+      first = await newProvider("Lagos, Nigeria", 1500, 100);
+      second = await newProvider("Abuja, Nigeria", 1510, 100);
+      withdrawalRequest = await requestWithdrawal(freelancer, 100);
+    });
+
+    it("Reserves each provider's liquidity per fill", async () => {
+      await addFill(first, 60);
+
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.deepEqual(request.status, { partiallyFilled: {} });
+      assert.equal(request.filledAmount.toNumber(), usdc(60).toNumber());
+      const lp = await program.account.liquidityProvider.fetch(first.account);
+      assert.equal(lp.reservedLiquidity.toNumber(), usdc(60).toNumber());
+    });
+
+    it("Rejects fills beyond the unfilled remainder", async () => {
+      await expectError(addFill(second, 50), "InvalidFillAmount");
+      await addFill(second, 40);
+    });
+
+    it("Only lets the provider cancel a fill before it times out", async () => {
+      await expectError(cancelFill(1, second, freelancer.keypair), "FillNotExpired");
+
+      await cancelFill(1, second, second.keypair);
+
+      const fill = await program.account.withdrawalFill.fetch(fillPda(1));
+      assert.deepEqual(fill.status, { cancelled: {} });
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.equal(request.filledAmount.toNumber(), usdc(60).toNumber());
+      const lp = await program.account.liquidityProvider.fetch(second.account);
+      assert.equal(lp.reservedLiquidity.toNumber(), 0);
+    });
+
+    it("Completes once the reallocated remainder is finalized", async () => {
+      await addFill(first, 40);
+
+      await finalizeFill(0, first);
+      let request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.deepEqual(request.status, { partiallyFilled: {} });
+
+      await finalizeFill(2, first);
+      request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.deepEqual(request.status, { completed: {} });
+      assert.equal(request.completedFills, 2);
+      assert.equal(request.cancelledFills, 1);
+
+      const lp = await program.account.liquidityProvider.fetch(first.account);
+      assert.equal(lp.reservedLiquidity.toNumber(), 0);
+      assert.equal(await balanceOf(first.tokenAccount), usdc(100).toNumber());
+    });
+  });
+
+  describe("Provider Acceptance", () => {
+    let freelancer: Awaited<ReturnType<typeof newUser>>;
+    let holder: Awaited<ReturnType<typeof newProvider>>;
+    let rival: Awaited<ReturnType<typeof newProvider>>;
+    let withdrawalRequest: PublicKey;
+
+    const accept = (acceptor: Awaited<ReturnType<typeof newProvider>>) =>
+      program.methods
+        .acceptWithdrawal()
+        .accountsPartial({
+          withdrawalRequest,
+          liquidityProvider: acceptor.account,
+          referenceRate: ngnReferenceRatePda,
+          authority: acceptor.keypair.publicKey,
+        })
+        .signers([acceptor.keypair])
+        .rpc();
+
+    const release = (signer: Keypair) =>
+      program.methods
+        .releaseWithdrawalLock()
+        .accountsPartial({ withdrawalRequest, authority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    before(async () => {
+      freelancer = await newUser("NGA", 100);
+      holder = await newProvider("Lagos, Nigeria", 1500, 100);
+      rival = await newProvider("Abuja, Nigeria", 1520, 100);
+      withdrawalRequest = await requestWithdrawal(freelancer, 50);
+    });
+
+    it("Requires the freelancer to opt in to provider acceptance", async () => {
+      await expectError(accept(holder), "ProviderAcceptanceDisabled");
+
+      // A rate floor alone doesn't open the withdrawal to providers
+      await program.methods
+        .setLimitOrder(usdc(1400), new anchor.BN(0))
+        .accountsPartial({ withdrawalRequest, freelancer: freelancer.keypair.publicKey })
+        .signers([freelancer.keypair])
+        .rpc();
+      await expectError(accept(holder), "ProviderAcceptanceDisabled");
+
+      await program.methods
+        .setProviderAcceptance(true)
+        .accountsPartial({ withdrawalRequest, freelancer: freelancer.keypair.publicKey })
+        .signers([freelancer.keypair])
+        .rpc();
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.isTrue(request.providerAccepts);
+    });
+
+    it("Locks the withdrawal to the first provider to accept", async () => {
+      await accept(holder);
+
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.deepEqual(request.status, { providerSelected: {} });
+      assert.equal(request.selectedProvider.toString(), holder.keypair.publicKey.toString());
+      assert.equal(request.lastLocker.toString(), holder.keypair.publicKey.toString());
+      assert.isAbove(request.lockExpiresAt.toNumber(), 0);
+
+      await expectError(accept(rival), "WithdrawalLocked");
+    });
+
+    it("Only lets the freelancer release an unexpired lock", async () => {
+      await expectError(release(rival.keypair), "WithdrawalLocked");
+
+      await release(freelancer.keypair);
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.deepEqual(request.status, { pending: {} });
+      assert.isNull(request.selectedProvider);
+    });
+
+    it("Hands a released withdrawal to another provider", async () => {
+      await expectError(accept(holder), "RelockNotAllowed");
+
+      await accept(rival);
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.equal(request.selectedProvider.toString(), rival.keypair.publicKey.toString());
+    });
+  });
+
+  describe("Limit Orders", () => {
+    let headline: Awaited<ReturnType<typeof newProvider>>;
+    let lean: Awaited<ReturnType<typeof newProvider>>;
+
+    const setLimit = (withdrawalRequest: PublicKey, freelancer: Keypair, minRate: number, expiresAt: number) =>
+      program.methods
+        .setLimitOrder(usdc(minRate), new anchor.BN(expiresAt))
+        .accountsPartial({ withdrawalRequest, freelancer: freelancer.publicKey })
+        .signers([freelancer])
+        .rpc();
+
+    const match = (withdrawalRequest: PublicKey, candidate: Awaited<ReturnType<typeof newProvider>>) =>
+      program.methods
+        .matchLimitOrder()
+        .accountsPartial({
+          withdrawalRequest,
+          liquidityProvider: candidate.account,
+          referenceRate: ngnReferenceRatePda,
+        })
+        .rpc();
+
+    const expire = (withdrawalRequest: PublicKey) =>
+      program.methods.expireLimitOrder().accountsPartial({ withdrawalRequest }).rpc();
+
+    before(async () => {
+      // All-in rates after fees: 1,485 and 1,495 NGN per USDC
+      headline = await newProvider("Lagos, Nigeria", 1500, 100);
+      lean = await newProvider("Lagos, Nigeria", 1495, 0);
+    });
+
+    it("Matches on the rate after fees, not the headline rate", async () => {
+      const freelancer = await newUser("NGA", 100);
+      const withdrawalRequest = await requestWithdrawal(freelancer, 50);
+
+      await expectError(
+        setLimit(withdrawalRequest, freelancer.keypair, 1490, (await chainTime()) - 1),
+        "InvalidLimitOrder"
+      );
+      await setLimit(withdrawalRequest, freelancer.keypair, 1490, 0);
+
+      await expectError(match(withdrawalRequest, headline), "RateBelowLimit");
+
+      await match(withdrawalRequest, lean);
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.deepEqual(request.status, { providerSelected: {} });
+      assert.equal(request.selectedProvider.toString(), lean.keypair.publicKey.toString());
+      assert.equal(request.netAmount.toNumber(), usdc(50).toNumber());
+    });
+
+    it("Expires an unmatched order once its deadline passes", async () => {
+      const freelancer = await newUser("NGA", 100);
+      const withdrawalRequest = await requestWithdrawal(freelancer, 50);
+
+      await setLimit(withdrawalRequest, freelancer.keypair, 2000, (await chainTime()) + 3);
+      await expectError(match(withdrawalRequest, lean), "RateBelowLimit");
+      await expectError(expire(withdrawalRequest), "LimitOrderNotExpired");
+
+      await sleep(5000);
+      await expectError(match(withdrawalRequest, lean), "LimitOrderExpired");
+
+      await expire(withdrawalRequest);
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.deepEqual(request.status, { expired: {} });
+    });
+  });
+
+  describe("Reference Rate Guard", () => {
+    const USD = { code: Array.from(Buffer.from("USD")) };
+    const KES = { code: Array.from(Buffer.from("KES")) };
+    const kesReferenceRatePda = pda(Buffer.from("reference_rate"), Buffer.from("USD"), Buffer.from("KES"));
+
+    let kesProvider: Keypair;
+
+    const register = (keypair: Keypair, exchangeRate: number, referenceRate: PublicKey = kesReferenceRatePda) =>
+      program.methods
+        .registerLiquidityProvider("Nairobi, Kenya", usdc(exchangeRate), KES, 100, new anchor.BN(0), [
+          { mobileMoney: {} },
+        ])
+        .accountsPartial({
+          liquidityProvider: providerPda(keypair.publicKey),
+          referenceRate,
+          authority: keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([keypair])
+        .rpc();
+
+    const postRate = (rate: number) =>
+      program.methods
+        .updateReferenceRate(usdc(rate))
+        .accountsPartial({ referenceRate: kesReferenceRatePda, oracle: provider.wallet.publicKey })
+        .rpc();
+
+    before(async () => {
+      kesProvider = await fundedKeypair();
+
+      // 5% band, stale after an hour
+      await program.methods
+        .createReferenceRate(USD, KES, provider.wallet.publicKey, 500, new anchor.BN(3600))
+        .accountsPartial({
+          referenceRate: kesReferenceRatePda,
+          admin: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("Rejects provider rates until the oracle posts one", async () => {
+      await expectError(register(kesProvider, 130), "StaleReferenceRate");
+    });
+
+    it("Only accepts rates from the oracle", async () => {
+      await expectError(
+        program.methods
+          .updateReferenceRate(usdc(130))
+          .accountsPartial({ referenceRate: kesReferenceRatePda, oracle: kesProvider.publicKey })
+          .signers([kesProvider])
+          .rpc(),
+        "Unauthorized"
+      );
+
+      await postRate(130);
+      const referenceRate = await program.account.referenceRate.fetch(kesReferenceRatePda);
+      assert.equal(referenceRate.rate.toNumber(), usdc(130).toNumber());
+    });
+
+    it("Rejects provider rates outside the band or checked against another pair", async () => {
+      await expectError(register(kesProvider, 200), "RateOutsideReferenceBand");
+      await expectError(register(kesProvider, 131, ngnReferenceRatePda), "InvalidReferenceRate");
+
+      await register(kesProvider, 131);
+      await program.methods
+        .updateProviderAvailability(usdc(1000), true)
+        .accountsPartial({ liquidityProvider: providerPda(kesProvider.publicKey), authority: kesProvider.publicKey })
+        .signers([kesProvider])
+        .rpc();
+    });
+
+    it("Re-checks the provider rate when a withdrawal locks it in", async () => {
+      const freelancer = await newUser("KEN", 100);
+      const withdrawalRequest = pda(
+        Buffer.from("withdrawal_request"),
+        freelancer.keypair.publicKey.toBuffer(),
+        Buffer.alloc(8)
+      );
+      await program.methods
+        .requestWithdrawal(usdc(50), { mobileMoney: {} }, KES, new anchor.BN(0), 0)
+        .accountsPartial({
+          freelancerProfile: freelancer.profile,
+          withdrawalRequest,
+          freelancerTokenAccount: freelancer.tokenAccount,
+          freelancer: freelancer.keypair.publicKey,
+          authority: freelancer.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([freelancer.keypair])
+        .rpc();
+
+      const selectProvider = () =>
+        program.methods
+          .selectProvider(kesProvider.publicKey, new anchor.BN(0))
+          .accountsPartial({
+            withdrawalRequest,
+            liquidityProvider: providerPda(kesProvider.publicKey),
+            referenceRate: kesReferenceRatePda,
+            freelancer: freelancer.keypair.publicKey,
+          })
+          .signers([freelancer.keypair])
+          .rpc();
+
+      // The market moves away from the provider's registered rate
+      await postRate(150);
+      await expectError(selectProvider(), "RateOutsideReferenceBand");
+
+      await postRate(132);
+      await selectProvider();
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.equal(request.selectedProvider.toString(), kesProvider.publicKey.toString());
+    });
+  });
+
+  describe("Batch Payouts", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let payees: Awaited<ReturnType<typeof newUser>>[];
+
+    const batchSummaryPda = async () => {
+      const profile = await program.account.userProfile.fetch(payer.profile);
+      return pda(
+        Buffer.from("batch_summary"),
+        payer.keypair.publicKey.toBuffer(),
+        profile.transferNonce.toArrayLike(Buffer, "le", 8)
+      );
+    };
+
+    // [receiver_profile, receiver_token_account, corridor, receiver_denial, referral_rewards]
+    const legAccounts = (payee: Awaited<ReturnType<typeof newUser>>) =>
+      [
+        payee.profile,
+        payee.tokenAccount,
+        pda(Buffer.from("corridor"), Buffer.from("USA"), Buffer.from("NGA")),
+        pda(Buffer.from("denied_address"), payee.keypair.publicKey.toBuffer()),
+        payee.profile,
+      ].map((pubkey, index) => ({ pubkey, isSigner: false, isWritable: index !== 3 }));
+
+    const batch = async (amounts: number[], legs: Awaited<ReturnType<typeof newUser>>[]) => {
+      const batchSummary = await batchSummaryPda();
+      await program.methods
+        .batchTransfer(amounts.map(usdc), null)
+        .accountsPartial({
+          senderProfile: payer.profile,
+          batchSummary,
+          senderTokenAccount: payer.tokenAccount,
+          referralVault: null,
+          mint: usdcMint,
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+          platformFeeAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(legs.reduce((all, payee) => all.concat(legAccounts(payee)), []))
+        .signers([payer.keypair])
+        .rpc();
+      return batchSummary;
+    };
+
+    before(async () => {
+      payer = await newUser("USA", 100, { kyc: true });
+      payees = [];
+      for (let i = 0; i < 3; i++) {
+        payees.push(await newUser("NGA", 0));
+      }
+    });
+
+    it("Rejects more legs than fit in one transaction", async () => {
+      // The size check runs before any leg accounts are read
+      await expectError(batch(Array(10).fill(1), []), "InvalidBatchSize");
+    });
+
+    it("Pays every receiver its net amount and records the batch", async () => {
+      const batchSummary = await batch([10, 20, 30], payees);
+
+      // 0.5% platform fee comes out of each leg
+      assert.equal(await balanceOf(payees[0].tokenAccount), usdc(9.95).toNumber());
+      assert.equal(await balanceOf(payees[1].tokenAccount), usdc(19.9).toNumber());
+      assert.equal(await balanceOf(payees[2].tokenAccount), usdc(29.85).toNumber());
+
+      const summary = await program.account.batchSummary.fetch(batchSummary);
+      assert.equal(summary.legs.length, 3);
+      assert.equal(summary.totalAmount.toNumber(), usdc(60).toNumber());
+      assert.equal(summary.totalNetAmount.toNumber(), usdc(59.7).toNumber());
+      assert.equal(summary.totalFee.toNumber(), usdc(0.3).toNumber());
+      assert.equal(summary.legs[2].receiver.toString(), payees[2].keypair.publicKey.toString());
+    });
+  });
+
+  describe("Compliance Holds", () => {
+    let officer: Keypair;
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let payee: Awaited<ReturnType<typeof newUser>>;
+
+    const holdPda = (target: PublicKey) => pda(Buffer.from("compliance_hold"), target.toBuffer());
+
+    const hold = (transferRequest: PublicKey) =>
+      program.methods
+        .holdTransfer({ suspiciousActivity: {} })
+        .accountsPartial({
+          transferRequest,
+          complianceHold: holdPda(transferRequest),
+          officer: officer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([officer])
+        .rpc();
+
+    const resolve = (transferRequest: PublicKey, release: boolean, resolution: object) =>
+      program.methods
+        .resolveTransferHold(release, resolution as any)
+        .accountsPartial({
+          transferRequest,
+          complianceHold: holdPda(transferRequest),
+          officer: officer.publicKey,
+        })
+        .signers([officer])
+        .rpc();
+
+    before(async () => {
+      officer = await newComplianceOfficer();
+      payer = await newUser("USA", 50, { kyc: true });
+      payee = await newUser("NGA", 0);
+    });
+
+    it("Holds a pending transfer until it is resolved", async () => {
+      const transferRequest = await initiateTransfer(payer, payee, 10);
+      await hold(transferRequest);
+
+      const request = await program.account.transferRequest.fetch(transferRequest);
+      assert.deepEqual(request.status, { underReview: {} });
+      const record = await program.account.complianceHold.fetch(holdPda(transferRequest));
+      assert.deepEqual(record.status, { open: {} });
+      assert.deepEqual(record.reason, { suspiciousActivity: {} });
+
+      await expectError(confirmTransfer(payer, payee, transferRequest), "InvalidTransferStatus");
+    });
+
+    it("Releases a held transfer and keeps the hold record", async () => {
+      const transferRequest = await initiateTransfer(payer, payee, 10);
+      await hold(transferRequest);
+      await resolve(transferRequest, true, { none: {} });
+
+      const record = await program.account.complianceHold.fetch(holdPda(transferRequest));
+      assert.deepEqual(record.status, { released: {} });
+      assert.deepEqual(record.resolution, { none: {} });
+      assert.equal(record.resolvedBy.toString(), officer.publicKey.toString());
+      assert.isNotNull(record.resolvedAt);
+
+      // The released transfer goes through, and the hold can't be resolved twice
+      await confirmTransfer(payer, payee, transferRequest);
+      assert.equal(await balanceOf(payee.tokenAccount), usdc(9.95).toNumber());
+      await expectError(resolve(transferRequest, false, { fraud: {} }), "InvalidTransferStatus");
+    });
+
+    it("Requires a resolution code to reject a hold", async () => {
+      const transferRequest = await initiateTransfer(payer, payee, 10);
+      await hold(transferRequest);
+
+      await expectError(resolve(transferRequest, false, { none: {} }), "InvalidComplianceReason");
+    });
+
+    it("Rejects a held transfer with its resolution code", async () => {
+      const transferRequest = await initiateTransfer(payer, payee, 10);
+      await hold(transferRequest);
+      await resolve(transferRequest, false, { fraud: {} });
+
+      const request = await program.account.transferRequest.fetch(transferRequest);
+      assert.deepEqual(request.status, { failed: {} });
+      const record = await program.account.complianceHold.fetch(holdPda(transferRequest));
+      assert.deepEqual(record.status, { rejected: {} });
+      assert.deepEqual(record.resolution, { fraud: {} });
+    });
+
+    it("Rejects a held withdrawal", async () => {
+      const freelancer = await newUser("NGA", 20);
+      const withdrawalRequest = await requestWithdrawal(freelancer, 10);
+
+      await program.methods
+        .holdWithdrawal({ kycReview: {} })
+        .accountsPartial({
+          withdrawalRequest,
+          complianceHold: holdPda(withdrawalRequest),
+          officer: officer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([officer])
+        .rpc();
+      await program.methods
+        .resolveWithdrawalHold(false, { kycReview: {} })
+        .accountsPartial({
+          withdrawalRequest,
+          complianceHold: holdPda(withdrawalRequest),
+          officer: officer.publicKey,
+        })
+        .signers([officer])
+        .rpc();
+
+      const request = await program.account.withdrawalRequest.fetch(withdrawalRequest);
+      assert.deepEqual(request.status, { failed: {} });
+      const record = await program.account.complianceHold.fetch(holdPda(withdrawalRequest));
+      assert.deepEqual(record.status, { rejected: {} });
+    });
+  });
+
+  describe("Refunds", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let payee: Awaited<ReturnType<typeof newUser>>;
+    let referrer: Awaited<ReturnType<typeof newUser>>;
+    let referee: Awaited<ReturnType<typeof newUser>>;
+
+    const referralVault = () => pda(Buffer.from("referral_vault"), usdcMint.toBuffer());
+    const referralRewards = () =>
+      pda(Buffer.from("referral_rewards"), referrer.keypair.publicKey.toBuffer(), usdcMint.toBuffer());
+
+    const refund = async (
+      receiver: Awaited<ReturnType<typeof newUser>>,
+      transferRequest: PublicKey,
+      amount: number,
+      refundFee: boolean = false
+    ) => {
+      const request = await program.account.transferRequest.fetch(transferRequest);
+      const index = Buffer.alloc(2);
+      index.writeUInt16LE(request.refundCount);
+
+      await program.methods
+        .refundTransfer(usdc(amount), refundFee)
+        .accountsPartial({
+          transferRequest,
+          transferRefund: pda(Buffer.from("transfer_refund"), transferRequest.toBuffer(), index),
+          senderProfile: payer.profile,
+          receiverProfile: receiver.profile,
+          senderTokenAccount: payer.tokenAccount,
+          receiverTokenAccount: receiver.tokenAccount,
+          referralRewards: refundFee ? referralRewards() : null,
+          referralVault: refundFee ? referralVault() : null,
+          authority: receiver.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([receiver.keypair])
+        .rpc();
+    };
+
+    before(async () => {
+      payer = await newUser("USA", 300, { kyc: true });
+      payee = await newUser("NGA", 0);
+      referrer = await newUser("NGA", 0);
+      referee = await newUser("NGA", 0, { referrer: referrer.keypair.publicKey });
+
+      await program.methods
+        .updateReferralShare(2000)
+        .accountsPartial({ admin: provider.wallet.publicKey })
+        .rpc();
+      await program.methods
+        .initializeReferralRewards(referrer.keypair.publicKey)
+        .accountsPartial({
+          referralRewards: referralRewards(),
+          mint: usdcMint,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("Adds up partial refunds until the whole net amount is returned", async () => {
+      const transferRequest = await initiateTransfer(payer, payee, 100);
+      await confirmTransfer(payer, payee, transferRequest);
+
+      await refund(payee, transferRequest, 40);
+      let request = await program.account.transferRequest.fetch(transferRequest);
+      assert.deepEqual(request.status, { completed: {} });
+      assert.equal(request.refundedAmount.toNumber(), usdc(40).toNumber());
+
+      await expectError(refund(payee, transferRequest, 60), "RefundExceedsTransfer");
+
+      await refund(payee, transferRequest, 59.5);
+      request = await program.account.transferRequest.fetch(transferRequest);
+      assert.deepEqual(request.status, { refunded: {} });
+      assert.equal(request.refundCount, 2);
+      assert.equal(await balanceOf(payee.tokenAccount), 0);
+
+      await expectError(refund(payee, transferRequest, 1), "InvalidTransferStatus");
+    });
+
+    it("Returns the referral share of the fee with the refund", async () => {
+      const transferRequest = await initiateTransfer(payer, referee, 100);
+      await program.methods
+        .confirmTransfer()
+        .accountsPartial({
+          transferRequest,
+          senderProfile: payer.profile,
+          receiverProfile: referee.profile,
+          senderTokenAccount: payer.tokenAccount,
+          receiverTokenAccount: referee.tokenAccount,
+          holdVault: null,
+          referralRewards: referralRewards(),
+          referralVault: referralVault(),
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          platformFeeAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+
+      // 20% of the 0.5 USDC fee went to the referrer; half the transfer returns half of it
+      const payerBefore = await balanceOf(payer.tokenAccount);
+      await refund(referee, transferRequest, 49.75, true);
+
+      assert.equal((await balanceOf(payer.tokenAccount)) - payerBefore, usdc(49.8).toNumber());
+      const rewards = await program.account.referralRewards.fetch(referralRewards());
+      assert.equal(rewards.claimable.toNumber(), usdc(0.05).toNumber());
+      const record = await program.account.transferRefund.fetch(
+        pda(Buffer.from("transfer_refund"), transferRequest.toBuffer(), Buffer.from([0, 0]))
+      );
+      assert.equal(record.feeRefunded.toNumber(), usdc(0.05).toNumber());
+    });
+  });
+
+  describe("Held Transfers", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let referrer: Awaited<ReturnType<typeof newUser>>;
+    let referee: Awaited<ReturnType<typeof newUser>>;
+    let released: PublicKey;
+
+    const holdVault = () => pda(Buffer.from("hold_vault"), usdcMint.toBuffer());
+    const referralVault = () => pda(Buffer.from("referral_vault"), usdcMint.toBuffer());
+    const referralRewards = () =>
+      pda(Buffer.from("referral_rewards"), referrer.keypair.publicKey.toBuffer(), usdcMint.toBuffer());
+    const claimable = async () =>
+      (await program.account.referralRewards.fetch(referralRewards())).claimable.toNumber();
+
+    const updateHoldSettings = (threshold: number, period: number) =>
+      program.methods
+        .updateHoldSettings(usdc(threshold), new anchor.BN(period))
+        .accountsPartial({ admin: provider.wallet.publicKey })
+        .rpc();
+
+    const sendHeld = async (amount: number) => {
+      const transferRequest = await initiateTransfer(payer, referee, amount);
+      await program.methods
+        .confirmTransfer()
+        .accountsPartial({
+          transferRequest,
+          senderProfile: payer.profile,
+          receiverProfile: referee.profile,
+          senderTokenAccount: payer.tokenAccount,
+          receiverTokenAccount: referee.tokenAccount,
+          holdVault: holdVault(),
+          referralRewards: referralRewards(),
+          referralVault: referralVault(),
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          platformFeeAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+      return transferRequest;
+    };
+
+    const releaseHeld = (transferRequest: PublicKey) =>
+      program.methods
+        .releaseHeldTransfer()
+        .accountsPartial({
+          transferRequest,
+          holdVault: holdVault(),
+          receiverProfile: referee.profile,
+          receiverTokenAccount: referee.tokenAccount,
+          referralRewards: referralRewards(),
+          referralVault: referralVault(),
+          platformFeeAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    const raiseFraudClaim = (transferRequest: PublicKey, signer: Keypair) =>
+      program.methods
+        .raiseFraudClaim()
+        .accountsPartial({ transferRequest, authority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    before(async () => {
+      payer = await newUser("USA", 300, { kyc: true });
+      referrer = await newUser("NGA", 0);
+      referee = await newUser("NGA", 0, { referrer: referrer.keypair.publicKey });
+
+      await program.methods
+        .updateReferralShare(2000)
+        .accountsPartial({ admin: provider.wallet.publicKey })
+        .rpc();
+      await program.methods
+        .initializeReferralRewards(referrer.keypair.publicKey)
+        .accountsPartial({
+          referralRewards: referralRewards(),
+          mint: usdcMint,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      await program.methods
+        .initializeHoldVault()
+        .accountsPartial({
+          holdVault: holdVault(),
+          mint: usdcMint,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      // Transfers of 50 USDC or more sit in escrow for 3 seconds
+      await updateHoldSettings(50, 3);
+    });
+
+    after(async () => {
+      await updateHoldSettings(0, 0);
+    });
+
+    it("Escrows a large transfer together with its whole fee", async () => {
+      const vaultBefore = await balanceOf(holdVault());
+      released = await sendHeld(100);
+
+      const request = await program.account.transferRequest.fetch(released);
+      assert.deepEqual(request.status, { held: {} });
+      assert.equal(request.referralReward.toNumber(), usdc(0.1).toNumber());
+      assert.equal((await balanceOf(holdVault())) - vaultBefore, usdc(100).toNumber());
+      assert.equal(await balanceOf(referee.tokenAccount), 0);
+      assert.equal(await claimable(), 0);
+
+      await expectError(releaseHeld(released), "HoldPeriodActive");
+    });
+
+    it("Returns the whole escrow when a fraud claim is upheld", async () => {
+      const payerBefore = await balanceOf(payer.tokenAccount);
+      const transferRequest = await sendHeld(100);
+
+      await expectError(raiseFraudClaim(transferRequest, referee.keypair), "Unauthorized");
+      await raiseFraudClaim(transferRequest, payer.keypair);
+      await expectError(releaseHeld(transferRequest), "InvalidTransferStatus");
+
+      await program.methods
+        .resolveFraudClaim(true)
+        .accountsPartial({
+          transferRequest,
+          holdVault: holdVault(),
+          senderProfile: payer.profile,
+          receiverProfile: referee.profile,
+          senderTokenAccount: payer.tokenAccount,
+          receiverTokenAccount: referee.tokenAccount,
+          referralRewards: referralRewards(),
+          referralVault: referralVault(),
+          admin: provider.wallet.publicKey,
+          platformFeeAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const request = await program.account.transferRequest.fetch(transferRequest);
+      assert.deepEqual(request.status, { cancelled: {} });
+      assert.equal(await balanceOf(payer.tokenAccount), payerBefore);
+      assert.equal(await claimable(), 0);
+    });
+
+    it("Pays the receiver, the referrer and the treasury once the cool-off has passed", async () => {
+      await sleep(4000);
+      const treasuryBefore = await balanceOf(platformFeeAccount);
+      await releaseHeld(released);
+
+      const request = await program.account.transferRequest.fetch(released);
+      assert.deepEqual(request.status, { completed: {} });
+      assert.equal(await balanceOf(referee.tokenAccount), usdc(99.5).toNumber());
+      assert.equal(await claimable(), usdc(0.1).toNumber());
+      assert.equal((await balanceOf(platformFeeAccount)) - treasuryBefore, usdc(0.4).toNumber());
+    });
+  });
+
+  describe("Scheduled Payments", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let payee: Awaited<ReturnType<typeof newUser>>;
+
+    const schedulePda = (nonce: number) =>
+      pda(
+        Buffer.from("payment_schedule"),
+        payer.keypair.publicKey.toBuffer(),
+        payee.keypair.publicKey.toBuffer(),
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
+      );
+
+    const createSchedule = async (nonce: number, totalPayments: number, firstPaymentAt: number) =>
+      program.methods
+        .createPaymentSchedule(
+          new anchor.BN(nonce),
+          usdc(10),
+          new anchor.BN(86_400),
+          totalPayments,
+          new anchor.BN(firstPaymentAt),
+          null
+        )
+        .accountsPartial({
+          senderProfile: payer.profile,
+          receiverProfile: payee.profile,
+          paymentSchedule: schedulePda(nonce),
+          senderTokenAccount: payer.tokenAccount,
+          referralRewards: null,
+          mint: usdcMint,
+          receiver: payee.keypair.publicKey,
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+
+    // Cranked by the local wallet, which has no stake in the schedule
+    const crank = (nonce: number) =>
+      program.methods
+        .executeScheduledPayment()
+        .accountsPartial({
+          paymentSchedule: schedulePda(nonce),
+          senderProfile: payer.profile,
+          receiverProfile: payee.profile,
+          senderTokenAccount: payer.tokenAccount,
+          receiverTokenAccount: payee.tokenAccount,
+          referralRewards: null,
+          referralVault: null,
+          executor: provider.wallet.publicKey,
+          platformFeeAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    const allowance = async () =>
+      Number((await getAccount(provider.connection, payer.tokenAccount)).delegatedAmount);
+
+    before(async () => {
+      payer = await newUser("USA", 100, { kyc: true });
+      payee = await newUser("NGA", 0);
+    });
+
+    it("Delegates the whole schedule and waits until the first payment is due", async () => {
+      await createSchedule(0, 3, (await chainTime()) + 3);
+
+      // Three payments of 10 USDC, the fee included
+      assert.equal(await allowance(), usdc(30).toNumber());
+      await expectError(crank(0), "PaymentNotDue");
+    });
+
+    it("Pays the receiver once due and advances the schedule", async () => {
+      await sleep(4000);
+      const previous = await program.account.paymentSchedule.fetch(schedulePda(0));
+      await crank(0);
+
+      assert.equal(await balanceOf(payee.tokenAccount), usdc(9.95).toNumber());
+      const schedule = await program.account.paymentSchedule.fetch(schedulePda(0));
+      assert.equal(schedule.remainingPayments, 2);
+      assert.equal(schedule.paymentsMade, 1);
+      assert.equal(schedule.nextDue.toNumber(), previous.nextDue.toNumber() + 86_400);
+
+      await expectError(crank(0), "PaymentNotDue");
+    });
+
+    it("Releases a cancelled schedule's unused allowance", async () => {
+      await createSchedule(1, 2, (await chainTime()) + 60);
+      assert.equal(await allowance(), usdc(40).toNumber());
+
+      await program.methods
+        .cancelPaymentSchedule()
+        .accountsPartial({
+          paymentSchedule: schedulePda(1),
+          senderTokenAccount: payer.tokenAccount,
+          authority: payer.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+
+      assert.equal(await allowance(), usdc(20).toNumber());
+      assert.isNull(await program.account.paymentSchedule.fetchNullable(schedulePda(1)));
+    });
+
+    it("Stops cranking once the sender revokes the delegate", async () => {
+      await revoke(provider.connection, payer.keypair, payer.tokenAccount, payer.keypair);
+
+      await expectError(crank(0), "ScheduleNotDelegated");
+    });
+  });
+
+  describe("Payment Streams", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let payee: Awaited<ReturnType<typeof newUser>>;
+
+    const streamPda = (nonce: number) =>
+      pda(
+        Buffer.from("payment_stream"),
+        payer.keypair.publicKey.toBuffer(),
+        payee.keypair.publicKey.toBuffer(),
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
+      );
+    const escrowPda = (nonce: number) =>
+      pda(Buffer.from("stream_escrow"), streamPda(nonce).toBuffer());
+
+    const createStream = (nonce: number, amount: number, startTime: number, endTime: number) =>
+      program.methods
+        .createStream(
+          new anchor.BN(nonce),
+          usdc(amount),
+          new anchor.BN(startTime),
+          new anchor.BN(endTime),
+          null
+        )
+        .accountsPartial({
+          senderProfile: payer.profile,
+          receiverProfile: payee.profile,
+          paymentStream: streamPda(nonce),
+          streamEscrow: escrowPda(nonce),
+          senderTokenAccount: payer.tokenAccount,
+          mint: usdcMint,
+          receiver: payee.keypair.publicKey,
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+
+    const withdraw = (nonce: number) =>
+      program.methods
+        .withdrawFromStream()
+        .accountsPartial({
+          paymentStream: streamPda(nonce),
+          streamEscrow: escrowPda(nonce),
+          senderProfile: payer.profile,
+          receiverProfile: payee.profile,
+          receiverTokenAccount: payee.tokenAccount,
+          platformFeeAccount,
+          referralRewards: null,
+          referralVault: null,
+          sender: payer.keypair.publicKey,
+          authority: payee.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payee.keypair])
+        .rpc();
+
+    const cancel = (nonce: number, signer = payer) =>
+      program.methods
+        .cancelStream()
+        .accountsPartial({
+          paymentStream: streamPda(nonce),
+          streamEscrow: escrowPda(nonce),
+          senderTokenAccount: signer.tokenAccount,
+          authority: signer.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([signer.keypair])
+        .rpc();
+
+    before(async () => {
+      payer = await newUser("USA", 100, { kyc: true });
+      payee = await newUser("NGA", 0);
+    });
+
+    it("Escrows the deposit and pays out what has vested", async () => {
+      const now = await chainTime();
+      await createStream(0, 10, now, now + 3);
+
+      assert.equal(await balanceOf(payer.tokenAccount), usdc(90).toNumber());
+      assert.equal(await balanceOf(escrowPda(0)), usdc(10).toNumber());
+
+      await sleep(4000);
+      const treasuryBefore = await balanceOf(platformFeeAccount);
+      const escrowRent = await provider.connection.getBalance(escrowPda(0));
+      const payerLamports = await provider.connection.getBalance(payer.keypair.publicKey);
+      await withdraw(0);
+
+      // 0.5% platform fee accrues with each withdrawal
+      assert.equal(await balanceOf(payee.tokenAccount), usdc(9.95).toNumber());
+      assert.equal((await balanceOf(platformFeeAccount)) - treasuryBefore, usdc(0.05).toNumber());
+      const stream = await program.account.paymentStream.fetch(streamPda(0));
+      assert.deepEqual(stream.status, { completed: {} });
+
+      // The last withdrawal closes the escrow and returns its rent to the sender
+      assert.isNull(await provider.connection.getAccountInfo(escrowPda(0)));
+      assert.equal(
+        await provider.connection.getBalance(payer.keypair.publicKey),
+        payerLamports + escrowRent
+      );
+    });
+
+    it("Refuses withdrawals before anything has vested", async () => {
+      const now = await chainTime();
+      await createStream(1, 20, now + 60, now + 120);
+
+      await expectError(withdraw(1), "NothingToWithdraw");
+    });
+
+    it("Refunds the unvested deposit on cancel and closes the emptied escrow", async () => {
+      await expectError(cancel(1, payee), "Unauthorized");
+      await cancel(1);
+
+      assert.equal(await balanceOf(payer.tokenAccount), usdc(90).toNumber());
+      assert.isNull(await provider.connection.getAccountInfo(escrowPda(1)));
+      const stream = await program.account.paymentStream.fetch(streamPda(1));
+      assert.deepEqual(stream.status, { cancelled: {} });
+      assert.equal(stream.refundedAmount.toNumber(), usdc(20).toNumber());
+    });
+  });
+
+  describe("Invoices", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let issuer: Awaited<ReturnType<typeof newUser>>;
+
+    const invoicePda = (nonce: number) =>
+      pda(
+        Buffer.from("invoice"),
+        issuer.keypair.publicKey.toBuffer(),
+        payer.keypair.publicKey.toBuffer(),
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
+      );
+
+    const createInvoice = (nonce: number, amount: number, dueDate: number) =>
+      program.methods
+        .createInvoice(new anchor.BN(nonce), usdc(amount), new anchor.BN(dueDate), Array(32).fill(7))
+        .accountsPartial({
+          issuerProfile: issuer.profile,
+          invoice: invoicePda(nonce),
+          mint: usdcMint,
+          payer: payer.keypair.publicKey,
+          issuer: issuer.keypair.publicKey,
+          authority: issuer.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([issuer.keypair])
+        .rpc();
+
+    const payInvoice = async (
+      nonce: number,
+      amount: number,
+      feeBearer: object = { receiver: {} },
+      by = payer
+    ) => {
+      const profile = await program.account.userProfile.fetch(by.profile);
+      const transferRequest = pda(
+        Buffer.from("transfer_request"),
+        by.keypair.publicKey.toBuffer(),
+        issuer.keypair.publicKey.toBuffer(),
+        profile.transferNonce.toArrayLike(Buffer, "le", 8)
+      );
+
+      await program.methods
+        .payInvoice(usdc(amount), feeBearer, null)
+        .accountsPartial({
+          invoice: invoicePda(nonce),
+          payerProfile: by.profile,
+          issuerProfile: issuer.profile,
+          transferRequest,
+          payerTokenAccount: by.tokenAccount,
+          issuerTokenAccount: issuer.tokenAccount,
+          platformFeeAccount,
+          referralRewards: null,
+          referralVault: null,
+          authority: by.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([by.keypair])
+        .rpc();
+      return transferRequest;
+    };
+
+    before(async () => {
+      payer = await newUser("USA", 200, { kyc: true });
+      issuer = await newUser("NGA", 0);
+      await createInvoice(0, 100, (await chainTime()) + 3600);
+    });
+
+    it("Records a part payment as a completed transfer", async () => {
+      const transferRequest = await payInvoice(0, 40);
+
+      // The issuer bears the 0.5% fee by default
+      assert.equal(await balanceOf(issuer.tokenAccount), usdc(39.8).toNumber());
+      const invoice = await program.account.invoice.fetch(invoicePda(0));
+      assert.deepEqual(invoice.status, { partiallyPaid: {} });
+      assert.equal(invoice.amountPaid.toNumber(), usdc(40).toNumber());
+      assert.equal(invoice.lastTransfer.toString(), transferRequest.toString());
+
+      const request = await program.account.transferRequest.fetch(transferRequest);
+      assert.deepEqual(request.status, { completed: {} });
+      assert.equal(request.netAmount.toNumber(), usdc(39.8).toNumber());
+    });
+
+    it("Only lets the named payer settle the invoice", async () => {
+      const stranger = await newUser("USA", 100, { kyc: true });
+      await expectError(payInvoice(0, 10, { receiver: {} }, stranger), "Unauthorized");
+    });
+
+    it("Rejects payments above the outstanding amount", async () => {
+      await expectError(payInvoice(0, 61), "InvoiceOverpayment");
+    });
+
+    it("Charges the payer the fee on top when the sender bears it", async () => {
+      const payerBefore = await balanceOf(payer.tokenAccount);
+      const treasuryBefore = await balanceOf(platformFeeAccount);
+      await payInvoice(0, 60, { sender: {} });
+
+      assert.equal(payerBefore - (await balanceOf(payer.tokenAccount)), usdc(60.3).toNumber());
+      assert.equal(await balanceOf(issuer.tokenAccount), usdc(99.8).toNumber());
+      assert.equal((await balanceOf(platformFeeAccount)) - treasuryBefore, usdc(0.3).toNumber());
+
+      const invoice = await program.account.invoice.fetch(invoicePda(0));
+      assert.deepEqual(invoice.status, { paid: {} });
+      assert.isNotNull(invoice.paidAt);
+      await expectError(payInvoice(0, 1), "InvalidInvoiceStatus");
+    });
+
+    it("Flags an unpaid invoice once it is past due", async () => {
+      const markOverdue = (nonce: number) =>
+        program.methods.markInvoiceOverdue().accountsPartial({ invoice: invoicePda(nonce) }).rpc();
+
+      await createInvoice(1, 50, (await chainTime()) + 3600);
+      await expectError(markOverdue(1), "InvoiceNotOverdue");
+
+      await createInvoice(2, 50, (await chainTime()) - 60);
+      await markOverdue(2);
+      const invoice = await program.account.invoice.fetch(invoicePda(2));
+      assert.deepEqual(invoice.status, { overdue: {} });
+    });
+  });
+
+  describe("Claimable Transfers", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let claimant: Awaited<ReturnType<typeof newUser>>;
+    let impostor: Awaited<ReturnType<typeof newUser>>;
+    const secret = randomBytes(32);
+
+    const sha256 = (...parts: Buffer[]) =>
+      Array.from(createHash("sha256").update(Buffer.concat(parts)).digest());
+
+    const claimablePda = (nonce: number) =>
+      pda(
+        Buffer.from("claimable_transfer"),
+        payer.keypair.publicKey.toBuffer(),
+        new anchor.BN(nonce).toArrayLike(Buffer, "le", 8)
+      );
+    const escrowPda = (nonce: number) =>
+      pda(Buffer.from("claimable_escrow"), claimablePda(nonce).toBuffer());
+
+    const createClaimable = (nonce: number, amount: number, expiresAt: number) =>
+      program.methods
+        .createClaimableTransfer(
+          new anchor.BN(nonce),
+          usdc(amount),
+          sha256(secret),
+          new anchor.BN(expiresAt),
+          null
+        )
+        .accountsPartial({
+          senderProfile: payer.profile,
+          claimableTransfer: claimablePda(nonce),
+          claimableEscrow: escrowPda(nonce),
+          senderTokenAccount: payer.tokenAccount,
+          mint: usdcMint,
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+
+    const commit = (nonce: number, user: Awaited<ReturnType<typeof newUser>>, commitment: number[]) =>
+      program.methods
+        .commitClaim(commitment)
+        .accountsPartial({
+          claimableTransfer: claimablePda(nonce),
+          receiverProfile: user.profile,
+          authority: user.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user.keypair])
+        .rpc();
+
+    const claim = (nonce: number, user: Awaited<ReturnType<typeof newUser>>, reveal: Buffer) =>
+      program.methods
+        .claimTransfer(reveal)
+        .accountsPartial({
+          claimableTransfer: claimablePda(nonce),
+          claimableEscrow: escrowPda(nonce),
+          senderProfile: payer.profile,
+          receiverProfile: user.profile,
+          receiverTokenAccount: user.tokenAccount,
+          platformFeeAccount,
+          referralRewards: null,
+          referralVault: null,
+          authority: user.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user.keypair])
+        .rpc();
+
+    before(async () => {
+      payer = await newUser("USA", 100, { kyc: true });
+      claimant = await newUser("NGA", 0);
+      impostor = await newUser("NGA", 0);
+      await createClaimable(0, 20, (await chainTime()) + 3600);
+    });
+
+    it("Escrows the gross amount until it is claimed", async () => {
+      assert.equal(await balanceOf(escrowPda(0)), usdc(20).toNumber());
+      const claimable = await program.account.claimableTransfer.fetch(claimablePda(0));
+      assert.deepEqual(claimable.status, { pending: {} });
+      assert.equal(claimable.netAmount.toNumber(), usdc(19.9).toNumber());
+    });
+
+    it("Rejects a reveal that does not match the signer's commitment", async () => {
+      // Committing before the secret is public means guessing it
+      await commit(0, impostor, sha256(randomBytes(32), impostor.keypair.publicKey.toBuffer()));
+      await sleep(1000);
+
+      await expectError(claim(0, impostor, secret), "InvalidClaimSecret");
+    });
+
+    it("Pays the committed claimant once the secret is revealed", async () => {
+      await commit(0, claimant, sha256(secret, claimant.keypair.publicKey.toBuffer()));
+      await sleep(1000);
+
+      const treasuryBefore = await balanceOf(platformFeeAccount);
+      await claim(0, claimant, secret);
+
+      assert.equal(await balanceOf(claimant.tokenAccount), usdc(19.9).toNumber());
+      assert.equal((await balanceOf(platformFeeAccount)) - treasuryBefore, usdc(0.1).toNumber());
+      const claimable = await program.account.claimableTransfer.fetch(claimablePda(0));
+      assert.deepEqual(claimable.status, { claimed: {} });
+      assert.equal(claimable.claimedBy.toString(), claimant.keypair.publicKey.toString());
+    });
+
+    it("Returns an unclaimed transfer to the sender only after it expires", async () => {
+      const reclaim = () =>
+        program.methods
+          .reclaimTransfer()
+          .accountsPartial({
+            claimableTransfer: claimablePda(1),
+            claimableEscrow: escrowPda(1),
+            senderTokenAccount: payer.tokenAccount,
+            authority: payer.keypair.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payer.keypair])
+          .rpc();
+
+      await createClaimable(1, 30, (await chainTime()) + 2);
+      assert.equal(await balanceOf(payer.tokenAccount), usdc(50).toNumber());
+      await expectError(reclaim(), "ClaimNotExpired");
+
+      await sleep(3000);
+      await reclaim();
+      assert.equal(await balanceOf(payer.tokenAccount), usdc(80).toNumber());
+      const claimable = await program.account.claimableTransfer.fetch(claimablePda(1));
+      assert.deepEqual(claimable.status, { reclaimed: {} });
+    });
+  });
+
+  describe("Split Transfers", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let payees: Awaited<ReturnType<typeof newUser>>[];
+
+    // [receiver_profile, receiver_token_account, corridor, receiver_denial, referral_rewards]
+    const legAccounts = (payee: Awaited<ReturnType<typeof newUser>>) =>
+      [
+        payee.profile,
+        payee.tokenAccount,
+        pda(Buffer.from("corridor"), Buffer.from("USA"), Buffer.from("NGA")),
+        pda(Buffer.from("denied_address"), payee.keypair.publicKey.toBuffer()),
+        payee.profile,
+      ].map((pubkey, index) => ({ pubkey, isSigner: false, isWritable: index !== 3 }));
+
+    const initiateSplit = async (amount: anchor.BN, sharesBps: number[]) => {
+      const profile = await program.account.userProfile.fetch(payer.profile);
+      const transferRequest = pda(
+        Buffer.from("transfer_request"),
+        payer.keypair.publicKey.toBuffer(),
+        payees[0].keypair.publicKey.toBuffer(),
+        profile.transferNonce.toArrayLike(Buffer, "le", 8)
+      );
+
+      await program.methods
+        .initiateSplitTransfer(
+          amount,
+          sharesBps.map((shareBps, i) => ({ receiver: payees[i].keypair.publicKey, shareBps }))
+        )
+        .accountsPartial({
+          senderProfile: payer.profile,
+          transferRequest,
+          senderTokenAccount: payer.tokenAccount,
+          mint: usdcMint,
+          leadReceiver: payees[0].keypair.publicKey,
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer.keypair])
+        .rpc();
+      return transferRequest;
+    };
+
+    const confirmSplit = (transferRequest: PublicKey, legs: Awaited<ReturnType<typeof newUser>>[]) =>
+      program.methods
+        .confirmSplitTransfer()
+        .accountsPartial({
+          transferRequest,
+          senderProfile: payer.profile,
+          senderTokenAccount: payer.tokenAccount,
+          platformFeeAccount,
+          referralVault: null,
+          sender: payer.keypair.publicKey,
+          authority: payer.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(legs.reduce((all, payee) => all.concat(legAccounts(payee)), []))
+        .signers([payer.keypair])
+        .rpc();
+
+    before(async () => {
+      payer = await newUser("USA", 100, { kyc: true });
+      payees = [];
+      for (let i = 0; i < 3; i++) {
+        payees.push(await newUser("NGA", 0));
+      }
+    });
+
+    it("Rejects shares that don't add up to the whole transfer", async () => {
+      await expectError(initiateSplit(usdc(10), [5000, 3000, 1000]), "InvalidSplit");
+    });
+
+    it("Pays every share and gives the rounding dust to the lead receiver", async () => {
+      // 1 USDC less the 0.5% fee leaves 995,000 base units, which thirds don't divide
+      const transferRequest = await initiateSplit(usdc(1), [3334, 3333, 3333]);
+      await expectError(
+        confirmSplit(transferRequest, [payees[1], payees[0], payees[2]]),
+        "InvalidReceiverAccount"
+      );
+
+      const treasuryBefore = await balanceOf(platformFeeAccount);
+      await confirmSplit(transferRequest, payees);
+
+      assert.equal(await balanceOf(payees[0].tokenAccount), 331_734);
+      assert.equal(await balanceOf(payees[1].tokenAccount), 331_633);
+      assert.equal(await balanceOf(payees[2].tokenAccount), 331_633);
+      assert.equal((await balanceOf(platformFeeAccount)) - treasuryBefore, 5_000);
+      assert.equal(await balanceOf(payer.tokenAccount), usdc(99).toNumber());
+
+      const request = await program.account.transferRequest.fetch(transferRequest);
+      assert.deepEqual(request.status, { completed: {} });
+    });
+  });
+
+  describe("Corridors", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let payee: Awaited<ReturnType<typeof newUser>>;
+    // A route no other flow uses, so its limits don't leak into them
+    const corridor = pda(Buffer.from("corridor"), Buffer.from("GBR"), Buffer.from("GHA"));
+
+    const updateCorridor = (enabled: boolean) =>
+      program.methods
+        .updateCorridor(enabled, 100, usdc(5), usdc(50), usdc(60))
+        .accountsPartial({ corridor, admin: provider.wallet.publicKey })
+        .rpc();
+
+    before(async () => {
+      payer = await newUser("GBR", 100, { kyc: true });
+      payee = await newUser("GHA", 0);
+    });
+
+    it("Only lets the platform admin configure a corridor", async () => {
+      const stranger = await fundedKeypair();
+      await expectError(
+        program.methods
+          .createCorridor("GBR", "GHA", true, 100, usdc(5), usdc(50), usdc(60))
+          .accountsPartial({ corridor, admin: stranger.publicKey, systemProgram: SystemProgram.programId })
+          .signers([stranger])
+          .rpc(),
+        "Unauthorized"
+      );
+
+      // 1% fee, 5 to 50 USDC per transfer and 60 USDC a day
+      await program.methods
+        .createCorridor("GBR", "GHA", true, 100, usdc(5), usdc(50), usdc(60))
+        .accountsPartial({
+          corridor,
+          admin: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("Charges the corridor fee instead of the platform default", async () => {
+      const transferRequest = await initiateTransfer(payer, payee, 20);
+      const request = await program.account.transferRequest.fetch(transferRequest);
+      assert.equal(request.feeBps, 100);
+      assert.equal(request.platformFee.toNumber(), usdc(0.2).toNumber());
+
+      await confirmTransfer(payer, payee, transferRequest);
+      assert.equal(await balanceOf(payee.tokenAccount), usdc(19.8).toNumber());
+      const stored = await program.account.corridor.fetch(corridor);
+      assert.equal(stored.dailyVolume.toNumber(), usdc(20).toNumber());
+    });
+
+    it("Rejects amounts outside the corridor limits", async () => {
+      await expectError(initiateTransfer(payer, payee, 4), "AmountOutsideCorridorLimits");
+      await expectError(initiateTransfer(payer, payee, 51), "AmountOutsideCorridorLimits");
+    });
+
+    it("Stops transfers once the daily cap would be exceeded", async () => {
+      await expectError(initiateTransfer(payer, payee, 45), "CorridorDailyCapExceeded");
+    });
+
+    it("Re-checks the corridor when a pending transfer is confirmed", async () => {
+      const transferRequest = await initiateTransfer(payer, payee, 10);
+      await updateCorridor(false);
+
+      await expectError(confirmTransfer(payer, payee, transferRequest), "CorridorDisabled");
+      await expectError(initiateTransfer(payer, payee, 10), "CorridorDisabled");
+
+      await updateCorridor(true);
+      await confirmTransfer(payer, payee, transferRequest);
+      assert.equal(await balanceOf(payee.tokenAccount), usdc(29.7).toNumber());
+    });
+  });
+
+  describe("Travel Rule", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let payee: Awaited<ReturnType<typeof newUser>>;
+    const dataHash = Array(32).fill(9);
+
+    const updateThreshold = (threshold: number) =>
+      program.methods
+        .updateTravelRuleThreshold(usdc(threshold))
+        .accountsPartial({ admin: provider.wallet.publicKey })
+        .rpc();
+
+    const attach = (transferRequest: PublicKey, hash: number[], signer = payer) =>
+      program.methods
+        .attachTravelRule(hash, "ipfs://travel-rule/payload")
+        .accountsPartial({ transferRequest, authority: signer.keypair.publicKey })
+        .signers([signer.keypair])
+        .rpc();
+
+    before(async () => {
+      payer = await newUser("USA", 100, { kyc: true });
+      payee = await newUser("NGA", 0);
+      // Transfers of 30 USDC or more must carry originator and beneficiary data
+      await updateThreshold(30);
+    });
+
+    after(async () => {
+      await updateThreshold(0);
+    });
+
+    it("Lets transfers below the threshold through without data", async () => {
+      await confirmTransfer(payer, payee, await initiateTransfer(payer, payee, 10));
+      assert.equal(await balanceOf(payee.tokenAccount), usdc(9.95).toNumber());
+    });
+
+    it("Holds back a transfer at the threshold until data is attached", async () => {
+      const transferRequest = await initiateTransfer(payer, payee, 30);
+      await expectError(confirmTransfer(payer, payee, transferRequest), "TravelRuleDataRequired");
+
+      await expectError(attach(transferRequest, Array(32).fill(0)), "InvalidTravelRuleData");
+      await expectError(attach(transferRequest, dataHash, payee), "Unauthorized");
+      await attach(transferRequest, dataHash);
+
+      await confirmTransfer(payer, payee, transferRequest);
+      const request = await program.account.transferRequest.fetch(transferRequest);
+      assert.deepEqual(request.status, { completed: {} });
+      assert.deepEqual(request.travelRule.dataHash, dataHash);
+      assert.equal(request.travelRule.blobUri, "ipfs://travel-rule/payload");
+    });
+  });
+
+  describe("Volume Fee Tiers", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let payee: Awaited<ReturnType<typeof newUser>>;
+
+    const tier = (minVolume: number, feeBps: number) => ({ minVolume: usdc(minVolume), feeBps });
+    const updateFeeTiers = (tiers: ReturnType<typeof tier>[], admin: Keypair = provider.wallet.payer) =>
+      program.methods
+        .updateFeeTiers(tiers)
+        .accountsPartial({ admin: admin.publicKey })
+        .signers([admin])
+        .rpc();
+
+    before(async () => {
+      payer = await newUser("USA", 200, { kyc: true });
+      payee = await newUser("NGA", 0);
+    });
+
+    after(async () => {
+      // A single tier at the default rate leaves the later flows unchanged
+      await updateFeeTiers([tier(0, 50)]);
+    });
+
+    it("Requires tiers to start at zero volume", async () => {
+      const initialize = (tiers: ReturnType<typeof tier>[]) =>
+        program.methods
+          .initializeFeeSchedule(tiers)
+          .accountsPartial({ admin: provider.wallet.publicKey, systemProgram: SystemProgram.programId })
+          .rpc();
+
+      await expectError(initialize([tier(10, 50), tier(50, 25)]), "InvalidFeeTiers");
+      // 0.5% until 50 USDC has been sent in the window, 0.25% after
+      await initialize([tier(0, 50), tier(50, 25)]);
+    });
+
+    it("Only lets the platform admin change the tiers", async () => {
+      const stranger = await fundedKeypair();
+      await expectError(updateFeeTiers([tier(0, 10)], stranger), "Unauthorized");
+    });
+
+    it("Prices each transfer from the sender's volume before it", async () => {
+      const first = await initiateTransfer(payer, payee, 60);
+      assert.equal((await program.account.transferRequest.fetch(first)).feeBps, 50);
+      await confirmTransfer(payer, payee, first);
+
+      const second = await initiateTransfer(payer, payee, 20);
+      const request = await program.account.transferRequest.fetch(second);
+      assert.equal(request.feeBps, 25);
+      assert.equal(request.platformFee.toNumber(), usdc(0.05).toNumber());
+
+      await confirmTransfer(payer, payee, second);
+      assert.equal(await balanceOf(payee.tokenAccount), usdc(59.7 + 19.95).toNumber());
+    });
+  });
+
+  describe("Mint Fee Config", () => {
+    let payer: Awaited<ReturnType<typeof newUser>>;
+    let payee: Awaited<ReturnType<typeof newUser>>;
+    const mintFeeConfig = () => pda(Buffer.from("mint_fee_config"), usdcMint.toBuffer());
+
+    const updateMintFeeConfig = (minFee: number, maxFee: number, rounding: object) =>
+      program.methods
+        .updateMintFeeConfig(usdc(minFee), usdc(maxFee), rounding as any)
+        .accountsPartial({ mintFeeConfig: mintFeeConfig(), admin: provider.wallet.publicKey })
+        .rpc();
+
+    const feeOn = async (amount: number) => {
+      const transferRequest = await initiateTransfer(payer, payee, amount);
+      return (await program.account.transferRequest.fetch(transferRequest)).platformFee.toNumber();
+    };
+
+    before(async () => {
+      payer = await newUser("USA", 400, { kyc: true });
+      payee = await newUser("NGA", 0);
+    });
+
+    after(async () => {
+      // No bounds with upward rounding is how an unconfigured mint is priced
+      await updateMintFeeConfig(0, 0, { up: {} });
+    });
+
+    it("Rejects a floor above the ceiling", async () => {
+      const initialize = (minFee: number, maxFee: number) =>
+        program.methods
+          .initializeMintFeeConfig(usdc(minFee), usdc(maxFee), { down: {} })
+          .accountsPartial({
+            mintFeeConfig: mintFeeConfig(),
+            mint: usdcMint,
+            admin: provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+      await expectError(initialize(2, 1), "InvalidMintFeeConfig");
+      // Fees of 0.1 to 1 USDC, rounded down
+      await initialize(0.1, 1);
+    });
+
+    it("Raises small fees to the floor", async () => {
+      assert.equal(await feeOn(10), usdc(0.1).toNumber());
+      await expectError(initiateTransfer(payer, payee, 0.05), "AmountBelowMinimumFee");
+    });
+
+    it("Caps large fees at the ceiling", async () => {
+      const transferRequest = await initiateTransfer(payer, payee, 300);
+      const treasuryBefore = await balanceOf(platformFeeAccount);
+      await confirmTransfer(payer, payee, transferRequest);
+
+      assert.equal(await balanceOf(payee.tokenAccount), usdc(299).toNumber());
+      assert.equal((await balanceOf(platformFeeAccount)) - treasuryBefore, usdc(1).toNumber());
+    });
+
+    it("Rounds fractional fees the configured way", async () => {
+      // 150 base units at 0.5% is 0.75 of a base unit
+      await updateMintFeeConfig(0, 0, { down: {} });
+      assert.equal(await feeOn(0.00015), 0);
+      await updateMintFeeConfig(0, 0, { halfUp: {} });
+      assert.equal(await feeOn(0.00015), 1);
+      await updateMintFeeConfig(0, 0, { up: {} });
+      assert.equal(await feeOn(0.00005), 1);
+    });
+  });
+
   describe("Complete Flow Summary", () => {
     it("Shows final balances", async () => {
       const senderBalance = await getAccount(
@@ -582,7 +3004,7 @@ describe("crosspay", () => {
       // Sender: 1000 - 100 = 900 USDC
       // Receiver: 500 + 99.5 - 50 = 549.5 USDC (after 0.5% fee on incoming transfer)
       // LP: 0 + 50 = 50 USDC
-      assert.equal(Number(senderBalance.amount), 900.5 * 10 ** 6);
+      assert.equal(Number(senderBalance.amount), 900 * 10 ** 6);
       assert.equal(Number(lpBalance.amount), 50 * 10 ** 6);
 
       console.log("\n✅ All flows completed successfully!");